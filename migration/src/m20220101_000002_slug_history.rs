@@ -0,0 +1,212 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+
+        // Posts were stored with the raw title as slug, normalise them before enforcing uniqueness
+        db.execute_unprepared(
+            r#"
+            UPDATE posts SET slug = trim(both '-' from regexp_replace(replace(lower(title), ' ', '-'), '[^[:alnum:]-]', '', 'g'));
+            UPDATE posts SET slug = 'post-' || id WHERE slug = '';
+            UPDATE posts p SET slug = p.slug || '-' || p.id
+                WHERE EXISTS (SELECT 1 FROM posts o WHERE o.slug = p.slug AND o.id < p.id);
+            "#,
+        )
+        .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-posts-slug")
+                    .table(Posts::Table)
+                    .col(Posts::Slug)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        // Add slug to categories
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Categories::Table)
+                    .add_column(
+                        ColumnDef::new(Categories::Slug)
+                            .string()
+                            .not_null()
+                            .default(""),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        db.execute_unprepared(
+            r#"
+            UPDATE categories SET slug = trim(both '-' from regexp_replace(replace(lower(name), ' ', '-'), '[^[:alnum:]-]', '', 'g'));
+            UPDATE categories SET slug = 'category-' || id WHERE slug = '';
+            UPDATE categories c SET slug = c.slug || '-' || c.id
+                WHERE EXISTS (SELECT 1 FROM categories o WHERE o.slug = c.slug AND o.id < c.id);
+            "#,
+        )
+        .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-categories-slug")
+                    .table(Categories::Table)
+                    .col(Categories::Slug)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        // Create post_slug_history table
+        manager
+            .create_table(
+                Table::create()
+                    .table(PostSlugHistory::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(PostSlugHistory::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(PostSlugHistory::PostId).integer().not_null())
+                    .col(
+                        ColumnDef::new(PostSlugHistory::Slug)
+                            .string()
+                            .unique_key()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(PostSlugHistory::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-post_slug_history-post_id")
+                            .from(PostSlugHistory::Table, PostSlugHistory::PostId)
+                            .to(Posts::Table, Posts::Id)
+                            .on_update(ForeignKeyAction::Cascade)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // Create category_slug_history table
+        manager
+            .create_table(
+                Table::create()
+                    .table(CategorySlugHistory::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(CategorySlugHistory::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(CategorySlugHistory::CategoryId)
+                            .integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(CategorySlugHistory::Slug)
+                            .string()
+                            .unique_key()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(CategorySlugHistory::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-category_slug_history-category_id")
+                            .from(CategorySlugHistory::Table, CategorySlugHistory::CategoryId)
+                            .to(Categories::Table, Categories::Id)
+                            .on_update(ForeignKeyAction::Cascade)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(CategorySlugHistory::Table).to_owned())
+            .await?;
+        manager
+            .drop_table(Table::drop().table(PostSlugHistory::Table).to_owned())
+            .await?;
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx-categories-slug")
+                    .table(Categories::Table)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Categories::Table)
+                    .drop_column(Categories::Slug)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .drop_index(Index::drop().name("idx-posts-slug").table(Posts::Table).to_owned())
+            .await?;
+        Ok(())
+    }
+}
+
+#[derive(Iden)]
+enum Categories {
+    Table,
+    Id,
+    Slug,
+}
+
+#[derive(Iden)]
+enum Posts {
+    Table,
+    Id,
+    Slug,
+}
+
+#[derive(Iden)]
+enum PostSlugHistory {
+    Table,
+    Id,
+    PostId,
+    Slug,
+    CreatedAt,
+}
+
+#[derive(Iden)]
+enum CategorySlugHistory {
+    Table,
+    Id,
+    CategoryId,
+    Slug,
+    CreatedAt,
+}
@@ -0,0 +1,100 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Create tags table
+        manager
+            .create_table(
+                Table::create()
+                    .table(Tags::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(Tags::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(Tags::Name).string().not_null())
+                    .col(ColumnDef::new(Tags::Slug).string().unique_key().not_null())
+                    .to_owned(),
+            )
+            .await?;
+
+        // Create post_tags table
+        manager
+            .create_table(
+                Table::create()
+                    .table(PostTags::Table)
+                    .if_not_exists()
+                    .col(ColumnDef::new(PostTags::PostId).integer().not_null())
+                    .col(ColumnDef::new(PostTags::TagId).integer().not_null())
+                    .primary_key(Index::create().col(PostTags::PostId).col(PostTags::TagId))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-post_tag-post_id")
+                            .from(PostTags::Table, PostTags::PostId)
+                            .to(Posts::Table, Posts::Id)
+                            .on_update(ForeignKeyAction::Cascade)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-post_tag-tag_id")
+                            .from(PostTags::Table, PostTags::TagId)
+                            .to(Tags::Table, Tags::Id)
+                            .on_update(ForeignKeyAction::Cascade)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-post_tags-tag_id")
+                    .table(PostTags::Table)
+                    .col(PostTags::TagId)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(PostTags::Table).to_owned())
+            .await?;
+        manager
+            .drop_table(Table::drop().table(Tags::Table).to_owned())
+            .await?;
+        Ok(())
+    }
+}
+
+#[derive(Iden)]
+enum Posts {
+    Table,
+    Id,
+}
+
+#[derive(Iden)]
+enum Tags {
+    Table,
+    Id,
+    Name,
+    Slug,
+}
+
+#[derive(Iden)]
+enum PostTags {
+    Table,
+    PostId,
+    TagId,
+}
\ No newline at end of file
@@ -0,0 +1,47 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Existing bodies are treated as plain text, body_html is filled in by the startup backfill
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Posts::Table)
+                    .add_column(
+                        ColumnDef::new(Posts::BodyFormat)
+                            .string_len(16)
+                            .not_null()
+                            .default("plain"),
+                    )
+                    .add_column(ColumnDef::new(Posts::BodyHtml).text().null())
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Posts::Table)
+                    .drop_column(Posts::BodyFormat)
+                    .drop_column(Posts::BodyHtml)
+                    .to_owned(),
+            )
+            .await?;
+        Ok(())
+    }
+}
+
+#[derive(Iden)]
+enum Posts {
+    Table,
+    BodyFormat,
+    BodyHtml,
+}
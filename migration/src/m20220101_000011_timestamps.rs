@@ -0,0 +1,118 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        for table in [Users::Table.into_iden(), Posts::Table.into_iden(), Categories::Table.into_iden(), Comments::Table.into_iden()] {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(table)
+                        .add_column(
+                            ColumnDef::new(Timestamps::CreatedAt)
+                                .timestamp_with_time_zone()
+                                .not_null()
+                                .default(Expr::current_timestamp()),
+                        )
+                        .add_column(
+                            ColumnDef::new(Timestamps::UpdatedAt)
+                                .timestamp_with_time_zone()
+                                .not_null()
+                                .default(Expr::current_timestamp()),
+                        )
+                        .to_owned(),
+                )
+                .await?;
+        }
+
+        // Posts have a revision history, the best record of when they were written and last edited
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"
+                UPDATE posts p SET
+                    created_at = COALESCE(r.first_revision, p.published_at, p.created_at),
+                    updated_at = COALESCE(r.last_revision, p.published_at, p.updated_at)
+                FROM (
+                    SELECT post_id, MIN(created_at) AS first_revision, MAX(created_at) AS last_revision
+                    FROM post_revisions GROUP BY post_id
+                ) r
+                WHERE r.post_id = p.id
+                "#,
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-posts-created_at")
+                    .table(Posts::Table)
+                    .col(Timestamps::CreatedAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-posts-updated_at")
+                    .table(Posts::Table)
+                    .col(Timestamps::UpdatedAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(Index::drop().name("idx-posts-updated_at").table(Posts::Table).to_owned())
+            .await?;
+        manager
+            .drop_index(Index::drop().name("idx-posts-created_at").table(Posts::Table).to_owned())
+            .await?;
+
+        for table in [Users::Table.into_iden(), Posts::Table.into_iden(), Categories::Table.into_iden(), Comments::Table.into_iden()] {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(table)
+                        .drop_column(Timestamps::CreatedAt)
+                        .drop_column(Timestamps::UpdatedAt)
+                        .to_owned(),
+                )
+                .await?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Iden)]
+enum Users {
+    Table,
+}
+
+#[derive(Iden)]
+enum Posts {
+    Table,
+}
+
+#[derive(Iden)]
+enum Categories {
+    Table,
+}
+
+#[derive(Iden)]
+enum Comments {
+    Table,
+}
+
+#[derive(Iden)]
+enum Timestamps {
+    CreatedAt,
+    UpdatedAt,
+}
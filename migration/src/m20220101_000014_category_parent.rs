@@ -0,0 +1,69 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Purging a category moves its children up to the top level
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Categories::Table)
+                    .add_column(ColumnDef::new(Categories::ParentId).integer().null())
+                    .add_foreign_key(
+                        TableForeignKey::new()
+                            .name("fk-categories-parent_id")
+                            .from_tbl(Categories::Table)
+                            .from_col(Categories::ParentId)
+                            .to_tbl(Categories::Table)
+                            .to_col(Categories::Id)
+                            .on_update(ForeignKeyAction::Cascade)
+                            .on_delete(ForeignKeyAction::SetNull),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-categories-parent_id")
+                    .table(Categories::Table)
+                    .col(Categories::ParentId)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx-categories-parent_id")
+                    .table(Categories::Table)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Categories::Table)
+                    .drop_foreign_key(Alias::new("fk-categories-parent_id"))
+                    .drop_column(Categories::ParentId)
+                    .to_owned(),
+            )
+            .await?;
+        Ok(())
+    }
+}
+
+#[derive(Iden)]
+enum Categories {
+    Table,
+    Id,
+    ParentId,
+}
@@ -0,0 +1,115 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Comments written before moderation existed stay visible
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Comments::Table)
+                    .add_column(
+                        ColumnDef::new(Comments::Status)
+                            .string_len(16)
+                            .not_null()
+                            .default("approved"),
+                    )
+                    .add_column(ColumnDef::new(Comments::UserId).integer().null())
+                    .add_foreign_key(
+                        TableForeignKey::new()
+                            .name("fk-comments-user_id")
+                            .from_tbl(Comments::Table)
+                            .from_col(Comments::UserId)
+                            .to_tbl(Users::Table)
+                            .to_col(Users::Id)
+                            .on_update(ForeignKeyAction::Cascade)
+                            .on_delete(ForeignKeyAction::SetNull),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-comments-status")
+                    .table(Comments::Table)
+                    .col(Comments::Status)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-comments-user_id")
+                    .table(Comments::Table)
+                    .col(Comments::UserId)
+                    .to_owned(),
+            )
+            .await?;
+
+        // NULL follows the global COMMENT_REQUIRE_APPROVAL setting
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Categories::Table)
+                    .add_column(ColumnDef::new(Categories::CommentModeration).boolean().null())
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Categories::Table)
+                    .drop_column(Categories::CommentModeration)
+                    .to_owned(),
+            )
+            .await?;
+
+        for name in ["idx-comments-user_id", "idx-comments-status"] {
+            manager
+                .drop_index(Index::drop().name(name).table(Comments::Table).to_owned())
+                .await?;
+        }
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Comments::Table)
+                    .drop_foreign_key(Alias::new("fk-comments-user_id"))
+                    .drop_column(Comments::UserId)
+                    .drop_column(Comments::Status)
+                    .to_owned(),
+            )
+            .await?;
+        Ok(())
+    }
+}
+
+#[derive(Iden)]
+enum Comments {
+    Table,
+    Status,
+    UserId,
+}
+
+#[derive(Iden)]
+enum Users {
+    Table,
+    Id,
+}
+
+#[derive(Iden)]
+enum Categories {
+    Table,
+    CommentModeration,
+}
@@ -0,0 +1,111 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Each row keeps the text a comment had before one of its edits
+        manager
+            .create_table(
+                Table::create()
+                    .table(CommentRevisions::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(CommentRevisions::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(CommentRevisions::CommentId).integer().not_null())
+                    .col(ColumnDef::new(CommentRevisions::UserId).integer().null())
+                    .col(ColumnDef::new(CommentRevisions::Comment).text().not_null())
+                    .col(
+                        ColumnDef::new(CommentRevisions::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-comment_revision-comment_id")
+                            .from(CommentRevisions::Table, CommentRevisions::CommentId)
+                            .to(Comments::Table, Comments::Id)
+                            .on_update(ForeignKeyAction::Cascade)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-comment_revision-user_id")
+                            .from(CommentRevisions::Table, CommentRevisions::UserId)
+                            .to(Users::Table, Users::Id)
+                            .on_update(ForeignKeyAction::Cascade)
+                            .on_delete(ForeignKeyAction::SetNull),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-comment_revisions-comment_id")
+                    .table(CommentRevisions::Table)
+                    .col(CommentRevisions::CommentId)
+                    .to_owned(),
+            )
+            .await?;
+
+        // Only set by edits, moderation and restores leave it alone
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Comments::Table)
+                    .add_column(ColumnDef::new(Comments::EditedAt).timestamp_with_time_zone().null())
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Comments::Table)
+                    .drop_column(Comments::EditedAt)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .drop_table(Table::drop().table(CommentRevisions::Table).to_owned())
+            .await?;
+        Ok(())
+    }
+}
+
+#[derive(Iden)]
+enum Users {
+    Table,
+    Id,
+}
+
+#[derive(Iden)]
+enum Comments {
+    Table,
+    Id,
+    EditedAt,
+}
+
+#[derive(Iden)]
+enum CommentRevisions {
+    Table,
+    Id,
+    CommentId,
+    UserId,
+    Comment,
+    CreatedAt,
+}
@@ -0,0 +1,216 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(UserFollows::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(UserFollows::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(UserFollows::FollowerId).integer().not_null())
+                    .col(ColumnDef::new(UserFollows::FollowedId).integer().not_null())
+                    .col(
+                        ColumnDef::new(UserFollows::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-user_follow-follower_id")
+                            .from(UserFollows::Table, UserFollows::FollowerId)
+                            .to(Users::Table, Users::Id)
+                            .on_update(ForeignKeyAction::Cascade)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-user_follow-followed_id")
+                            .from(UserFollows::Table, UserFollows::FollowedId)
+                            .to(Users::Table, Users::Id)
+                            .on_update(ForeignKeyAction::Cascade)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // Leading with the follower lets the feed read a reader's follows straight from the index
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-user_follows-follower_id-followed_id")
+                    .table(UserFollows::Table)
+                    .col(UserFollows::FollowerId)
+                    .col(UserFollows::FollowedId)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-user_follows-followed_id")
+                    .table(UserFollows::Table)
+                    .col(UserFollows::FollowedId)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(CategoryFollows::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(CategoryFollows::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(CategoryFollows::UserId).integer().not_null())
+                    .col(ColumnDef::new(CategoryFollows::CategoryId).integer().not_null())
+                    .col(
+                        ColumnDef::new(CategoryFollows::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-category_follow-user_id")
+                            .from(CategoryFollows::Table, CategoryFollows::UserId)
+                            .to(Users::Table, Users::Id)
+                            .on_update(ForeignKeyAction::Cascade)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-category_follow-category_id")
+                            .from(CategoryFollows::Table, CategoryFollows::CategoryId)
+                            .to(Categories::Table, Categories::Id)
+                            .on_update(ForeignKeyAction::Cascade)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-category_follows-user_id-category_id")
+                    .table(CategoryFollows::Table)
+                    .col(CategoryFollows::UserId)
+                    .col(CategoryFollows::CategoryId)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        // The feed walks each followed author or category newest first and stops after one page
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-posts-user_id-published_at-id")
+                    .table(Posts::Table)
+                    .col(Posts::UserId)
+                    .col(Posts::PublishedAt)
+                    .col(Posts::Id)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-posts-category_id-published_at-id")
+                    .table(Posts::Table)
+                    .col(Posts::CategoryId)
+                    .col(Posts::PublishedAt)
+                    .col(Posts::Id)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx-posts-category_id-published_at-id")
+                    .table(Posts::Table)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx-posts-user_id-published_at-id")
+                    .table(Posts::Table)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .drop_table(Table::drop().table(CategoryFollows::Table).to_owned())
+            .await?;
+        manager
+            .drop_table(Table::drop().table(UserFollows::Table).to_owned())
+            .await?;
+        Ok(())
+    }
+}
+
+#[derive(Iden)]
+enum Users {
+    Table,
+    Id,
+}
+
+#[derive(Iden)]
+enum Categories {
+    Table,
+    Id,
+}
+
+#[derive(Iden)]
+enum Posts {
+    Table,
+    Id,
+    UserId,
+    CategoryId,
+    PublishedAt,
+}
+
+#[derive(Iden)]
+enum UserFollows {
+    Table,
+    Id,
+    FollowerId,
+    FollowedId,
+    CreatedAt,
+}
+
+#[derive(Iden)]
+enum CategoryFollows {
+    Table,
+    Id,
+    UserId,
+    CategoryId,
+    CreatedAt,
+}
@@ -0,0 +1,186 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Notifications::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(Notifications::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(Notifications::UserId).integer().not_null())
+                    .col(ColumnDef::new(Notifications::ActorId).integer().null())
+                    .col(ColumnDef::new(Notifications::Kind).string_len(16).not_null())
+                    .col(ColumnDef::new(Notifications::PostId).integer().null())
+                    .col(ColumnDef::new(Notifications::CommentId).integer().null())
+                    .col(ColumnDef::new(Notifications::Message).text().not_null())
+                    .col(ColumnDef::new(Notifications::ReadAt).timestamp_with_time_zone().null())
+                    .col(
+                        ColumnDef::new(Notifications::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-notification-user_id")
+                            .from(Notifications::Table, Notifications::UserId)
+                            .to(Users::Table, Users::Id)
+                            .on_update(ForeignKeyAction::Cascade)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-notification-actor_id")
+                            .from(Notifications::Table, Notifications::ActorId)
+                            .to(Users::Table, Users::Id)
+                            .on_update(ForeignKeyAction::Cascade)
+                            .on_delete(ForeignKeyAction::SetNull),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-notification-post_id")
+                            .from(Notifications::Table, Notifications::PostId)
+                            .to(Posts::Table, Posts::Id)
+                            .on_update(ForeignKeyAction::Cascade)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-notification-comment_id")
+                            .from(Notifications::Table, Notifications::CommentId)
+                            .to(Comments::Table, Comments::Id)
+                            .on_update(ForeignKeyAction::Cascade)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // Serves the newest first listing and the unread count of a user
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-notifications-user_id-created_at")
+                    .table(Notifications::Table)
+                    .col(Notifications::UserId)
+                    .col(Notifications::CreatedAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-notifications-user_id-read_at")
+                    .table(Notifications::Table)
+                    .col(Notifications::UserId)
+                    .col(Notifications::ReadAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        // Only opt outs need a row, a missing preference means the kind is enabled
+        manager
+            .create_table(
+                Table::create()
+                    .table(NotificationPreferences::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(NotificationPreferences::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(NotificationPreferences::UserId).integer().not_null())
+                    .col(ColumnDef::new(NotificationPreferences::Kind).string_len(16).not_null())
+                    .col(ColumnDef::new(NotificationPreferences::Enabled).boolean().not_null())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-notification_preference-user_id")
+                            .from(NotificationPreferences::Table, NotificationPreferences::UserId)
+                            .to(Users::Table, Users::Id)
+                            .on_update(ForeignKeyAction::Cascade)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-notification_preferences-user_id-kind")
+                    .table(NotificationPreferences::Table)
+                    .col(NotificationPreferences::UserId)
+                    .col(NotificationPreferences::Kind)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(NotificationPreferences::Table).to_owned())
+            .await?;
+        manager
+            .drop_table(Table::drop().table(Notifications::Table).to_owned())
+            .await?;
+        Ok(())
+    }
+}
+
+#[derive(Iden)]
+enum Users {
+    Table,
+    Id,
+}
+
+#[derive(Iden)]
+enum Posts {
+    Table,
+    Id,
+}
+
+#[derive(Iden)]
+enum Comments {
+    Table,
+    Id,
+}
+
+#[derive(Iden)]
+enum Notifications {
+    Table,
+    Id,
+    UserId,
+    ActorId,
+    Kind,
+    PostId,
+    CommentId,
+    Message,
+    ReadAt,
+    CreatedAt,
+}
+
+#[derive(Iden)]
+enum NotificationPreferences {
+    Table,
+    Id,
+    UserId,
+    Kind,
+    Enabled,
+}
@@ -0,0 +1,42 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Both stay NULL on older revisions, restoring one of those keeps the post's current format and tags
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(PostRevisions::Table)
+                    .add_column(ColumnDef::new(PostRevisions::BodyFormat).string_len(16).null())
+                    .add_column(ColumnDef::new(PostRevisions::Tags).json_binary().null())
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(PostRevisions::Table)
+                    .drop_column(PostRevisions::BodyFormat)
+                    .drop_column(PostRevisions::Tags)
+                    .to_owned(),
+            )
+            .await?;
+        Ok(())
+    }
+}
+
+#[derive(Iden)]
+enum PostRevisions {
+    Table,
+    BodyFormat,
+    Tags,
+}
@@ -0,0 +1,36 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use sea_orm::DbErr;
+
+use crate::{
+    domain::{ApiResponse, BookmarkFolderResponse, BookmarkListQuery, BookmarkPageResponse, BookmarkResponse, ErrorResponse, SaveBookmarkRequest},
+    entities::{bookmarks, posts},
+};
+
+pub type DynBookmarkRepository = Arc<dyn BookmarkRepositoryTrait + Send + Sync>;
+pub type DynBookmarkService = Arc<dyn BookmarkServiceTrait + Send + Sync>;
+
+#[async_trait]
+pub trait BookmarkRepositoryTrait {
+    async fn save(&self, user_id: i32, post_id: i32, folder: Option<String>, note: Option<String>) -> Result<bookmarks::Model, DbErr>;
+    async fn delete(&self, user_id: i32, post_id: i32) -> Result<u64, DbErr>;
+    // Bookmarks of published posts, newest first, with the total across all pages
+    async fn find_page(
+        &self,
+        user_id: i32,
+        folder: Option<&str>,
+        page: u64,
+        per_page: u64,
+    ) -> Result<(Vec<(bookmarks::Model, posts::Model)>, u64), DbErr>;
+    async fn find_folders(&self, user_id: i32) -> Result<Vec<(String, i64)>, DbErr>;
+    async fn find_bookmarked(&self, post_ids: &[i32], user_id: i32) -> Result<Vec<i32>, DbErr>;
+}
+
+#[async_trait]
+pub trait BookmarkServiceTrait {
+    async fn save_bookmark(&self, user_id: i32, post_id: i32, input: &SaveBookmarkRequest) -> Result<Option<ApiResponse<BookmarkResponse>>, ErrorResponse>;
+    async fn remove_bookmark(&self, user_id: i32, post_id: i32) -> Result<Option<ApiResponse<()>>, ErrorResponse>;
+    async fn get_bookmarks(&self, user_id: i32, query: &BookmarkListQuery) -> Result<ApiResponse<BookmarkPageResponse>, ErrorResponse>;
+    async fn get_folders(&self, user_id: i32) -> Result<ApiResponse<Vec<BookmarkFolderResponse>>, ErrorResponse>;
+}
@@ -1,9 +1,10 @@
 use std::sync::Arc;
 
+use chrono::{DateTime, Utc};
 use sea_orm::DbErr;
 use async_trait::async_trait;
 
-use crate::{domain::{ApiResponse, CategoryResponse, CreateCategoryRequest, ErrorResponse, UpdateCategoryRequest}, entities::categories};
+use crate::{domain::{ApiResponse, CategoryDeleteStrategy, CategoryDeletionResponse, CategoryMergeResponse, CategoryResponse, CategoryTreeResponse, CreateCategoryRequest, DeleteCategoryQuery, ErrorResponse, MergeCategoriesRequest, SlugLookup, UpdateCategoryRequest}, entities::categories};
 
 
 pub type DynCategoryRepository = Arc<dyn CategoryRepositoryTrait + Send + Sync>;
@@ -13,18 +14,31 @@ pub type DynCategoryService = Arc<dyn CategoryServiceTrait + Send + Sync>;
 
 #[async_trait]
 pub trait CategoryRepositoryTrait {
-    async fn find_all(&self) -> Result<Vec<categories::Model>, DbErr>;
-    async fn find_by_id(&self, id: i32) -> Result<Option<categories::Model>, DbErr>;
+    // Each category comes with its number of published posts
+    async fn find_all(&self) -> Result<Vec<(categories::Model, i64)>, DbErr>;
+    async fn find_by_id(&self, id: i32) -> Result<Option<(categories::Model, i64)>, DbErr>;
+    async fn find_by_slug(&self, slug: &str) -> Result<Option<(categories::Model, i64)>, DbErr>;
+    async fn find_slug_redirect(&self, slug: &str) -> Result<Option<String>, DbErr>;
     async fn create(&self, input: &CreateCategoryRequest) -> Result<categories::Model, DbErr>;
     async fn update(&self, input: &UpdateCategoryRequest) -> Result<categories::Model, DbErr>;
-    async fn delete(&self, id: i32) -> Result<(), DbErr>;
+    // Returns how many posts were reassigned or moved to the trash
+    async fn delete(&self, id: i32, strategy: CategoryDeleteStrategy, target_id: Option<i32>) -> Result<u64, DbErr>;
+    // Runs every step inside one transaction, which is rolled back again for a dry run
+    async fn merge(&self, input: &MergeCategoriesRequest) -> Result<CategoryMergeResponse, DbErr>;
+    async fn find_trashed(&self) -> Result<Vec<(categories::Model, i64)>, DbErr>;
+    async fn restore(&self, id: i32) -> Result<categories::Model, DbErr>;
+    async fn purge_trashed(&self, deleted_before: DateTime<Utc>) -> Result<u64, DbErr>;
 }
 
 #[async_trait]
 pub trait CategoryServiceTrait {
     async fn get_categories(&self) -> Result<ApiResponse<Vec<CategoryResponse>>, ErrorResponse>;
+    async fn get_category_tree(&self) -> Result<ApiResponse<Vec<CategoryTreeResponse>>, ErrorResponse>;
+    async fn get_category_subtree(&self, id: i32) -> Result<Option<ApiResponse<CategoryTreeResponse>>, ErrorResponse>;
     async fn get_category(&self, id: i32) -> Result<Option<ApiResponse<CategoryResponse>>, ErrorResponse>;
+    async fn get_category_by_slug(&self, slug: &str) -> Result<Option<SlugLookup<ApiResponse<CategoryResponse>>>, ErrorResponse>;
     async fn create_category(&self, input: &CreateCategoryRequest) -> Result<ApiResponse<CategoryResponse>, ErrorResponse>;
     async fn update_category(&self, input: &UpdateCategoryRequest) -> Result<Option<ApiResponse<CategoryResponse>>, ErrorResponse>;
-    async fn delete_category(&self, id: i32) -> Result<ApiResponse<()>, ErrorResponse>;
+    async fn merge_categories(&self, input: &MergeCategoriesRequest) -> Result<Option<ApiResponse<CategoryMergeResponse>>, ErrorResponse>;
+    async fn delete_category(&self, id: i32, query: &DeleteCategoryQuery) -> Result<Option<ApiResponse<CategoryDeletionResponse>>, ErrorResponse>;
 }
\ No newline at end of file
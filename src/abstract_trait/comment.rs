@@ -1,10 +1,11 @@
 use std::sync::Arc;
+use chrono::{DateTime, Utc};
 use sea_orm::DbErr;
 use async_trait::async_trait;
 
 use crate::{
-    domain::{ApiResponse, CommentResponse, CreateCommentRequest, ErrorResponse, UpdateCommentRequest},
-    entities::comments,
+    domain::{ApiResponse, CommentEdit, CommentModerationResponse, CommentPageResponse, CommentResponse, CommentRevisionResponse, CommentSort, CommentThreadResponse, CreateCommentRequest, ErrorResponse, FlatCommentResponse, ModerateCommentsRequest, PostCommentsQuery, UpdateCommentRequest},
+    entities::{comment_revisions, comments, sea_orm_active_enums::CommentStatus},
     
 };
 
@@ -16,19 +17,54 @@ pub type DynCommentService = Arc<dyn CommentServiceTrait + Send + Sync>;
 pub trait CommentRepositoryTrait {
     async fn find_all(&self) -> Result<Vec<comments::Model>, DbErr>;
     async fn find_by_id(&self, id: i32) -> Result<Option<comments::Model>, DbErr>;
-    async fn create(&self, input: &CreateCommentRequest) -> Result<comments::Model, DbErr>;
-    async fn update(&self, input: &UpdateCommentRequest) -> Result<comments::Model, DbErr>;
+    async fn find_by_ids(&self, ids: &[i32]) -> Result<Vec<comments::Model>, DbErr>;
+    async fn find_by_post(&self, post_id: i32) -> Result<Vec<comments::Model>, DbErr>;
+    async fn count_threads(&self, post_id: i32, viewer_id: Option<i32>) -> Result<u64, DbErr>;
+    async fn find_thread_page(
+        &self,
+        post_id: i32,
+        viewer_id: Option<i32>,
+        sort: CommentSort,
+        offset: u64,
+        limit: u64,
+    ) -> Result<Vec<comments::Model>, DbErr>;
+    async fn count_by_posts(&self, post_ids: &[i32]) -> Result<Vec<(i32, i64)>, DbErr>;
+    async fn find_by_status(&self, status: CommentStatus) -> Result<Vec<comments::Model>, DbErr>;
+    async fn set_status(&self, ids: &[i32], status: CommentStatus) -> Result<u64, DbErr>;
+    async fn create(&self, input: &CreateCommentRequest, user_id: Option<i32>, status: CommentStatus) -> Result<comments::Model, DbErr>;
+    async fn update(&self, id: i32, input: &UpdateCommentRequest, editor_id: Option<i32>, status: Option<CommentStatus>) -> Result<comments::Model, DbErr>;
+    async fn find_revisions(&self, id: i32) -> Result<Vec<comment_revisions::Model>, DbErr>;
     async fn delete(&self, id: i32) -> Result<(), DbErr>;
+    async fn find_trashed(&self) -> Result<Vec<comments::Model>, DbErr>;
+    async fn restore(&self, id: i32) -> Result<comments::Model, DbErr>;
+    async fn purge_trashed(&self, deleted_before: DateTime<Utc>) -> Result<u64, DbErr>;
 }
 
 #[async_trait]
 pub trait CommentServiceTrait {
-    async fn get_comments(&self) -> Result<ApiResponse<Vec<CommentResponse>>, ErrorResponse>;
-    async fn get_comment(&self, id: i32) -> Result<Option<ApiResponse<CommentResponse>>, ErrorResponse> ;
-    async fn create_comment(&self, input: &CreateCommentRequest) -> Result<ApiResponse<CommentResponse>, ErrorResponse>;
+    async fn get_comments(&self, viewer_id: Option<i32>) -> Result<ApiResponse<Vec<CommentResponse>>, ErrorResponse>;
+    async fn get_comment(&self, id: i32, viewer_id: Option<i32>) -> Result<Option<ApiResponse<CommentResponse>>, ErrorResponse> ;
+    async fn get_post_comment_tree(
+        &self,
+        post_id: i32,
+        viewer_id: Option<i32>,
+        query: &PostCommentsQuery,
+    ) -> Result<Option<ApiResponse<CommentPageResponse<CommentThreadResponse>>>, ErrorResponse>;
+    async fn get_post_comment_list(
+        &self,
+        post_id: i32,
+        viewer_id: Option<i32>,
+        query: &PostCommentsQuery,
+    ) -> Result<Option<ApiResponse<CommentPageResponse<FlatCommentResponse>>>, ErrorResponse>;
+    async fn create_comment(&self, input: &CreateCommentRequest, user_id: Option<i32>) -> Result<ApiResponse<CommentResponse>, ErrorResponse>;
+    async fn get_moderation_queue(&self, status: CommentStatus) -> Result<ApiResponse<Vec<CommentResponse>>, ErrorResponse>;
+    async fn moderate_comments(&self, input: &ModerateCommentsRequest) -> Result<ApiResponse<CommentModerationResponse>, ErrorResponse>;
     async fn update_comment(
         &self,
-        input: &UpdateCommentRequest
-    ) -> Result<Option<ApiResponse<CommentResponse>>, ErrorResponse>;
+        id: i32,
+        input: &UpdateCommentRequest,
+        editor_id: i32,
+    ) -> Result<Option<CommentEdit<ApiResponse<CommentResponse>>>, ErrorResponse>;
+    async fn get_comment_revisions(&self, id: i32) -> Result<Option<ApiResponse<Vec<CommentRevisionResponse>>>, ErrorResponse>;
     async fn delete_comment(&self, id: i32) -> Result<ApiResponse<()>, ErrorResponse>;
 }
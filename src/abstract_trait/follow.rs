@@ -0,0 +1,29 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use sea_orm::DbErr;
+
+use crate::domain::{ApiResponse, ErrorResponse, FeedQuery, FeedResponse, FollowingResponse};
+
+pub type DynFollowRepository = Arc<dyn FollowRepositoryTrait + Send + Sync>;
+pub type DynFollowService = Arc<dyn FollowServiceTrait + Send + Sync>;
+
+#[async_trait]
+pub trait FollowRepositoryTrait {
+    async fn follow_user(&self, follower_id: i32, followed_id: i32) -> Result<(), DbErr>;
+    async fn unfollow_user(&self, follower_id: i32, followed_id: i32) -> Result<u64, DbErr>;
+    async fn follow_category(&self, user_id: i32, category_id: i32) -> Result<(), DbErr>;
+    async fn unfollow_category(&self, user_id: i32, category_id: i32) -> Result<u64, DbErr>;
+    async fn find_followed_user_ids(&self, user_id: i32) -> Result<Vec<i32>, DbErr>;
+    async fn find_followed_category_ids(&self, user_id: i32) -> Result<Vec<i32>, DbErr>;
+}
+
+#[async_trait]
+pub trait FollowServiceTrait {
+    async fn follow_user(&self, follower_id: i32, user_id: i32) -> Result<Option<ApiResponse<()>>, ErrorResponse>;
+    async fn unfollow_user(&self, follower_id: i32, user_id: i32) -> Result<Option<ApiResponse<()>>, ErrorResponse>;
+    async fn follow_category(&self, user_id: i32, category_id: i32) -> Result<Option<ApiResponse<()>>, ErrorResponse>;
+    async fn unfollow_category(&self, user_id: i32, category_id: i32) -> Result<Option<ApiResponse<()>>, ErrorResponse>;
+    async fn get_following(&self, user_id: i32) -> Result<ApiResponse<FollowingResponse>, ErrorResponse>;
+    async fn get_feed(&self, user_id: i32, query: &FeedQuery) -> Result<ApiResponse<FeedResponse>, ErrorResponse>;
+}
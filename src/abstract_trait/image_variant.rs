@@ -0,0 +1,16 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use sea_orm::DbErr;
+
+use crate::entities::image_variants;
+
+pub type DynImageVariantRepository = Arc<dyn ImageVariantRepositoryTrait + Send + Sync>;
+
+#[async_trait]
+pub trait ImageVariantRepositoryTrait {
+    async fn create(&self, variant: image_variants::ActiveModel) -> Result<image_variants::Model, DbErr>;
+    async fn find_by_sources(&self, source_keys: &[String]) -> Result<Vec<image_variants::Model>, DbErr>;
+    async fn find_all(&self) -> Result<Vec<image_variants::Model>, DbErr>;
+    async fn delete(&self, id: i32) -> Result<(), DbErr>;
+}
@@ -0,0 +1,36 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use sea_orm::DbErr;
+
+use crate::{
+    domain::{ApiResponse, ErrorResponse, MediaResponse, UpdateMediaRequest},
+    entities::media,
+};
+
+pub type DynMediaRepository = Arc<dyn MediaRepositoryTrait + Send + Sync>;
+pub type DynMediaService = Arc<dyn MediaServiceTrait + Send + Sync>;
+
+#[async_trait]
+pub trait MediaRepositoryTrait {
+    async fn create(&self, media: media::ActiveModel) -> Result<media::Model, DbErr>;
+    async fn find_by_id(&self, id: i32) -> Result<Option<media::Model>, DbErr>;
+    async fn search(&self, user_id: Option<i32>, query: Option<&str>) -> Result<Vec<media::Model>, DbErr>;
+    async fn find_by_posts(&self, post_ids: &[i32]) -> Result<Vec<(i32, media::Model)>, DbErr>;
+    async fn update_alt_text(&self, id: i32, alt_text: &str) -> Result<media::Model, DbErr>;
+    async fn delete(&self, id: i32) -> Result<(), DbErr>;
+    // Posts attaching the media or using its file as their image, and categories using it as cover
+    async fn count_references(&self, id: i32, url: &str) -> Result<u64, DbErr>;
+    async fn all_filenames(&self) -> Result<Vec<String>, DbErr>;
+    async fn all_post_images(&self) -> Result<Vec<String>, DbErr>;
+    async fn all_category_covers(&self) -> Result<Vec<String>, DbErr>;
+}
+
+#[async_trait]
+pub trait MediaServiceTrait {
+    async fn get_media(&self, user_id: Option<i32>, query: Option<&str>) -> Result<ApiResponse<Vec<MediaResponse>>, ErrorResponse>;
+    async fn get_media_item(&self, id: i32) -> Result<Option<ApiResponse<MediaResponse>>, ErrorResponse>;
+    async fn update_media(&self, user_id: i32, input: &UpdateMediaRequest) -> Result<Option<ApiResponse<MediaResponse>>, ErrorResponse>;
+    async fn delete_media(&self, user_id: i32, id: i32, force: bool) -> Result<Option<ApiResponse<()>>, ErrorResponse>;
+    async fn collect_garbage(&self) -> Result<usize, ErrorResponse>;
+}
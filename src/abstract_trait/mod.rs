@@ -3,6 +3,17 @@ mod post;
 mod comment;
 mod user;
 mod auth;
+mod post_revision;
+mod tag;
+mod storage;
+mod image_variant;
+mod media;
+mod upload;
+mod trash;
+mod reaction;
+mod bookmark;
+mod follow;
+mod notification;
 
 pub use self::category::{
     CategoryRepositoryTrait, CategoryServiceTrait, DynCategoryRepository, DynCategoryService,
@@ -25,4 +36,73 @@ pub use self::user::{
 pub use self::auth::{
     DynAuthService,
     AuthServiceTrait
-};
\ No newline at end of file
+};
+
+pub use self::post_revision::{
+    DynPostRevisionRepository,
+    DynPostRevisionService,
+    PostRevisionRepositoryTrait,
+    PostRevisionServiceTrait
+};
+
+pub use self::tag::{
+    DynTagRepository,
+    DynTagService,
+    TagRepositoryTrait,
+    TagServiceTrait
+};
+
+pub use self::storage::{
+    DynStorage,
+    StorageTrait
+};
+
+pub use self::upload::{
+    DynUploadService,
+    UploadServiceTrait
+};
+
+pub use self::image_variant::{
+    DynImageVariantRepository,
+    ImageVariantRepositoryTrait
+};
+
+pub use self::media::{
+    DynMediaRepository,
+    DynMediaService,
+    MediaRepositoryTrait,
+    MediaServiceTrait
+};
+
+pub use self::trash::{
+    DynTrashService,
+    TrashServiceTrait
+};
+
+pub use self::reaction::{
+    DynReactionRepository,
+    DynReactionService,
+    ReactionRepositoryTrait,
+    ReactionServiceTrait
+};
+
+pub use self::bookmark::{
+    BookmarkRepositoryTrait,
+    BookmarkServiceTrait,
+    DynBookmarkRepository,
+    DynBookmarkService
+};
+
+pub use self::follow::{
+    DynFollowRepository,
+    DynFollowService,
+    FollowRepositoryTrait,
+    FollowServiceTrait
+};
+
+pub use self::notification::{
+    DynNotificationRepository,
+    DynNotificationService,
+    NotificationRepositoryTrait,
+    NotificationServiceTrait
+};
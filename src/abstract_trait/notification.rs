@@ -0,0 +1,43 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use sea_orm::DbErr;
+
+use crate::{
+    domain::{
+        ApiResponse, ErrorResponse, MarkAllReadResponse, NewNotification, NotificationListQuery, NotificationPageResponse,
+        NotificationPreferenceResponse, NotificationResponse, UnreadCountResponse, UpdateNotificationPreferencesRequest,
+    },
+    entities::{notifications, sea_orm_active_enums::NotificationKind},
+};
+
+pub type DynNotificationRepository = Arc<dyn NotificationRepositoryTrait + Send + Sync>;
+pub type DynNotificationService = Arc<dyn NotificationServiceTrait + Send + Sync>;
+
+#[async_trait]
+pub trait NotificationRepositoryTrait {
+    async fn create(&self, input: &NewNotification) -> Result<notifications::Model, DbErr>;
+    // Newest first, with the total across all pages
+    async fn find_page(&self, user_id: i32, unread_only: bool, page: u64, per_page: u64) -> Result<(Vec<notifications::Model>, u64), DbErr>;
+    async fn count_unread(&self, user_id: i32) -> Result<u64, DbErr>;
+    async fn mark_read(&self, user_id: i32, id: i32) -> Result<Option<notifications::Model>, DbErr>;
+    async fn mark_all_read(&self, user_id: i32) -> Result<u64, DbErr>;
+    async fn find_preferences(&self, user_id: i32) -> Result<Vec<(NotificationKind, bool)>, DbErr>;
+    async fn set_preference(&self, user_id: i32, kind: NotificationKind, enabled: bool) -> Result<(), DbErr>;
+}
+
+#[async_trait]
+pub trait NotificationServiceTrait {
+    // Skips notifications about the recipient's own actions and kinds they switched off
+    async fn notify(&self, input: NewNotification) -> Result<(), ErrorResponse>;
+    async fn get_notifications(&self, user_id: i32, query: &NotificationListQuery) -> Result<ApiResponse<NotificationPageResponse>, ErrorResponse>;
+    async fn get_unread_count(&self, user_id: i32) -> Result<ApiResponse<UnreadCountResponse>, ErrorResponse>;
+    async fn mark_read(&self, user_id: i32, id: i32) -> Result<Option<ApiResponse<NotificationResponse>>, ErrorResponse>;
+    async fn mark_all_read(&self, user_id: i32) -> Result<ApiResponse<MarkAllReadResponse>, ErrorResponse>;
+    async fn get_preferences(&self, user_id: i32) -> Result<ApiResponse<Vec<NotificationPreferenceResponse>>, ErrorResponse>;
+    async fn update_preferences(
+        &self,
+        user_id: i32,
+        input: &UpdateNotificationPreferencesRequest,
+    ) -> Result<ApiResponse<Vec<NotificationPreferenceResponse>>, ErrorResponse>;
+}
@@ -1,7 +1,8 @@
 use std::sync::Arc;
 
-use crate::{domain::{ApiResponse, CreatePostRequest, ErrorResponse, PostRelationResponse, PostResponse, UpdatePostRequest}, entities::posts};
+use crate::{domain::{ApiResponse, CreatePostRequest, ErrorResponse, FeedCursor, PostAggregateResponse, PostListQuery, PostRelationQuery, PostResponse, SlugLookup, UpdatePostRequest}, entities::{posts, sea_orm_active_enums::PostStatus}};
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 use sea_orm::DbErr;
 
 
@@ -11,9 +12,14 @@ pub type DynPostsService = Arc<dyn PostsServiceTrait + Send + Sync>;
 
 #[async_trait]
 pub trait PostsRepositoryTrait {
-    async fn get_all_posts(&self) -> Result<Vec<posts::Model>, DbErr>;
+    async fn get_published_posts(&self, query: &PostListQuery) -> Result<Vec<posts::Model>, DbErr>;
+    async fn get_published_posts_by_tag(&self, tag_slug: &str) -> Result<Vec<posts::Model>, DbErr>;
+    async fn get_posts_by_author(&self, user_id: i32, status: Option<PostStatus>) -> Result<Vec<posts::Model>, DbErr>;
+    // Published posts from the authors and categories the user follows, newest first, strictly after the cursor
+    async fn get_feed_posts(&self, user_id: i32, after: Option<FeedCursor>, limit: u64) -> Result<Vec<posts::Model>, DbErr>;
     async fn get_post(&self, post_id: i32) -> Result<Option<posts::Model>, DbErr>;
-    async fn get_post_relation(&self, post_id: i32) -> Result<Vec<PostRelationResponse>, DbErr>;
+    async fn get_post_by_slug(&self, slug: &str) -> Result<Option<posts::Model>, DbErr>;
+    async fn find_slug_redirect(&self, slug: &str) -> Result<Option<String>, DbErr>;
     async fn create_post(
         &self,
         input: &CreatePostRequest
@@ -23,13 +29,29 @@ pub trait PostsRepositoryTrait {
         input: &UpdatePostRequest
     ) -> Result<posts::Model, DbErr>;
     async fn delete_post(&self, post_id: i32) -> Result<(), DbErr>;
+    async fn get_trashed_posts(&self) -> Result<Vec<posts::Model>, DbErr>;
+    async fn restore_post(&self, post_id: i32) -> Result<posts::Model, DbErr>;
+    async fn purge_trashed_posts(&self, deleted_before: DateTime<Utc>) -> Result<u64, DbErr>;
+    async fn publish_due_posts(&self) -> Result<u64, DbErr>;
+    async fn render_missing_body_html(&self, limit: u64) -> Result<u64, DbErr>;
 }
 
 #[async_trait]
 pub trait PostsServiceTrait {
-    async fn get_all_posts(&self) -> Result<ApiResponse<Vec<PostResponse>>, ErrorResponse>;
-    async fn get_post(&self, post_id: i32) -> Result<Option<ApiResponse<PostResponse>>, ErrorResponse>  ;
-    async fn get_post_relation(&self, post_id: i32) -> Result<ApiResponse<PostRelationResponse>, ErrorResponse>;
+    // Posts loaded elsewhere, such as a reader's bookmarks, get the same tags, media and counts as listings
+    async fn can_manage(&self, post_id: i32, user_id: i32) -> Result<Option<bool>, ErrorResponse>;
+    async fn to_responses(&self, posts: Vec<posts::Model>, viewer_id: Option<i32>) -> Result<Vec<PostResponse>, ErrorResponse>;
+    async fn get_all_posts(&self, query: &PostListQuery, viewer_id: Option<i32>) -> Result<ApiResponse<Vec<PostResponse>>, ErrorResponse>;
+    async fn get_posts_by_tag(&self, tag_slug: &str, viewer_id: Option<i32>) -> Result<ApiResponse<Vec<PostResponse>>, ErrorResponse>;
+    async fn get_author_posts(&self, user_id: i32, status: Option<PostStatus>) -> Result<ApiResponse<Vec<PostResponse>>, ErrorResponse>;
+    async fn get_post(&self, post_id: i32, viewer_id: Option<i32>) -> Result<Option<ApiResponse<PostResponse>>, ErrorResponse>  ;
+    async fn get_post_by_slug(&self, slug: &str, viewer_id: Option<i32>) -> Result<Option<SlugLookup<ApiResponse<PostResponse>>>, ErrorResponse>;
+    async fn get_post_relation(
+        &self,
+        post_id: i32,
+        viewer_id: Option<i32>,
+        query: &PostRelationQuery,
+    ) -> Result<Option<ApiResponse<PostAggregateResponse>>, ErrorResponse>;
     async fn create_post(
         &self,
         input: &CreatePostRequest
@@ -39,4 +61,6 @@ pub trait PostsServiceTrait {
         input: &UpdatePostRequest
     ) -> Result<ApiResponse<PostResponse>, ErrorResponse>;
     async fn delete_post(&self, post_id: i32) -> Result<ApiResponse<()>, ErrorResponse>;
+    async fn publish_scheduled_posts(&self) -> Result<u64, ErrorResponse>;
+    async fn backfill_body_html(&self) -> Result<u64, ErrorResponse>;
 }
@@ -0,0 +1,35 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use sea_orm::DbErr;
+
+use crate::{
+    domain::{ApiResponse, ErrorResponse, PostResponse, PostRevisionDiffResponse, PostRevisionResponse},
+    entities::post_revisions,
+};
+
+pub type DynPostRevisionRepository = Arc<dyn PostRevisionRepositoryTrait + Send + Sync>;
+pub type DynPostRevisionService = Arc<dyn PostRevisionServiceTrait + Send + Sync>;
+
+#[async_trait]
+pub trait PostRevisionRepositoryTrait {
+    async fn find_by_post(&self, post_id: i32) -> Result<Vec<post_revisions::Model>, DbErr>;
+    async fn find_by_id(&self, post_id: i32, revision_id: i32) -> Result<Option<post_revisions::Model>, DbErr>;
+}
+
+#[async_trait]
+pub trait PostRevisionServiceTrait {
+    async fn get_revisions(&self, post_id: i32) -> Result<ApiResponse<Vec<PostRevisionResponse>>, ErrorResponse>;
+    async fn diff_revisions(
+        &self,
+        post_id: i32,
+        from_revision: i32,
+        to_revision: i32
+    ) -> Result<Option<ApiResponse<PostRevisionDiffResponse>>, ErrorResponse>;
+    async fn restore_revision(
+        &self,
+        post_id: i32,
+        revision_id: i32,
+        editor_id: i32
+    ) -> Result<Option<ApiResponse<PostResponse>>, ErrorResponse>;
+}
\ No newline at end of file
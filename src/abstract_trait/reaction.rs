@@ -0,0 +1,33 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use sea_orm::DbErr;
+
+use crate::{
+    domain::{ApiResponse, CommentVoteResponse, ErrorResponse, PostReactionsResponse, VoteDirection},
+    entities::sea_orm_active_enums::ReactionKind,
+};
+
+pub type DynReactionRepository = Arc<dyn ReactionRepositoryTrait + Send + Sync>;
+pub type DynReactionService = Arc<dyn ReactionServiceTrait + Send + Sync>;
+
+#[async_trait]
+pub trait ReactionRepositoryTrait {
+    async fn add_post_reaction(&self, post_id: i32, user_id: i32, kind: ReactionKind) -> Result<(), DbErr>;
+    async fn remove_post_reaction(&self, post_id: i32, user_id: i32, kind: ReactionKind) -> Result<u64, DbErr>;
+    async fn count_post_reactions(&self, post_ids: &[i32]) -> Result<Vec<(i32, ReactionKind, i64)>, DbErr>;
+    async fn find_user_post_reactions(&self, post_ids: &[i32], user_id: i32) -> Result<Vec<(i32, ReactionKind)>, DbErr>;
+    async fn set_comment_vote(&self, comment_id: i32, user_id: i32, value: i16) -> Result<(), DbErr>;
+    async fn remove_comment_vote(&self, comment_id: i32, user_id: i32) -> Result<u64, DbErr>;
+    // (comment id, upvotes, downvotes)
+    async fn count_comment_votes(&self, comment_ids: &[i32]) -> Result<Vec<(i32, i64, i64)>, DbErr>;
+    async fn find_user_comment_votes(&self, comment_ids: &[i32], user_id: i32) -> Result<Vec<(i32, i16)>, DbErr>;
+}
+
+#[async_trait]
+pub trait ReactionServiceTrait {
+    async fn react_to_post(&self, post_id: i32, user_id: i32, kind: ReactionKind) -> Result<Option<ApiResponse<PostReactionsResponse>>, ErrorResponse>;
+    async fn remove_post_reaction(&self, post_id: i32, user_id: i32, kind: ReactionKind) -> Result<Option<ApiResponse<PostReactionsResponse>>, ErrorResponse>;
+    async fn vote_comment(&self, comment_id: i32, user_id: i32, direction: VoteDirection) -> Result<Option<ApiResponse<CommentVoteResponse>>, ErrorResponse>;
+    async fn remove_comment_vote(&self, comment_id: i32, user_id: i32) -> Result<Option<ApiResponse<CommentVoteResponse>>, ErrorResponse>;
+}
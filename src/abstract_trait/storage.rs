@@ -0,0 +1,19 @@
+use std::sync::Arc;
+use std::time::SystemTime;
+
+use async_trait::async_trait;
+
+pub type DynStorage = Arc<dyn StorageTrait + Send + Sync>;
+
+// Where uploaded files live. Keys are flat file names, the backend decides how they are laid out.
+#[async_trait]
+pub trait StorageTrait {
+    async fn put(&self, key: &str, bytes: &[u8]) -> Result<(), std::io::Error>;
+    async fn get(&self, key: &str) -> Result<Option<Vec<u8>>, std::io::Error>;
+    async fn exists(&self, key: &str) -> Result<bool, std::io::Error>;
+    async fn delete(&self, key: &str) -> Result<(), std::io::Error>;
+    // Every stored key with its last modification time
+    async fn list(&self) -> Result<Vec<(String, SystemTime)>, std::io::Error>;
+    fn url(&self, key: &str) -> String;
+    fn key_from_url(&self, url: &str) -> Option<String>;
+}
@@ -0,0 +1,29 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use sea_orm::DbErr;
+
+use crate::{
+    domain::{ApiResponse, ErrorResponse, MergeTagRequest, RenameTagRequest, TagResponse},
+    entities::tags,
+};
+
+pub type DynTagRepository = Arc<dyn TagRepositoryTrait + Send + Sync>;
+pub type DynTagService = Arc<dyn TagServiceTrait + Send + Sync>;
+
+#[async_trait]
+pub trait TagRepositoryTrait {
+    async fn find_all_with_post_count(&self) -> Result<Vec<(tags::Model, i64)>, DbErr>;
+    async fn find_by_id(&self, id: i32) -> Result<Option<tags::Model>, DbErr>;
+    async fn find_by_slug(&self, slug: &str) -> Result<Option<tags::Model>, DbErr>;
+    async fn find_by_posts(&self, post_ids: &[i32]) -> Result<Vec<(i32, tags::Model)>, DbErr>;
+    async fn rename(&self, id: i32, name: &str) -> Result<tags::Model, DbErr>;
+    async fn merge(&self, source_id: i32, target_id: i32) -> Result<tags::Model, DbErr>;
+}
+
+#[async_trait]
+pub trait TagServiceTrait {
+    async fn get_tags(&self) -> Result<ApiResponse<Vec<TagResponse>>, ErrorResponse>;
+    async fn rename_tag(&self, input: &RenameTagRequest) -> Result<Option<ApiResponse<TagResponse>>, ErrorResponse>;
+    async fn merge_tag(&self, source_id: i32, input: &MergeTagRequest) -> Result<Option<ApiResponse<TagResponse>>, ErrorResponse>;
+}
\ No newline at end of file
@@ -0,0 +1,15 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use crate::domain::{ApiResponse, ErrorResponse, TrashKind, TrashResponse};
+
+pub type DynTrashService = Arc<dyn TrashServiceTrait + Send + Sync>;
+
+#[async_trait]
+pub trait TrashServiceTrait {
+    async fn get_trash(&self) -> Result<ApiResponse<TrashResponse>, ErrorResponse>;
+    async fn restore(&self, kind: TrashKind, id: i32) -> Result<Option<ApiResponse<()>>, ErrorResponse>;
+    // Permanently removes items that have been in the trash longer than the retention period
+    async fn purge_expired(&self) -> Result<u64, ErrorResponse>;
+}
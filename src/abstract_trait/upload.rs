@@ -0,0 +1,13 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use crate::domain::{ApiResponse, ErrorResponse, StoredFile, UploadResponse};
+
+pub type DynUploadService = Arc<dyn UploadServiceTrait + Send + Sync>;
+
+#[async_trait]
+pub trait UploadServiceTrait {
+    async fn upload_image(&self, user_id: i32, original_name: &str, bytes: &[u8]) -> Result<ApiResponse<UploadResponse>, ErrorResponse>;
+    async fn get_upload(&self, filename: &str) -> Result<Option<StoredFile>, ErrorResponse>;
+}
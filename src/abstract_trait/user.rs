@@ -1,3 +1,4 @@
+use chrono::{DateTime, Utc};
 use sea_orm::DbErr;
 use std::sync::Arc;
 
@@ -22,6 +23,9 @@ pub trait UserRepositoryTrait {
         input: &UpdateUserRequest
     ) -> Result<users::Model, DbErr>;
     async fn delete_user(&self, email: &str) -> Result<(), DbErr>;
+    async fn find_trashed_users(&self) -> Result<Vec<users::Model>, DbErr>;
+    async fn restore_user(&self, id: i32) -> Result<users::Model, DbErr>;
+    async fn purge_trashed_users(&self, deleted_before: DateTime<Utc>) -> Result<u64, DbErr>;
 }
 
 #[async_trait]
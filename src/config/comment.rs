@@ -0,0 +1,14 @@
+#[derive(Debug, Clone)]
+pub struct CommentConfig {
+    pub max_depth: usize,
+    /// Default for categories that do not set `comment_moderation` themselves
+    pub require_approval: bool,
+    /// How long authors can edit their comments, moderators are not limited
+    pub edit_window_minutes: i64,
+}
+
+impl CommentConfig {
+    pub fn new(max_depth: usize, require_approval: bool, edit_window_minutes: i64) -> Self {
+        CommentConfig { max_depth, require_approval, edit_window_minutes }
+    }
+}
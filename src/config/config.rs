@@ -1,9 +1,20 @@
+use crate::utils::{ImageSize, DEFAULT_HIGHLIGHT_THEME};
+
 #[derive(Debug, Clone)]
 pub struct Config {
     pub database_url: String,
     pub jwt_secret: String,
     pub run_migrations: bool,
     pub port: u16,
+    pub highlight_theme: String,
+    pub highlight_languages: Option<Vec<String>>,
+    pub upload_dir: String,
+    pub upload_max_size: usize,
+    pub image_sizes: Vec<ImageSize>,
+    pub trash_retention_days: i64,
+    pub comment_max_depth: usize,
+    pub comment_require_approval: bool,
+    pub comment_edit_window_minutes: i64,
 }
 
 impl Config {
@@ -24,7 +35,66 @@ impl Config {
 
         let port = port_str.parse().expect("Invalid value for PORT");
 
-        Config { database_url, jwt_secret, run_migrations, port }
+        // Optional, every bundled language is highlighted when HIGHLIGHT_LANGUAGES is unset
+        let highlight_theme = std::env::var("HIGHLIGHT_THEME")
+            .unwrap_or_else(|_| DEFAULT_HIGHLIGHT_THEME.to_string());
+        let highlight_languages = std::env::var("HIGHLIGHT_LANGUAGES")
+            .ok()
+            .map(|languages| languages.split(',').map(|language| language.trim().to_string()).collect());
+
+        let upload_dir = std::env::var("UPLOAD_DIR").unwrap_or_else(|_| "uploads".to_string());
+        let upload_max_size = std::env::var("UPLOAD_MAX_SIZE")
+            .map(|size| size.parse().expect("Invalid value for UPLOAD_MAX_SIZE"))
+            .unwrap_or(5 * 1024 * 1024);
+
+        // name:width pairs, for example thumbnail:320,medium:800,large:1600
+        let image_sizes = std::env::var("IMAGE_SIZES")
+            .unwrap_or_else(|_| "thumbnail:320,medium:800,large:1600".to_string())
+            .split(',')
+            .filter(|size| !size.trim().is_empty())
+            .map(|size| {
+                let (name, width) = size.split_once(':').expect("IMAGE_SIZES entries must be name:width");
+                ImageSize {
+                    name: name.trim().to_string(),
+                    width: width.trim().parse().expect("Invalid width in IMAGE_SIZES"),
+                }
+            })
+            .collect();
+
+        // Soft deleted items are purged for good once they have been in the trash this long
+        let trash_retention_days = std::env::var("TRASH_RETENTION_DAYS")
+            .map(|days| days.parse().expect("Invalid value for TRASH_RETENTION_DAYS"))
+            .unwrap_or(30);
+
+        // Top level comments sit at depth 0, replies deeper than this are rejected
+        let comment_max_depth = std::env::var("COMMENT_MAX_DEPTH")
+            .map(|depth| depth.parse().expect("Invalid value for COMMENT_MAX_DEPTH"))
+            .unwrap_or(5);
+
+        // Categories can override this with their own comment_moderation flag
+        let comment_require_approval = std::env::var("COMMENT_REQUIRE_APPROVAL")
+            .map(|required| required.parse().expect("COMMENT_REQUIRE_APPROVAL must be either 'true' or 'false'"))
+            .unwrap_or(false);
+
+        let comment_edit_window_minutes = std::env::var("COMMENT_EDIT_WINDOW_MINUTES")
+            .map(|minutes| minutes.parse().expect("Invalid value for COMMENT_EDIT_WINDOW_MINUTES"))
+            .unwrap_or(15);
+
+        Config {
+            database_url,
+            jwt_secret,
+            run_migrations,
+            port,
+            highlight_theme,
+            highlight_languages,
+            upload_dir,
+            upload_max_size,
+            image_sizes,
+            trash_retention_days,
+            comment_max_depth,
+            comment_require_approval,
+            comment_edit_window_minutes,
+        }
  
     }
 }
\ No newline at end of file
@@ -1,12 +1,12 @@
 use sea_orm::{Database, DatabaseConnection};
-use sea_orm_migration::{MigrationTrait, SchemaManager};
+use sea_orm_migration::MigratorTrait;
 
 use crate::utils::ConnectionManagerError;
 
 pub struct ConnectionManager;
 
 impl ConnectionManager {
-    pub async fn new_pool<M: MigrationTrait + Default>(
+    pub async fn new_pool<M: MigratorTrait>(
         connection_string: &str,
         run_migrations: bool
     ) -> Result<DatabaseConnection, ConnectionManagerError> {
@@ -15,10 +15,7 @@ impl ConnectionManager {
         
 
         if run_migrations {
-            let schema_manager = SchemaManager::new(&pool);
-            let migration = M::default();
-
-               migration.up(&schema_manager).await
+            M::up(&pool, None).await
                 .map_err(ConnectionManagerError::MigrationError)?;
         }
         
@@ -4,6 +4,12 @@ use crate::utils::AppError;
 #[derive(Clone)]
 pub struct Hashing;
 
+impl Default for Hashing {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Hashing {
     pub fn new() -> Self {
         Hashing
@@ -16,7 +22,7 @@ impl Hashing {
     pub async fn compare_password(&self, hashed_password: &str, password: &str) -> Result<(), AppError> {
         match verify(password, hashed_password) {
             Ok(true) => Ok(()), 
-            Ok(false) => Err(AppError::HashingError(BcryptError::from(std::io::Error::new(std::io::ErrorKind::Other, "Passwords do not match.")))), // Passwords do not match
+            Ok(false) => Err(AppError::HashingError(BcryptError::from(std::io::Error::other("Passwords do not match.")))), // Passwords do not match
             Err(e) => Err(AppError::BcryptError(e.to_string())), // Handle bcrypt error
         }
     }
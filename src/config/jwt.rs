@@ -43,15 +43,15 @@ impl JwtConfig {
         match encode(
             &Header::default(),
             &claims,
-            &EncodingKey::from_secret("YOUR_SECRET_KEY".as_ref()),
+            &EncodingKey::from_secret(self.jwt_secret.as_ref()),
         ) {
             Ok(token) => Ok(token),
-            Err(err) => Err(AppError::TokenGenerationError(err.into())),
+            Err(err) => Err(AppError::TokenGenerationError(err)),
         }
     }
 
     pub fn verify_token(&self, token: &str) -> Result<i64, AppError> {
-        let decoding_key = DecodingKey::from_secret("YOUR_SECRET_KEY".as_ref());
+        let decoding_key = DecodingKey::from_secret(self.jwt_secret.as_ref());
     
         match decode::<Claims>(token, &decoding_key, &Validation::default()) {
             Ok(token_data) => {
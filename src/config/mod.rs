@@ -1,9 +1,14 @@
 mod hashing;
 mod jwt;
+#[allow(clippy::module_inception)]
 mod config;
 mod database;
+mod upload;
+mod comment;
 
 pub use self::jwt::{JwtConfig, Claims};
 pub use self::hashing::Hashing;
 pub use self::config::Config;
-pub use self::database::ConnectionManager;
\ No newline at end of file
+pub use self::database::ConnectionManager;
+pub use self::upload::UploadConfig;
+pub use self::comment::CommentConfig;
\ No newline at end of file
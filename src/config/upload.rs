@@ -0,0 +1,18 @@
+use crate::utils::ImageSize;
+
+#[derive(Debug, Clone)]
+pub struct UploadConfig {
+    pub upload_dir: String,
+    pub max_size: usize,
+    pub image_sizes: Vec<ImageSize>,
+}
+
+impl UploadConfig {
+    pub fn new(upload_dir: &str, max_size: usize, image_sizes: Vec<ImageSize>) -> Self {
+        UploadConfig {
+            upload_dir: upload_dir.to_string(),
+            max_size,
+            image_sizes,
+        }
+    }
+}
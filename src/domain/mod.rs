@@ -4,22 +4,84 @@ mod response;
 pub use self::request::{
     CreateCategoryRequest,
     UpdateCategoryRequest,
+    CategoryDeleteStrategy,
+    DeleteCategoryQuery,
+    MergeCategoriesRequest,
     CreatePostRequest,
     UpdatePostRequest,
+    PostListQuery,
+    PostRelationQuery,
+    PostSort,
+    SortOrder,
     CreateCommentRequest,
     UpdateCommentRequest,
+    CommentFormat,
+    CommentSort,
+    PostCommentsQuery,
+    ModerateCommentsRequest,
+    ModerationAction,
+    ModerationQueueQuery,
     CreateUserRequest,
     UpdateUserRequest,
     LoginRequest,
-    RegisterRequest
+    RegisterRequest,
+    RenameTagRequest,
+    MergeTagRequest,
+    UpdateMediaRequest,
+    TrashKind,
+    CommentVoteRequest,
+    VoteDirection,
+    BookmarkListQuery,
+    SaveBookmarkRequest,
+    MAX_BOOKMARK_FOLDER_LENGTH,
+    FeedCursor,
+    FeedQuery,
+    NewNotification,
+    NotificationListQuery,
+    NotificationPreferenceRequest,
+    UpdateNotificationPreferencesRequest
 };
 
 pub use self::response::{
     ApiResponse,
     ErrorResponse,
     CategoryResponse,
+    CategoryDeletionResponse,
+    CategoryTreeResponse,
+    CategoryMergeResponse,
     PostResponse,
-    PostRelationResponse,
+    PostAggregateResponse,
     CommentResponse,
-    UserResponse
+    CommentThreadResponse,
+    FlatCommentResponse,
+    CommentModerationResponse,
+    CommentEdit,
+    CommentRevisionResponse,
+    CommentPageResponse,
+    UserResponse,
+    SlugLookup,
+    SlugRedirectResponse,
+    DiffLineResponse,
+    PostRevisionResponse,
+    PostRevisionDiffResponse,
+    TagResponse,
+    TocEntryResponse,
+    ImageVariantResponse,
+    StoredFile,
+    UploadResponse,
+    MediaResponse,
+    TrashResponse,
+    CommentVoteResponse,
+    PostReactionsResponse,
+    ReactionCountResponse,
+    BookmarkResponse,
+    BookmarkPageResponse,
+    BookmarkFolderResponse,
+    FeedResponse,
+    FollowingResponse,
+    NotificationResponse,
+    NotificationPageResponse,
+    NotificationPreferenceResponse,
+    UnreadCountResponse,
+    MarkAllReadResponse
 };
\ No newline at end of file
@@ -0,0 +1,36 @@
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+
+pub const MAX_BOOKMARK_FOLDER_LENGTH: usize = 64;
+pub const DEFAULT_BOOKMARKS_PER_PAGE: u64 = 20;
+pub const MAX_BOOKMARKS_PER_PAGE: u64 = 100;
+
+#[derive(Debug, Default, Deserialize, Serialize, ToSchema)]
+pub struct SaveBookmarkRequest {
+    /// Folder to file the bookmark under, leave out to keep it unfiled
+    #[serde(default)]
+    pub folder: Option<String>,
+    #[serde(default)]
+    pub note: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct BookmarkListQuery {
+    /// Only list bookmarks in this folder
+    pub folder: Option<String>,
+    /// Starts at 1
+    pub page: Option<u64>,
+    /// Defaults to 20, at most 100
+    pub per_page: Option<u64>,
+}
+
+impl BookmarkListQuery {
+    pub fn page(&self) -> u64 {
+        self.page.unwrap_or(1).max(1)
+    }
+
+    pub fn per_page(&self) -> u64 {
+        self.per_page.unwrap_or(DEFAULT_BOOKMARKS_PER_PAGE).clamp(1, MAX_BOOKMARKS_PER_PAGE)
+    }
+}
@@ -1,15 +1,74 @@
-use serde::{Deserialize, Serialize};
-use utoipa::ToSchema;
+use serde::{Deserialize, Deserializer, Serialize};
+use utoipa::{IntoParams, ToSchema};
 
 
 
 #[derive(Serialize, Deserialize, Clone,Debug, ToSchema)]
 pub struct CreateCategoryRequest {
     pub name: String,
+    pub parent_id: Option<i32>,
+    pub description: Option<String>,
+    /// Categories are listed by ascending display order, then name
+    pub display_order: Option<i32>,
+    pub cover_img: Option<String>,
+    /// Whether new comments need approval, null follows the global setting
+    pub comment_moderation: Option<bool>,
 }
 
 #[derive(Serialize, Deserialize, Clone,Debug, ToSchema)]
 pub struct UpdateCategoryRequest {
     pub id: Option<i32>,
     pub name: Option<String>,
-}
\ No newline at end of file
+    /// Leave out to keep the current parent, null moves the category to the top level
+    #[serde(default, deserialize_with = "present_or_null")]
+    #[schema(value_type = Option<i32>)]
+    pub parent_id: Option<Option<i32>>,
+    pub description: Option<String>,
+    pub display_order: Option<i32>,
+    /// Leave out to keep the current cover, null removes it
+    #[serde(default, deserialize_with = "present_or_null")]
+    #[schema(value_type = Option<String>)]
+    pub cover_img: Option<Option<String>>,
+    /// Leave out to keep the current setting, null falls back to the global setting
+    #[serde(default, deserialize_with = "present_or_null")]
+    #[schema(value_type = Option<bool>)]
+    pub comment_moderation: Option<Option<bool>>,
+}
+
+// Tells an explicit null apart from a missing field, which serde would otherwise both read as None
+fn present_or_null<'de, D, T>(deserializer: D) -> Result<Option<Option<T>>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    Option::<T>::deserialize(deserializer).map(Some)
+}
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum CategoryDeleteStrategy {
+    /// Only delete the category when it has no posts
+    #[default]
+    Refuse,
+    /// Move the posts to `target_id` first
+    Reassign,
+    /// Move the posts to the trash along with the category
+    DeletePosts,
+}
+
+#[derive(Debug, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct DeleteCategoryQuery {
+    /// What happens to the posts of the category, defaults to refuse
+    pub strategy: Option<CategoryDeleteStrategy>,
+    /// Category receiving the posts when the strategy is reassign
+    pub target_id: Option<i32>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, ToSchema)]
+pub struct MergeCategoriesRequest {
+    pub source_ids: Vec<i32>,
+    pub target_id: i32,
+    /// Report what the merge would change without applying it
+    #[serde(default)]
+    pub dry_run: bool,
+}
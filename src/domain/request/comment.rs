@@ -1,16 +1,99 @@
 use serde::{Deserialize, Serialize};
-use utoipa::ToSchema;
+use utoipa::{IntoParams, ToSchema};
+
+use crate::entities::sea_orm_active_enums::CommentStatus;
 
 #[derive(Debug, Deserialize, Serialize, ToSchema)]
 pub struct CreateCommentRequest {
     pub id_post_comment: i32,
     pub user_name_comment: String,
+    /// Mention a user with @ followed by their email, for example @jane@example.com
     pub comment: String,
+    /// Comment being replied to, it must belong to the same post
+    #[serde(default)]
+    pub parent_id: Option<i32>,
 }
 
 #[derive(Debug, Deserialize, Serialize,ToSchema)]
 pub struct UpdateCommentRequest {
-    pub id_post_comment: i32,
-    pub user_name_comment: String,
     pub comment: String,
-}
\ No newline at end of file
+}
+
+#[derive(Debug, Clone, Copy, Default, Deserialize, Serialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum CommentFormat {
+    #[default]
+    Tree,
+    Flat,
+}
+
+#[derive(Debug, Clone, Copy, Default, Deserialize, Serialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum CommentSort {
+    #[default]
+    Oldest,
+    Newest,
+    /// Threads with the most replies first
+    Top,
+    /// Highest upvotes minus downvotes first
+    Score,
+}
+
+pub const DEFAULT_COMMENTS_PER_PAGE: u64 = 20;
+pub const MAX_COMMENTS_PER_PAGE: u64 = 100;
+
+#[derive(Debug, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct PostCommentsQuery {
+    /// Nested replies or a flat list in thread order, defaults to tree
+    #[serde(default)]
+    pub format: CommentFormat,
+    /// Applies to top level comments and to the replies within each thread, defaults to oldest
+    #[serde(default)]
+    pub sort: CommentSort,
+    /// Pages count top level comments, replies always come with their thread. Starts at 1
+    pub page: Option<u64>,
+    /// Top level comments per page, defaults to 20, at most 100
+    pub per_page: Option<u64>,
+}
+
+impl PostCommentsQuery {
+    pub fn page(&self) -> u64 {
+        self.page.unwrap_or(1).max(1)
+    }
+
+    pub fn per_page(&self) -> u64 {
+        self.per_page.unwrap_or(DEFAULT_COMMENTS_PER_PAGE).clamp(1, MAX_COMMENTS_PER_PAGE)
+    }
+}
+
+#[derive(Debug, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ModerationQueueQuery {
+    /// Defaults to pending
+    pub status: Option<CommentStatus>,
+}
+
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ModerationAction {
+    Approve,
+    Reject,
+    Spam,
+}
+
+impl From<ModerationAction> for CommentStatus {
+    fn from(action: ModerationAction) -> Self {
+        match action {
+            ModerationAction::Approve => CommentStatus::Approved,
+            ModerationAction::Reject => CommentStatus::Rejected,
+            ModerationAction::Spam => CommentStatus::Spam,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
+pub struct ModerateCommentsRequest {
+    pub comment_ids: Vec<i32>,
+    pub action: ModerationAction,
+}
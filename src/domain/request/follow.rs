@@ -0,0 +1,92 @@
+use chrono::{DateTime, FixedOffset};
+use serde::Deserialize;
+use utoipa::IntoParams;
+
+pub const DEFAULT_FEED_LIMIT: u64 = 20;
+pub const MAX_FEED_LIMIT: u64 = 100;
+
+#[derive(Debug, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct FeedQuery {
+    /// next_cursor from the previous page, leave out for the newest posts
+    pub cursor: Option<String>,
+    /// Defaults to 20, at most 100
+    pub limit: Option<u64>,
+}
+
+impl FeedQuery {
+    pub fn limit(&self) -> u64 {
+        self.limit.unwrap_or(DEFAULT_FEED_LIMIT).clamp(1, MAX_FEED_LIMIT)
+    }
+}
+
+/// Position of the last post on a feed page, the next page starts right after it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FeedCursor {
+    pub published_at: DateTime<FixedOffset>,
+    pub id: i32,
+}
+
+impl FeedCursor {
+    // Opaque to clients, it only has to survive a round trip through a query string
+    pub fn encode(&self) -> String {
+        hex::encode(format!("{}:{}", self.published_at.timestamp_micros(), self.id))
+    }
+
+    pub fn decode(cursor: &str) -> Option<Self> {
+        let decoded = String::from_utf8(hex::decode(cursor).ok()?).ok()?;
+        let (micros, id) = decoded.split_once(':')?;
+
+        Some(FeedCursor {
+            published_at: DateTime::from_timestamp_micros(micros.parse().ok()?)?.fixed_offset(),
+            id: id.parse().ok()?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+
+    #[test]
+    fn cursor_survives_a_round_trip() {
+        let published_at = FixedOffset::east_opt(2 * 3600)
+            .unwrap()
+            .with_ymd_and_hms(2024, 5, 17, 9, 30, 0)
+            .unwrap()
+            + chrono::Duration::microseconds(123_456);
+        let cursor = FeedCursor { published_at, id: 42 };
+
+        let decoded = FeedCursor::decode(&cursor.encode()).unwrap();
+
+        // The offset is not encoded, the instant and the id are what the keyset compares
+        assert_eq!(decoded.published_at, published_at);
+        assert_eq!(decoded.id, 42);
+    }
+
+    #[test]
+    fn cursor_encoding_is_url_safe() {
+        let cursor = FeedCursor { published_at: DateTime::from_timestamp_micros(-1).unwrap().fixed_offset(), id: -7 };
+
+        assert!(cursor.encode().chars().all(|c| c.is_ascii_hexdigit()));
+        assert_eq!(FeedCursor::decode(&cursor.encode()), Some(cursor));
+    }
+
+    #[test]
+    fn decode_rejects_malformed_cursors() {
+        for cursor in [
+            "",
+            "not hex",
+            "abc",
+            &hex::encode("1715938200000000"),
+            &hex::encode("later:42"),
+            &hex::encode("1715938200000000:x"),
+            &hex::encode("99999999999999999999:1"),
+            &hex::encode([0xFF, 0xFE]),
+        ] {
+            assert_eq!(FeedCursor::decode(cursor), None, "{:?}", cursor);
+        }
+    }
+}
@@ -0,0 +1,8 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Serialize, Deserialize, Clone, Debug, ToSchema)]
+pub struct UpdateMediaRequest {
+    pub id: Option<i32>,
+    pub alt_text: String,
+}
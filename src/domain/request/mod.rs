@@ -3,16 +3,33 @@ mod post;
 mod comment;
 mod user;
 mod auth;
+mod tag;
+mod media;
+mod trash;
+mod reaction;
+mod bookmark;
+mod follow;
+mod notification;
 
-pub use self::category::{CreateCategoryRequest, UpdateCategoryRequest};
+pub use self::category::{CategoryDeleteStrategy, CreateCategoryRequest, DeleteCategoryQuery, MergeCategoriesRequest, UpdateCategoryRequest};
 pub use self::post::{
     CreatePostRequest,
-    UpdatePostRequest
+    UpdatePostRequest,
+    PostListQuery,
+    PostRelationQuery,
+    PostSort,
+    SortOrder
 };
 
 
 pub use self::comment::{
+    CommentFormat,
+    CommentSort,
     CreateCommentRequest,
+    ModerateCommentsRequest,
+    ModerationAction,
+    ModerationQueueQuery,
+    PostCommentsQuery,
     UpdateCommentRequest
 };
 
@@ -24,4 +41,27 @@ pub use self::auth::{
 pub use self::user::{
     CreateUserRequest,
     UpdateUserRequest
-};
\ No newline at end of file
+};
+
+pub use self::tag::{
+    MergeTagRequest,
+    RenameTagRequest
+};
+
+pub use self::media::UpdateMediaRequest;
+
+pub use self::trash::TrashKind;
+
+
+pub use self::reaction::{CommentVoteRequest, VoteDirection};
+
+pub use self::bookmark::{BookmarkListQuery, SaveBookmarkRequest, MAX_BOOKMARK_FOLDER_LENGTH};
+
+pub use self::follow::{FeedCursor, FeedQuery};
+
+pub use self::notification::{
+    NewNotification,
+    NotificationListQuery,
+    NotificationPreferenceRequest,
+    UpdateNotificationPreferencesRequest
+};
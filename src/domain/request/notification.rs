@@ -0,0 +1,53 @@
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+
+use crate::entities::sea_orm_active_enums::NotificationKind;
+
+pub const DEFAULT_NOTIFICATIONS_PER_PAGE: u64 = 20;
+pub const MAX_NOTIFICATIONS_PER_PAGE: u64 = 100;
+
+// Built by other services, never read from a request body
+#[derive(Debug, Clone)]
+pub struct NewNotification {
+    /// Who receives the notification
+    pub user_id: i32,
+    /// Who caused it, nobody is notified about their own actions
+    pub actor_id: Option<i32>,
+    pub kind: NotificationKind,
+    pub post_id: Option<i32>,
+    pub comment_id: Option<i32>,
+    pub message: String,
+}
+
+#[derive(Debug, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct NotificationListQuery {
+    /// Only list notifications that have not been read yet
+    pub unread: Option<bool>,
+    /// Starts at 1
+    pub page: Option<u64>,
+    /// Defaults to 20, at most 100
+    pub per_page: Option<u64>,
+}
+
+impl NotificationListQuery {
+    pub fn page(&self) -> u64 {
+        self.page.unwrap_or(1).max(1)
+    }
+
+    pub fn per_page(&self) -> u64 {
+        self.per_page.unwrap_or(DEFAULT_NOTIFICATIONS_PER_PAGE).clamp(1, MAX_NOTIFICATIONS_PER_PAGE)
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
+pub struct NotificationPreferenceRequest {
+    pub kind: NotificationKind,
+    pub enabled: bool,
+}
+
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
+pub struct UpdateNotificationPreferencesRequest {
+    /// Kinds left out keep their current setting
+    pub preferences: Vec<NotificationPreferenceRequest>,
+}
@@ -1,14 +1,26 @@
+use chrono::{DateTime, FixedOffset};
 use serde::{Deserialize, Serialize};
-use utoipa::ToSchema;
+use utoipa::{IntoParams, ToSchema};
+
+use crate::domain::{CommentFormat, CommentSort, PostCommentsQuery};
+use crate::entities::sea_orm_active_enums::{BodyFormat, PostStatus};
 
 #[derive(Debug, Deserialize, Serialize, ToSchema)]
 pub struct CreatePostRequest {
     pub title: String,
     pub body: String,
+    pub body_format: Option<BodyFormat>,
     pub img: String,
     pub category_id: i32,
     pub user_id: i32,
     pub user_name: String,
+    pub status: Option<PostStatus>,
+    pub publish_at: Option<DateTime<FixedOffset>>,
+    pub tags: Option<Vec<String>>,
+    pub media_ids: Option<Vec<i32>>,
+    // Set from the token by the handler, never read from the request body
+    #[serde(skip)]
+    pub editor_id: Option<i32>,
 }
 
 #[derive(Debug, Deserialize, Serialize, ToSchema)]
@@ -16,8 +28,77 @@ pub struct UpdatePostRequest {
     pub post_id: Option<i32>,
     pub title: String,
     pub body: String,
+    pub body_format: Option<BodyFormat>,
     pub img: String,
     pub category_id: i32,
-    pub user_id: i32,
-    pub user_name: String,
-}
\ No newline at end of file
+    pub status: Option<PostStatus>,
+    pub publish_at: Option<DateTime<FixedOffset>>,
+    pub tags: Option<Vec<String>>,
+    pub media_ids: Option<Vec<i32>>,
+    // Set from the token by the handler, never read from the request body
+    #[serde(skip)]
+    pub editor_id: Option<i32>,
+}
+
+#[derive(Debug, Clone, Copy, Default, Deserialize, Serialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum PostSort {
+    #[default]
+    PublishedAt,
+    CreatedAt,
+    UpdatedAt,
+    Title,
+}
+
+#[derive(Debug, Clone, Copy, Default, Deserialize, Serialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum SortOrder {
+    Asc,
+    #[default]
+    Desc,
+}
+
+#[derive(Debug, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct PostListQuery {
+    /// Field to sort by, defaults to published_at
+    pub sort: Option<PostSort>,
+    /// Sort direction, defaults to desc
+    pub order: Option<SortOrder>,
+    /// Only list posts filed under this category
+    pub category_id: Option<i32>,
+    /// Also list posts from the subcategories of category_id
+    pub include_descendants: Option<bool>,
+}
+
+#[derive(Debug, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct PostRelationQuery {
+    /// Comma separated parts to load from author, category, comments and toc. Defaults to author,category,comments
+    pub include: Option<String>,
+    /// Order of the comment threads, defaults to oldest
+    #[serde(default)]
+    pub sort: CommentSort,
+    /// Page of top level comments, starts at 1
+    pub page: Option<u64>,
+    /// Top level comments per page, defaults to 20, at most 100
+    pub per_page: Option<u64>,
+}
+
+impl PostRelationQuery {
+    pub fn includes(&self, name: &str) -> bool {
+        match &self.include {
+            Some(include) => include.split(',').any(|item| item.trim() == name),
+            None => matches!(name, "author" | "category" | "comments"),
+        }
+    }
+
+    pub fn comments_query(&self) -> PostCommentsQuery {
+        PostCommentsQuery {
+            format: CommentFormat::Tree,
+            sort: self.sort,
+            page: self.page,
+            per_page: self.per_page,
+        }
+    }
+}
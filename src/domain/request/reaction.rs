@@ -0,0 +1,32 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum VoteDirection {
+    Up,
+    Down,
+}
+
+impl VoteDirection {
+    // Stored as 1 or -1 so a comment's score is the sum of its votes
+    pub fn value(self) -> i16 {
+        match self {
+            VoteDirection::Up => 1,
+            VoteDirection::Down => -1,
+        }
+    }
+
+    pub fn from_value(value: i16) -> Self {
+        if value > 0 {
+            VoteDirection::Up
+        } else {
+            VoteDirection::Down
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
+pub struct CommentVoteRequest {
+    pub direction: VoteDirection,
+}
@@ -0,0 +1,13 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Serialize, Deserialize, Clone, Debug, ToSchema)]
+pub struct RenameTagRequest {
+    pub id: Option<i32>,
+    pub name: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, ToSchema)]
+pub struct MergeTagRequest {
+    pub target_id: i32,
+}
\ No newline at end of file
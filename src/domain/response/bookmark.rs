@@ -0,0 +1,47 @@
+use chrono::{DateTime, FixedOffset};
+use serde::Serialize;
+use utoipa::ToSchema;
+
+use crate::entities::bookmarks;
+
+use super::PostResponse;
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct BookmarkResponse {
+    pub id: i32,
+    pub post_id: i32,
+    pub folder: Option<String>,
+    pub note: Option<String>,
+    pub created_at: DateTime<FixedOffset>,
+    pub updated_at: DateTime<FixedOffset>,
+    pub post: PostResponse,
+}
+
+impl BookmarkResponse {
+    pub fn new(bookmark: bookmarks::Model, post: PostResponse) -> Self {
+        BookmarkResponse {
+            id: bookmark.id,
+            post_id: bookmark.post_id,
+            folder: bookmark.folder,
+            note: bookmark.note,
+            created_at: bookmark.created_at,
+            updated_at: bookmark.updated_at,
+            post,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct BookmarkPageResponse {
+    pub bookmarks: Vec<BookmarkResponse>,
+    pub page: u64,
+    pub per_page: u64,
+    pub total: u64,
+    pub total_pages: u64,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct BookmarkFolderResponse {
+    pub folder: String,
+    pub count: i64,
+}
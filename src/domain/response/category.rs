@@ -1,3 +1,7 @@
+use std::collections::{HashMap, HashSet};
+
+use chrono::{DateTime, FixedOffset};
+use crate::domain::CategoryDeleteStrategy;
 use crate::entities::categories;
 use utoipa::ToSchema;
 use serde::Serialize;
@@ -7,13 +11,123 @@ use serde::Serialize;
 pub struct CategoryResponse {
     pub id: i32,
     pub name: String,
+    pub slug: String,
+    pub parent_id: Option<i32>,
+    pub description: String,
+    pub display_order: i32,
+    pub cover_img: Option<String>,
+    /// Whether new comments need approval, null follows the global setting
+    pub comment_moderation: Option<bool>,
+    /// Published posts filed directly under this category
+    pub post_count: i64,
+    pub created_at: DateTime<FixedOffset>,
+    pub updated_at: DateTime<FixedOffset>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deleted_at: Option<DateTime<FixedOffset>>,
 }
 
-impl From<categories::Model> for CategoryResponse {
-    fn from(category: categories::Model) -> Self {
+impl From<(categories::Model, i64)> for CategoryResponse {
+    fn from((category, post_count): (categories::Model, i64)) -> Self {
         CategoryResponse {
             id: category.id,
             name: category.name,
+            slug: category.slug,
+            parent_id: category.parent_id,
+            description: category.description,
+            display_order: category.display_order,
+            cover_img: category.cover_img,
+            comment_moderation: category.comment_moderation,
+            post_count,
+            created_at: category.created_at,
+            updated_at: category.updated_at,
+            deleted_at: category.deleted_at,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct CategoryDeletionResponse {
+    pub strategy: CategoryDeleteStrategy,
+    pub affected_posts: u64,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct CategoryTreeResponse {
+    pub id: i32,
+    pub name: String,
+    pub slug: String,
+    pub description: String,
+    pub display_order: i32,
+    pub cover_img: Option<String>,
+    /// Published posts filed directly under this category
+    pub post_count: i64,
+    /// Published posts in this category and all of its descendants
+    pub total_post_count: i64,
+    #[schema(no_recursion)]
+    pub children: Vec<CategoryTreeResponse>,
+}
+
+impl CategoryTreeResponse {
+    // Categories whose parent is missing, for example because it was trashed, become roots
+    pub fn build(categories: Vec<(categories::Model, i64)>) -> Vec<Self> {
+        let ids: HashSet<i32> = categories.iter().map(|(category, _)| category.id).collect();
+        let mut children: HashMap<Option<i32>, Vec<(categories::Model, i64)>> = HashMap::new();
+
+        for (category, post_count) in categories {
+            let parent = category.parent_id.filter(|parent_id| ids.contains(parent_id));
+            children.entry(parent).or_default().push((category, post_count));
         }
+
+        Self::build_level(None, &mut children)
+    }
+
+    fn build_level(
+        parent: Option<i32>,
+        children: &mut HashMap<Option<i32>, Vec<(categories::Model, i64)>>,
+    ) -> Vec<Self> {
+        let mut level = children.remove(&parent).unwrap_or_default();
+        level.sort_by(|(a, _), (b, _)| a.display_order.cmp(&b.display_order).then_with(|| a.name.cmp(&b.name)));
+
+        level
+            .into_iter()
+            .map(|(category, post_count)| {
+                let nested = Self::build_level(Some(category.id), children);
+
+                CategoryTreeResponse {
+                    id: category.id,
+                    name: category.name,
+                    slug: category.slug,
+                    description: category.description,
+                    display_order: category.display_order,
+                    cover_img: category.cover_img,
+                    post_count,
+                    total_post_count: post_count + nested.iter().map(|child| child.total_post_count).sum::<i64>(),
+                    children: nested,
+                }
+            })
+            .collect()
+    }
+
+    pub fn find(tree: Vec<Self>, id: i32) -> Option<Self> {
+        tree.into_iter().find_map(|node| {
+            if node.id == id {
+                Some(node)
+            } else {
+                Self::find(node.children, id)
+            }
+        })
     }
 }
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct CategoryMergeResponse {
+    pub target_id: i32,
+    pub source_ids: Vec<i32>,
+    pub dry_run: bool,
+    /// Posts moved from the sources to the target, trashed posts included
+    pub moved_posts: u64,
+    /// Subcategories of the sources now nested under the target
+    pub moved_children: u64,
+    /// Slugs that now redirect to the target
+    pub redirected_slugs: Vec<String>,
+}
@@ -1,15 +1,37 @@
+use std::collections::{HashMap, HashSet};
+
+use chrono::{DateTime, FixedOffset};
 use utoipa::ToSchema;
 use serde::{Deserialize, Serialize};
 
 
-use crate::entities::comments;
+use crate::domain::{CommentSort, VoteDirection};
+use crate::entities::{comment_revisions, comments, sea_orm_active_enums::CommentStatus};
+
+pub const DELETED_COMMENT_PLACEHOLDER: &str = "[deleted]";
 
 #[derive(Debug, Deserialize, Serialize, ToSchema)]
 pub struct CommentResponse {
     pub id: i32,
     pub id_post_comment: i32,
+    pub parent_id: Option<i32>,
+    pub user_id: Option<i32>,
     pub user_name_comment: String,
     pub comment: String,
+    pub status: CommentStatus,
+    pub created_at: DateTime<FixedOffset>,
+    pub updated_at: DateTime<FixedOffset>,
+    /// Set once the text has been changed after posting
+    pub edited_at: Option<DateTime<FixedOffset>>,
+    pub upvotes: i64,
+    pub downvotes: i64,
+    /// Upvotes minus downvotes
+    pub score: i64,
+    /// Vote of the signed in reader, only present when they voted
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub my_vote: Option<VoteDirection>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deleted_at: Option<DateTime<FixedOffset>>,
 }
 
 impl From<comments::Model> for CommentResponse {
@@ -17,8 +39,375 @@ impl From<comments::Model> for CommentResponse {
         CommentResponse {
             id: comment.id,
             id_post_comment: comment.id_post_comment,
+            parent_id: comment.parent_id,
+            user_id: comment.user_id,
             user_name_comment: comment.user_name_comment,
             comment: comment.comment,
+            status: comment.status,
+            created_at: comment.created_at,
+            updated_at: comment.updated_at,
+            edited_at: comment.edited_at,
+            upvotes: 0,
+            downvotes: 0,
+            score: 0,
+            my_vote: None,
+            deleted_at: comment.deleted_at,
         }
     }
-}
\ No newline at end of file
+}
+
+impl CommentResponse {
+    pub fn set_votes(&mut self, upvotes: i64, downvotes: i64, my_vote: Option<VoteDirection>) {
+        self.upvotes = upvotes;
+        self.downvotes = downvotes;
+        self.score = upvotes - downvotes;
+        self.my_vote = my_vote;
+    }
+
+    // Hidden comments that still have visible replies keep their place in the thread without their content
+    fn placeholder(comment: comments::Model) -> Self {
+        let mut response = CommentResponse::from(comment);
+        response.user_id = None;
+        response.user_name_comment = DELETED_COMMENT_PLACEHOLDER.to_string();
+        response.comment = DELETED_COMMENT_PLACEHOLDER.to_string();
+        response
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct CommentThreadResponse {
+    #[serde(flatten)]
+    pub comment: CommentResponse,
+    /// Replies at any depth below this comment
+    pub reply_count: usize,
+    #[schema(no_recursion)]
+    pub replies: Vec<CommentThreadResponse>,
+}
+
+impl CommentThreadResponse {
+    // Expects whole threads, trashed and unmoderated comments included. Comments failing
+    // `visible` are dropped unless they have visible replies, then they become "[deleted]" placeholders
+    pub fn build(comments: Vec<comments::Model>, visible: impl Fn(&comments::Model) -> bool) -> Vec<Self> {
+        let ids: HashSet<i32> = comments.iter().map(|comment| comment.id).collect();
+        let mut replies: HashMap<Option<i32>, Vec<comments::Model>> = HashMap::new();
+
+        for comment in comments {
+            let parent = comment.parent_id.filter(|parent_id| ids.contains(parent_id));
+            replies.entry(parent).or_default().push(comment);
+        }
+
+        Self::build_level(None, &mut replies, &visible)
+    }
+
+    fn build_level(
+        parent: Option<i32>,
+        replies: &mut HashMap<Option<i32>, Vec<comments::Model>>,
+        visible: &impl Fn(&comments::Model) -> bool,
+    ) -> Vec<Self> {
+        let mut level = replies.remove(&parent).unwrap_or_default();
+        level.sort_by(|a, b| a.created_at.cmp(&b.created_at).then_with(|| a.id.cmp(&b.id)));
+
+        level
+            .into_iter()
+            .filter_map(|comment| {
+                let nested = Self::build_level(Some(comment.id), replies, visible);
+
+                let comment = if visible(&comment) {
+                    CommentResponse::from(comment)
+                } else if !nested.is_empty() {
+                    CommentResponse::placeholder(comment)
+                } else {
+                    return None;
+                };
+
+                Some(CommentThreadResponse {
+                    comment,
+                    reply_count: nested.iter().map(|reply| reply.reply_count + 1).sum(),
+                    replies: nested,
+                })
+            })
+            .collect()
+    }
+
+    // `build` returns oldest first, every level is reordered the same way
+    pub fn sort(threads: &mut [Self], sort: CommentSort) {
+        match sort {
+            CommentSort::Oldest => {}
+            CommentSort::Newest => threads.reverse(),
+            CommentSort::Top => threads.sort_by_key(|thread| std::cmp::Reverse(thread.reply_count)),
+            CommentSort::Score => threads.sort_by_key(|thread| std::cmp::Reverse(thread.comment.score)),
+        }
+
+        for thread in threads {
+            Self::sort(&mut thread.replies, sort);
+        }
+    }
+
+    pub fn for_each_comment(threads: &mut [Self], f: &mut impl FnMut(&mut CommentResponse)) {
+        for thread in threads {
+            f(&mut thread.comment);
+            Self::for_each_comment(&mut thread.replies, f);
+        }
+    }
+
+    pub fn flatten(threads: Vec<Self>) -> Vec<FlatCommentResponse> {
+        let mut flat = Vec::new();
+        Self::flatten_into(threads, 0, "", &mut flat);
+        flat
+    }
+
+    fn flatten_into(threads: Vec<Self>, depth: usize, prefix: &str, flat: &mut Vec<FlatCommentResponse>) {
+        for thread in threads {
+            let path = if prefix.is_empty() {
+                thread.comment.id.to_string()
+            } else {
+                format!("{}/{}", prefix, thread.comment.id)
+            };
+
+            flat.push(FlatCommentResponse {
+                comment: thread.comment,
+                reply_count: thread.reply_count,
+                depth,
+                path: path.clone(),
+            });
+            Self::flatten_into(thread.replies, depth + 1, &path, flat);
+        }
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct FlatCommentResponse {
+    #[serde(flatten)]
+    pub comment: CommentResponse,
+    /// Replies at any depth below this comment
+    pub reply_count: usize,
+    /// 0 for top level comments
+    pub depth: usize,
+    /// Ids from the top level comment down to this one, for example 12/15/19
+    pub path: String,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct CommentPageResponse<T> {
+    pub comments: Vec<T>,
+    pub page: u64,
+    pub per_page: u64,
+    /// Top level comments across all pages
+    pub total_threads: u64,
+    pub total_pages: u64,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct CommentModerationResponse {
+    pub status: CommentStatus,
+    /// Comments whose status changed, unknown and trashed ids are skipped
+    pub updated: u64,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct CommentRevisionResponse {
+    pub id: i32,
+    pub comment_id: i32,
+    /// Who made the edit that replaced this text
+    pub user_id: Option<i32>,
+    pub comment: String,
+    pub created_at: DateTime<FixedOffset>,
+}
+
+impl From<comment_revisions::Model> for CommentRevisionResponse {
+    fn from(revision: comment_revisions::Model) -> Self {
+        CommentRevisionResponse {
+            id: revision.id,
+            comment_id: revision.comment_id,
+            user_id: revision.user_id,
+            comment: revision.comment,
+            created_at: revision.created_at,
+        }
+    }
+}
+
+// Edits can be refused for reasons other than bad input, the handler turns this into a 403
+pub enum CommentEdit<T> {
+    Edited(T),
+    Forbidden(String),
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, TimeZone, Utc};
+
+    use super::*;
+
+    fn comment(id: i32, parent_id: Option<i32>, status: CommentStatus, minute: i64) -> comments::Model {
+        let created_at = (Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap() + Duration::minutes(minute)).fixed_offset();
+
+        comments::Model {
+            id,
+            id_post_comment: 1,
+            parent_id,
+            user_id: Some(id * 10),
+            user_name_comment: format!("user {}", id),
+            comment: format!("comment {}", id),
+            status,
+            created_at,
+            updated_at: created_at,
+            edited_at: None,
+            deleted_at: None,
+        }
+    }
+
+    fn approved(comment: &comments::Model) -> bool {
+        comment.status == CommentStatus::Approved
+    }
+
+    fn ids(threads: &[CommentThreadResponse]) -> Vec<i32> {
+        threads.iter().map(|thread| thread.comment.id).collect()
+    }
+
+    #[test]
+    fn build_nests_replies_oldest_first() {
+        let threads = CommentThreadResponse::build(
+            vec![
+                comment(3, Some(1), CommentStatus::Approved, 3),
+                comment(2, None, CommentStatus::Approved, 2),
+                comment(1, None, CommentStatus::Approved, 1),
+                comment(4, Some(3), CommentStatus::Approved, 4),
+            ],
+            approved,
+        );
+
+        assert_eq!(ids(&threads), [1, 2]);
+        assert_eq!(ids(&threads[0].replies), [3]);
+        assert_eq!(ids(&threads[0].replies[0].replies), [4]);
+        assert_eq!(threads[0].reply_count, 2);
+        assert_eq!(threads[1].reply_count, 0);
+    }
+
+    #[test]
+    fn build_turns_hidden_comments_with_visible_replies_into_placeholders() {
+        let threads = CommentThreadResponse::build(
+            vec![
+                comment(1, None, CommentStatus::Rejected, 1),
+                comment(2, Some(1), CommentStatus::Spam, 2),
+                comment(3, Some(2), CommentStatus::Approved, 3),
+            ],
+            approved,
+        );
+
+        assert_eq!(ids(&threads), [1]);
+        assert_eq!(threads[0].reply_count, 2);
+
+        for placeholder in [&threads[0].comment, &threads[0].replies[0].comment] {
+            assert_eq!(placeholder.comment, DELETED_COMMENT_PLACEHOLDER);
+            assert_eq!(placeholder.user_name_comment, DELETED_COMMENT_PLACEHOLDER);
+            assert_eq!(placeholder.user_id, None);
+        }
+        assert_eq!(threads[0].replies[0].replies[0].comment.comment, "comment 3");
+    }
+
+    #[test]
+    fn build_drops_hidden_comments_without_visible_replies() {
+        let threads = CommentThreadResponse::build(
+            vec![
+                comment(1, None, CommentStatus::Approved, 1),
+                comment(2, Some(1), CommentStatus::Rejected, 2),
+                comment(3, Some(2), CommentStatus::Pending, 3),
+                comment(4, None, CommentStatus::Spam, 4),
+            ],
+            approved,
+        );
+
+        assert_eq!(ids(&threads), [1]);
+        assert!(threads[0].replies.is_empty());
+        assert_eq!(threads[0].reply_count, 0);
+    }
+
+    #[test]
+    fn build_treats_replies_to_missing_parents_as_top_level() {
+        let threads = CommentThreadResponse::build(vec![comment(5, Some(99), CommentStatus::Approved, 1)], approved);
+
+        assert_eq!(ids(&threads), [5]);
+    }
+
+    #[test]
+    fn sort_reorders_every_level() {
+        let comments = || {
+            vec![
+                comment(1, None, CommentStatus::Approved, 1),
+                comment(2, None, CommentStatus::Approved, 2),
+                comment(3, Some(2), CommentStatus::Approved, 3),
+                comment(4, Some(2), CommentStatus::Approved, 4),
+                comment(5, Some(3), CommentStatus::Approved, 5),
+            ]
+        };
+
+        let mut newest = CommentThreadResponse::build(comments(), approved);
+        CommentThreadResponse::sort(&mut newest, CommentSort::Newest);
+        assert_eq!(ids(&newest), [2, 1]);
+        assert_eq!(ids(&newest[0].replies), [4, 3]);
+
+        let mut top = CommentThreadResponse::build(comments(), approved);
+        CommentThreadResponse::sort(&mut top, CommentSort::Top);
+        assert_eq!(ids(&top), [2, 1]);
+        assert_eq!(ids(&top[0].replies), [3, 4]);
+
+        let mut score = CommentThreadResponse::build(comments(), approved);
+        CommentThreadResponse::for_each_comment(&mut score, &mut |comment| {
+            if comment.id == 1 || comment.id == 4 {
+                comment.set_votes(2, 0, None);
+            }
+        });
+        CommentThreadResponse::sort(&mut score, CommentSort::Score);
+        assert_eq!(ids(&score), [1, 2]);
+        assert_eq!(ids(&score[1].replies), [4, 3]);
+    }
+
+    #[test]
+    fn sort_keeps_the_oldest_first_on_ties() {
+        let mut threads = CommentThreadResponse::build(
+            vec![
+                comment(1, None, CommentStatus::Approved, 1),
+                comment(2, None, CommentStatus::Approved, 2),
+                comment(3, None, CommentStatus::Approved, 3),
+            ],
+            approved,
+        );
+
+        CommentThreadResponse::sort(&mut threads, CommentSort::Top);
+        assert_eq!(ids(&threads), [1, 2, 3]);
+
+        CommentThreadResponse::sort(&mut threads, CommentSort::Score);
+        assert_eq!(ids(&threads), [1, 2, 3]);
+    }
+
+    #[test]
+    fn flatten_lists_threads_depth_first_with_depth_and_path() {
+        let threads = CommentThreadResponse::build(
+            vec![
+                comment(12, None, CommentStatus::Approved, 1),
+                comment(15, Some(12), CommentStatus::Approved, 2),
+                comment(19, Some(15), CommentStatus::Approved, 3),
+                comment(16, Some(12), CommentStatus::Approved, 4),
+                comment(20, None, CommentStatus::Approved, 5),
+            ],
+            approved,
+        );
+
+        let flat = CommentThreadResponse::flatten(threads);
+        let rows: Vec<(i32, usize, &str, usize)> = flat
+            .iter()
+            .map(|comment| (comment.comment.id, comment.depth, comment.path.as_str(), comment.reply_count))
+            .collect();
+
+        assert_eq!(
+            rows,
+            [
+                (12, 0, "12", 3),
+                (15, 1, "12/15", 1),
+                (19, 2, "12/15/19", 0),
+                (16, 1, "12/16", 0),
+                (20, 0, "20", 0),
+            ]
+        );
+    }
+}
@@ -0,0 +1,17 @@
+use serde::Serialize;
+use utoipa::ToSchema;
+
+use super::PostResponse;
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct FeedResponse {
+    pub posts: Vec<PostResponse>,
+    /// Pass as cursor to get the next page, absent on the last page
+    pub next_cursor: Option<String>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct FollowingResponse {
+    pub user_ids: Vec<i32>,
+    pub category_ids: Vec<i32>,
+}
@@ -0,0 +1,39 @@
+use chrono::{DateTime, FixedOffset};
+use serde::Serialize;
+use utoipa::ToSchema;
+
+use crate::entities::media;
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct MediaResponse {
+    pub id: i32,
+    pub user_id: Option<i32>,
+    pub filename: String,
+    pub original_name: String,
+    pub url: String,
+    pub mime: String,
+    pub size: i32,
+    pub width: Option<i32>,
+    pub height: Option<i32>,
+    pub alt_text: String,
+    pub created_at: DateTime<FixedOffset>,
+}
+
+impl MediaResponse {
+    // The public URL depends on the storage backend, so the caller supplies it
+    pub fn from_model(media: media::Model, url: String) -> Self {
+        MediaResponse {
+            id: media.id,
+            user_id: media.user_id,
+            filename: media.filename,
+            original_name: media.original_name,
+            url,
+            mime: media.mime,
+            size: media.size,
+            width: media.width,
+            height: media.height,
+            alt_text: media.alt_text,
+            created_at: media.created_at,
+        }
+    }
+}
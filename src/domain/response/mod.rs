@@ -7,16 +7,37 @@ mod category;
 mod post;
 mod comment;
 mod user;
+mod slug;
+mod post_revision;
+mod tag;
+mod upload;
+mod media;
+mod trash;
+mod reaction;
+mod bookmark;
+mod follow;
+mod notification;
 
 use crate::utils::AppError;
 
-pub use self::category::CategoryResponse;
+pub use self::category::{CategoryDeletionResponse, CategoryMergeResponse, CategoryResponse, CategoryTreeResponse};
 pub use self::post::{
     PostResponse,
-    PostRelationResponse
+    PostAggregateResponse,
+    TocEntryResponse
 };
-pub use self::comment::CommentResponse;
+pub use self::comment::{CommentEdit, CommentModerationResponse, CommentPageResponse, CommentResponse, CommentRevisionResponse, CommentThreadResponse, FlatCommentResponse};
 pub use self::user::UserResponse;
+pub use self::slug::{SlugLookup, SlugRedirectResponse};
+pub use self::tag::TagResponse;
+pub use self::upload::{ImageVariantResponse, StoredFile, UploadResponse};
+pub use self::media::MediaResponse;
+pub use self::trash::TrashResponse;
+pub use self::reaction::{CommentVoteResponse, PostReactionsResponse, ReactionCountResponse};
+pub use self::bookmark::{BookmarkFolderResponse, BookmarkPageResponse, BookmarkResponse};
+pub use self::follow::{FeedResponse, FollowingResponse};
+pub use self::notification::{MarkAllReadResponse, NotificationPageResponse, NotificationPreferenceResponse, NotificationResponse, UnreadCountResponse};
+pub use self::post_revision::{DiffLineResponse, PostRevisionDiffResponse, PostRevisionResponse};
 
 
 #[derive(Debug, Serialize, ToSchema)]
@@ -53,6 +74,8 @@ impl From<AppError> for ErrorResponse {
             AppError::BcryptError(ref msg) => ("error".to_string(), format!("Bcrypt error: {}", msg)),
             AppError::InvalidCredentials => ("error".to_string(), "Invalid credentials".to_string()),
             AppError::EmailAlreadyExists => ("error".to_string(), "Email already exists".to_string()),
+            AppError::ValidationError(ref msg) => ("fail".to_string(), msg.clone()),
+            AppError::StorageError(_) => ("error".to_string(), "Storage error occurred".to_string()),
         };
         ErrorResponse { status, message }
     }
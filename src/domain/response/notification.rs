@@ -0,0 +1,62 @@
+use chrono::{DateTime, FixedOffset};
+use serde::Serialize;
+use utoipa::ToSchema;
+
+use crate::entities::{notifications, sea_orm_active_enums::NotificationKind};
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct NotificationResponse {
+    pub id: i32,
+    pub kind: NotificationKind,
+    pub actor_id: Option<i32>,
+    pub post_id: Option<i32>,
+    pub comment_id: Option<i32>,
+    pub message: String,
+    pub read: bool,
+    pub read_at: Option<DateTime<FixedOffset>>,
+    pub created_at: DateTime<FixedOffset>,
+}
+
+impl From<notifications::Model> for NotificationResponse {
+    fn from(notification: notifications::Model) -> Self {
+        NotificationResponse {
+            id: notification.id,
+            kind: notification.kind,
+            actor_id: notification.actor_id,
+            post_id: notification.post_id,
+            comment_id: notification.comment_id,
+            message: notification.message,
+            read: notification.read_at.is_some(),
+            read_at: notification.read_at,
+            created_at: notification.created_at,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct NotificationPageResponse {
+    pub notifications: Vec<NotificationResponse>,
+    pub page: u64,
+    pub per_page: u64,
+    pub total: u64,
+    pub total_pages: u64,
+    /// Unread notifications across all pages
+    pub unread: u64,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct UnreadCountResponse {
+    pub unread: u64,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct MarkAllReadResponse {
+    /// Notifications that were unread until now
+    pub updated: u64,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct NotificationPreferenceResponse {
+    pub kind: NotificationKind,
+    pub enabled: bool,
+}
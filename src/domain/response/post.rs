@@ -1,50 +1,141 @@
+use chrono::{DateTime, FixedOffset};
 use utoipa::ToSchema;
 use serde::Serialize;
 
-use crate::entities::{comments, posts};
+use crate::{
+    entities::{posts, sea_orm_active_enums::{BodyFormat, PostStatus, ReactionKind}},
+    utils::{excerpt, headings, plain_text, reading_time, render_body, word_count, Heading},
+};
+
+use super::{CategoryResponse, CommentPageResponse, CommentThreadResponse, ImageVariantResponse, MediaResponse, ReactionCountResponse, TagResponse, UserResponse};
 
 #[derive(Debug, Serialize, ToSchema)]
 pub struct PostResponse {
     pub id: i32,
     pub title: String,
+    pub slug: String,
+    pub img: String,
     pub body: String,
+    pub body_format: BodyFormat,
+    pub body_html: String,
+    pub excerpt: String,
+    pub word_count: usize,
+    /// Estimated reading time in minutes
+    pub reading_time: usize,
     pub category_id: i32,
     pub user_id: i32,
     pub user_name: String,
+    pub status: PostStatus,
+    pub published_at: Option<DateTime<FixedOffset>>,
+    pub publish_at: Option<DateTime<FixedOffset>>,
+    pub created_at: DateTime<FixedOffset>,
+    pub updated_at: DateTime<FixedOffset>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deleted_at: Option<DateTime<FixedOffset>>,
+    /// Approved comments, replies included
+    pub comment_count: i64,
+    pub reactions: Vec<ReactionCountResponse>,
+    /// Reactions left by the signed in reader, only present when a token was sent
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub my_reactions: Option<Vec<ReactionKind>>,
+    /// Whether the signed in reader saved the post, only present when a token was sent
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bookmarked: Option<bool>,
+    pub tags: Vec<TagResponse>,
+    /// Resized versions of img, narrowest first
+    pub srcset: Vec<ImageVariantResponse>,
+    pub media: Vec<MediaResponse>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub toc: Option<Vec<TocEntryResponse>>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct TocEntryResponse {
+    pub level: u8,
+    pub title: String,
+    pub anchor: String,
+    #[schema(no_recursion)]
+    pub children: Vec<TocEntryResponse>,
+}
+
+impl TocEntryResponse {
+    // Nests each heading under the closest preceding heading of a higher level
+    fn build(headings: &[Heading], index: &mut usize, parent_level: u8) -> Vec<Self> {
+        let mut entries = Vec::new();
+
+        while let Some(heading) = headings.get(*index).filter(|heading| heading.level > parent_level) {
+            *index += 1;
+            entries.push(TocEntryResponse {
+                level: heading.level,
+                title: heading.title.clone(),
+                anchor: heading.anchor.clone(),
+                children: Self::build(headings, index, heading.level),
+            });
+        }
+
+        entries
+    }
+}
+
+impl PostResponse {
+    // Plain text posts have no headings, so they get an empty table of contents
+    pub fn with_toc(mut self) -> Self {
+        let entries = match self.body_format {
+            BodyFormat::Markdown => TocEntryResponse::build(&headings(&self.body), &mut 0, 0),
+            BodyFormat::Plain => Vec::new(),
+        };
+
+        self.toc = Some(entries);
+        self
+    }
 }
 
 impl From<posts::Model> for PostResponse {
     fn from(post: posts::Model) -> Self {
+        // Only rows the startup backfill has not reached yet lack cached HTML
+        let body_html = post.body_html.unwrap_or_else(|| render_body(&post.body, post.body_format));
+        let text = plain_text(&post.body, post.body_format);
+        let word_count = word_count(&text);
+
         PostResponse {
             id: post.id,
             title: post.title,
+            slug: post.slug,
+            img: post.img,
             body: post.body,
+            body_format: post.body_format,
+            body_html,
+            excerpt: excerpt(&text),
+            word_count,
+            reading_time: reading_time(word_count),
             category_id: post.category_id,
             user_id: post.user_id,
             user_name: post.user_name,
+            status: post.status,
+            published_at: post.published_at,
+            publish_at: post.publish_at,
+            created_at: post.created_at,
+            updated_at: post.updated_at,
+            deleted_at: post.deleted_at,
+            comment_count: 0,
+            reactions: Vec::new(),
+            my_reactions: None,
+            bookmarked: None,
+            tags: Vec::new(),
+            srcset: Vec::new(),
+            media: Vec::new(),
+            toc: None,
         }
     }
 }
 
 #[derive(Debug, Serialize, ToSchema)]
-pub struct PostRelationResponse {
-    pub post_id: i32,
-    pub title: String,
-    pub comment_id: i32,
-    pub id_post_comment: i32,
-    pub user_name_comment: String,
-    pub comment: String,
-}
-
-impl PostRelationResponse {
-    pub fn from_post_and_comment(post: &posts::Model, comment: &comments::Model) -> Self {
-        PostRelationResponse {
-            post_id: post.id,
-            title: post.title.clone(),
-            comment_id: comment.id,
-            id_post_comment: comment.id_post_comment,
-            user_name_comment: comment.user_name_comment.clone(),
-            comment: comment.comment.clone(),
-        }
-    }
+pub struct PostAggregateResponse {
+    pub post: PostResponse,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub author: Option<UserResponse>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub category: Option<CategoryResponse>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub comments: Option<CommentPageResponse<CommentThreadResponse>>,
 }
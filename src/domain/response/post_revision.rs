@@ -0,0 +1,56 @@
+use chrono::{DateTime, FixedOffset};
+use utoipa::ToSchema;
+use serde::Serialize;
+
+use crate::entities::{post_revisions, sea_orm_active_enums::BodyFormat};
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct PostRevisionResponse {
+    pub id: i32,
+    pub post_id: i32,
+    pub user_id: Option<i32>,
+    pub title: String,
+    pub body: String,
+    pub img: String,
+    pub category_id: i32,
+    /// Missing on revisions recorded before formats and tags were tracked
+    pub body_format: Option<BodyFormat>,
+    pub tags: Option<Vec<String>>,
+    pub created_at: DateTime<FixedOffset>,
+}
+
+impl From<post_revisions::Model> for PostRevisionResponse {
+    fn from(revision: post_revisions::Model) -> Self {
+        PostRevisionResponse {
+            id: revision.id,
+            post_id: revision.post_id,
+            user_id: revision.user_id,
+            title: revision.title,
+            body: revision.body,
+            img: revision.img,
+            category_id: revision.category_id,
+            body_format: revision.body_format,
+            tags: revision.tags.and_then(|tags| serde_json::from_value(tags).ok()),
+            created_at: revision.created_at,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct DiffLineResponse {
+    pub tag: String,
+    pub old_line: Option<usize>,
+    pub new_line: Option<usize>,
+    pub content: String,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct PostRevisionDiffResponse {
+    pub post_id: i32,
+    pub from_revision: i32,
+    pub to_revision: i32,
+    pub title: Vec<DiffLineResponse>,
+    pub body: Vec<DiffLineResponse>,
+    pub img_changed: bool,
+    pub category_changed: bool,
+}
\ No newline at end of file
@@ -0,0 +1,27 @@
+use serde::Serialize;
+use utoipa::ToSchema;
+
+use crate::domain::VoteDirection;
+use crate::entities::sea_orm_active_enums::ReactionKind;
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct ReactionCountResponse {
+    pub kind: ReactionKind,
+    pub count: i64,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct PostReactionsResponse {
+    pub post_id: i32,
+    pub reactions: Vec<ReactionCountResponse>,
+    pub my_reactions: Vec<ReactionKind>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct CommentVoteResponse {
+    pub comment_id: i32,
+    pub upvotes: i64,
+    pub downvotes: i64,
+    pub score: i64,
+    pub my_vote: Option<VoteDirection>,
+}
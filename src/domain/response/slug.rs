@@ -0,0 +1,13 @@
+use utoipa::ToSchema;
+use serde::Serialize;
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SlugRedirectResponse {
+    pub slug: String,
+    pub location: String,
+}
+
+pub enum SlugLookup<T> {
+    Found(T),
+    Moved(SlugRedirectResponse),
+}
\ No newline at end of file
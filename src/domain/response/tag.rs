@@ -0,0 +1,24 @@
+use utoipa::ToSchema;
+use serde::Serialize;
+
+use crate::entities::tags;
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct TagResponse {
+    pub id: i32,
+    pub name: String,
+    pub slug: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub post_count: Option<i64>,
+}
+
+impl From<tags::Model> for TagResponse {
+    fn from(tag: tags::Model) -> Self {
+        TagResponse {
+            id: tag.id,
+            name: tag.name,
+            slug: tag.slug,
+            post_count: None,
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,12 @@
+use serde::Serialize;
+use utoipa::ToSchema;
+
+use super::{CategoryResponse, CommentResponse, PostResponse, UserResponse};
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct TrashResponse {
+    pub posts: Vec<PostResponse>,
+    pub categories: Vec<CategoryResponse>,
+    pub comments: Vec<CommentResponse>,
+    pub users: Vec<UserResponse>,
+}
@@ -0,0 +1,27 @@
+use serde::Serialize;
+use utoipa::ToSchema;
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct UploadResponse {
+    pub media_id: i32,
+    pub filename: String,
+    pub url: String,
+    pub content_type: String,
+    pub size: usize,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct ImageVariantResponse {
+    pub name: String,
+    pub url: String,
+    pub content_type: String,
+    pub width: i32,
+    pub height: i32,
+}
+
+// A stored file on its way back to the client, not serialised
+#[derive(Debug)]
+pub struct StoredFile {
+    pub bytes: Vec<u8>,
+    pub content_type: String,
+}
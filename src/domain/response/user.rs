@@ -1,6 +1,7 @@
+use chrono::{DateTime, FixedOffset};
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
-use crate::entities::users;
+use crate::entities::{sea_orm_active_enums::UserRole, users};
 
 
 #[derive(Debug, Deserialize, Serialize, ToSchema)]
@@ -9,6 +10,11 @@ pub struct UserResponse {
     pub firstname: String,
     pub lastname: String,
     pub email: String,
+    pub created_at: DateTime<FixedOffset>,
+    pub updated_at: DateTime<FixedOffset>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deleted_at: Option<DateTime<FixedOffset>>,
+    pub role: UserRole,
 }
 
 impl From<users::Model> for UserResponse {
@@ -18,6 +24,10 @@ impl From<users::Model> for UserResponse {
             firstname: user.firstname,
             lastname: user.lastname,
             email: user.email,
+            created_at: user.created_at,
+            updated_at: user.updated_at,
+            deleted_at: user.deleted_at,
+            role: user.role,
         }
     }
 }
\ No newline at end of file
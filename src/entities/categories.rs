@@ -1,6 +1,7 @@
 //! `SeaORM` Entity, @generated by sea-orm-codegen 1.1.0
 
 use sea_orm::entity::prelude::*;
+use sea_orm::Set;
 
 #[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq)]
 #[sea_orm(table_name = "categories")]
@@ -8,12 +9,39 @@ pub struct Model {
     #[sea_orm(primary_key)]
     pub id: i32,
     pub name: String,
+    #[sea_orm(unique)]
+    pub slug: String,
+    pub parent_id: Option<i32>,
+    #[sea_orm(column_type = "Text")]
+    pub description: String,
+    pub display_order: i32,
+    pub cover_img: Option<String>,
+    pub comment_moderation: Option<bool>,
+    pub created_at: DateTimeWithTimeZone,
+    pub updated_at: DateTimeWithTimeZone,
+    pub deleted_at: Option<DateTimeWithTimeZone>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
 pub enum Relation {
+    #[sea_orm(has_many = "super::category_slug_history::Entity")]
+    CategorySlugHistory,
     #[sea_orm(has_many = "super::posts::Entity")]
     Posts,
+    #[sea_orm(
+        belongs_to = "Entity",
+        from = "Column::ParentId",
+        to = "Column::Id",
+        on_update = "Cascade",
+        on_delete = "SetNull"
+    )]
+    SelfRef,
+}
+
+impl Related<super::category_slug_history::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::CategorySlugHistory.def()
+    }
 }
 
 impl Related<super::posts::Entity> for Entity {
@@ -22,4 +50,30 @@ impl Related<super::posts::Entity> for Entity {
     }
 }
 
-impl ActiveModelBehavior for ActiveModel {}
+impl Entity {
+    // Soft deleted rows stay in the table until purged, regular queries skip them
+    pub fn find_active() -> Select<Entity> {
+        Self::find().filter(Column::DeletedAt.is_null())
+    }
+
+    pub fn find_active_by_id(id: i32) -> Select<Entity> {
+        Self::find_active().filter(Column::Id.eq(id))
+    }
+}
+
+#[async_trait::async_trait]
+impl ActiveModelBehavior for ActiveModel {
+    async fn before_save<C>(mut self, _db: &C, insert: bool) -> Result<Self, DbErr>
+    where
+        C: ConnectionTrait,
+    {
+        let now: DateTimeWithTimeZone = chrono::Utc::now().into();
+
+        if insert {
+            self.created_at = Set(now);
+        }
+        self.updated_at = Set(now);
+
+        Ok(self)
+    }
+}
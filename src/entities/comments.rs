@@ -1,6 +1,9 @@
 //! `SeaORM` Entity, @generated by sea-orm-codegen 1.1.0
 
 use sea_orm::entity::prelude::*;
+use sea_orm::Set;
+
+use super::sea_orm_active_enums::CommentStatus;
 
 #[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq)]
 #[sea_orm(table_name = "comments")]
@@ -8,12 +11,23 @@ pub struct Model {
     #[sea_orm(primary_key)]
     pub id: i32,
     pub id_post_comment: i32,
+    pub parent_id: Option<i32>,
+    pub user_id: Option<i32>,
     pub user_name_comment: String,
     pub comment: String,
+    pub status: CommentStatus,
+    pub created_at: DateTimeWithTimeZone,
+    pub updated_at: DateTimeWithTimeZone,
+    pub edited_at: Option<DateTimeWithTimeZone>,
+    pub deleted_at: Option<DateTimeWithTimeZone>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
 pub enum Relation {
+    #[sea_orm(has_many = "super::comment_revisions::Entity")]
+    CommentRevisions,
+    #[sea_orm(has_many = "super::comment_votes::Entity")]
+    CommentVotes,
     #[sea_orm(
         belongs_to = "super::posts::Entity",
         from = "Column::IdPostComment",
@@ -22,6 +36,34 @@ pub enum Relation {
         on_delete = "Cascade"
     )]
     Posts,
+    #[sea_orm(
+        belongs_to = "Entity",
+        from = "Column::ParentId",
+        to = "Column::Id",
+        on_update = "Cascade",
+        on_delete = "NoAction"
+    )]
+    SelfRef,
+    #[sea_orm(
+        belongs_to = "super::users::Entity",
+        from = "Column::UserId",
+        to = "super::users::Column::Id",
+        on_update = "Cascade",
+        on_delete = "SetNull"
+    )]
+    Users,
+}
+
+impl Related<super::comment_revisions::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::CommentRevisions.def()
+    }
+}
+
+impl Related<super::comment_votes::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::CommentVotes.def()
+    }
 }
 
 impl Related<super::posts::Entity> for Entity {
@@ -30,4 +72,30 @@ impl Related<super::posts::Entity> for Entity {
     }
 }
 
-impl ActiveModelBehavior for ActiveModel {}
+impl Entity {
+    // Soft deleted rows stay in the table until purged, regular queries skip them
+    pub fn find_active() -> Select<Entity> {
+        Self::find().filter(Column::DeletedAt.is_null())
+    }
+
+    pub fn find_active_by_id(id: i32) -> Select<Entity> {
+        Self::find_active().filter(Column::Id.eq(id))
+    }
+}
+
+#[async_trait::async_trait]
+impl ActiveModelBehavior for ActiveModel {
+    async fn before_save<C>(mut self, _db: &C, insert: bool) -> Result<Self, DbErr>
+    where
+        C: ConnectionTrait,
+    {
+        let now: DateTimeWithTimeZone = chrono::Utc::now().into();
+
+        if insert {
+            self.created_at = Set(now);
+        }
+        self.updated_at = Set(now);
+
+        Ok(self)
+    }
+}
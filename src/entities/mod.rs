@@ -2,7 +2,24 @@
 
 pub mod prelude;
 
+pub mod bookmarks;
 pub mod categories;
+pub mod category_follows;
+pub mod category_slug_history;
+pub mod comment_revisions;
+pub mod comment_votes;
 pub mod comments;
+pub mod image_variants;
+pub mod media;
+pub mod notification_preferences;
+pub mod notifications;
+pub mod post_media;
+pub mod post_reactions;
+pub mod post_revisions;
+pub mod post_slug_history;
+pub mod post_tags;
 pub mod posts;
+pub mod sea_orm_active_enums;
+pub mod tags;
+pub mod user_follows;
 pub mod users;
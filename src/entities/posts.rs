@@ -1,6 +1,8 @@
 //! `SeaORM` Entity, @generated by sea-orm-codegen 1.1.0
 
+use super::sea_orm_active_enums::{BodyFormat, PostStatus};
 use sea_orm::entity::prelude::*;
+use sea_orm::Set;
 
 #[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq)]
 #[sea_orm(table_name = "posts")]
@@ -8,13 +10,23 @@ pub struct Model {
     #[sea_orm(primary_key)]
     pub id: i32,
     pub title: String,
+    #[sea_orm(unique)]
     pub slug: String,
     pub img: String,
     #[sea_orm(column_type = "Text")]
     pub body: String,
+    pub body_format: BodyFormat,
+    #[sea_orm(column_type = "Text", nullable)]
+    pub body_html: Option<String>,
     pub category_id: i32,
     pub user_id: i32,
     pub user_name: String,
+    pub status: PostStatus,
+    pub published_at: Option<DateTimeWithTimeZone>,
+    pub publish_at: Option<DateTimeWithTimeZone>,
+    pub created_at: DateTimeWithTimeZone,
+    pub updated_at: DateTimeWithTimeZone,
+    pub deleted_at: Option<DateTimeWithTimeZone>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -24,11 +36,23 @@ pub enum Relation {
         from = "Column::CategoryId",
         to = "super::categories::Column::Id",
         on_update = "Cascade",
-        on_delete = "Cascade"
+        on_delete = "Restrict"
     )]
     Categories,
+    #[sea_orm(has_many = "super::bookmarks::Entity")]
+    Bookmarks,
     #[sea_orm(has_many = "super::comments::Entity")]
     Comments,
+    #[sea_orm(has_many = "super::post_media::Entity")]
+    PostMedia,
+    #[sea_orm(has_many = "super::post_reactions::Entity")]
+    PostReactions,
+    #[sea_orm(has_many = "super::post_revisions::Entity")]
+    PostRevisions,
+    #[sea_orm(has_many = "super::post_slug_history::Entity")]
+    PostSlugHistory,
+    #[sea_orm(has_many = "super::post_tags::Entity")]
+    PostTags,
     #[sea_orm(
         belongs_to = "super::users::Entity",
         from = "Column::UserId",
@@ -39,6 +63,12 @@ pub enum Relation {
     Users,
 }
 
+impl Related<super::bookmarks::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Bookmarks.def()
+    }
+}
+
 impl Related<super::categories::Entity> for Entity {
     fn to() -> RelationDef {
         Relation::Categories.def()
@@ -51,10 +81,84 @@ impl Related<super::comments::Entity> for Entity {
     }
 }
 
+impl Related<super::post_media::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::PostMedia.def()
+    }
+}
+
+impl Related<super::media::Entity> for Entity {
+    fn to() -> RelationDef {
+        super::post_media::Relation::Media.def()
+    }
+    fn via() -> Option<RelationDef> {
+        Some(super::post_media::Relation::Posts.def().rev())
+    }
+}
+
+impl Related<super::post_reactions::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::PostReactions.def()
+    }
+}
+
+impl Related<super::post_revisions::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::PostRevisions.def()
+    }
+}
+
+impl Related<super::post_slug_history::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::PostSlugHistory.def()
+    }
+}
+
+impl Related<super::post_tags::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::PostTags.def()
+    }
+}
+
+impl Related<super::tags::Entity> for Entity {
+    fn to() -> RelationDef {
+        super::post_tags::Relation::Tags.def()
+    }
+    fn via() -> Option<RelationDef> {
+        Some(super::post_tags::Relation::Posts.def().rev())
+    }
+}
+
 impl Related<super::users::Entity> for Entity {
     fn to() -> RelationDef {
         Relation::Users.def()
     }
 }
 
-impl ActiveModelBehavior for ActiveModel {}
+impl Entity {
+    // Soft deleted rows stay in the table until purged, regular queries skip them
+    pub fn find_active() -> Select<Entity> {
+        Self::find().filter(Column::DeletedAt.is_null())
+    }
+
+    pub fn find_active_by_id(id: i32) -> Select<Entity> {
+        Self::find_active().filter(Column::Id.eq(id))
+    }
+}
+
+#[async_trait::async_trait]
+impl ActiveModelBehavior for ActiveModel {
+    async fn before_save<C>(mut self, _db: &C, insert: bool) -> Result<Self, DbErr>
+    where
+        C: ConnectionTrait,
+    {
+        let now: DateTimeWithTimeZone = chrono::Utc::now().into();
+
+        if insert {
+            self.created_at = Set(now);
+        }
+        self.updated_at = Set(now);
+
+        Ok(self)
+    }
+}
@@ -1,6 +1,22 @@
 //! `SeaORM` Entity, @generated by sea-orm-codegen 1.1.0
 
+pub use super::bookmarks::Entity as Bookmarks;
 pub use super::categories::Entity as Categories;
+pub use super::category_follows::Entity as CategoryFollows;
+pub use super::category_slug_history::Entity as CategorySlugHistory;
+pub use super::comment_revisions::Entity as CommentRevisions;
+pub use super::comment_votes::Entity as CommentVotes;
 pub use super::comments::Entity as Comments;
+pub use super::image_variants::Entity as ImageVariants;
+pub use super::media::Entity as Media;
+pub use super::notification_preferences::Entity as NotificationPreferences;
+pub use super::notifications::Entity as Notifications;
+pub use super::post_media::Entity as PostMedia;
+pub use super::post_reactions::Entity as PostReactions;
+pub use super::post_revisions::Entity as PostRevisions;
+pub use super::post_slug_history::Entity as PostSlugHistory;
+pub use super::post_tags::Entity as PostTags;
 pub use super::posts::Entity as Posts;
+pub use super::tags::Entity as Tags;
+pub use super::user_follows::Entity as UserFollows;
 pub use super::users::Entity as Users;
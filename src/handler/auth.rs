@@ -152,8 +152,8 @@ pub fn auth_routes() -> Router {
             .get(get_user_handler)
     );
 
-    return Router::new()
+    Router::new()
         .push(private_routes)
         .push(public_routes)
-        .hoop(size_limiter::max_size(1024 * 16));
+        .hoop(size_limiter::max_size(1024 * 16))
 }
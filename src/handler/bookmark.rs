@@ -0,0 +1,181 @@
+use salvo::prelude::*;
+use serde_json::json;
+use crate::{
+    domain::{ApiResponse, BookmarkFolderResponse, BookmarkListQuery, BookmarkPageResponse, BookmarkResponse, SaveBookmarkRequest},
+    middleware::{current_user_id, jwt_auth},
+    state::AppState,
+};
+
+fn unauthorized(res: &mut Response) {
+    res.status_code(StatusCode::UNAUTHORIZED).render(Json(json!({
+        "status": "fail",
+        "message": "Unauthorized"
+    })));
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/bookmarks",
+    params(BookmarkListQuery),
+    responses(
+        (status = 200, description = "Saved posts of the authenticated user, newest first", body = ApiResponse<BookmarkPageResponse>),
+        (status = 400, description = "Invalid query"),
+        (status = 401, description = "Unauthorized")
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "Bookmarks"
+)]
+#[handler]
+pub async fn get_bookmarks(req: &mut Request, depot: &mut Depot, res: &mut Response) {
+    let Some(user_id) = current_user_id(depot) else {
+        return unauthorized(res);
+    };
+
+    let query = match req.parse_queries::<BookmarkListQuery>() {
+        Ok(query) => query,
+        Err(e) => {
+            res.status_code(StatusCode::BAD_REQUEST).render(Json(json!({
+                "status": "fail",
+                "message": format!("Invalid query: {}", e)
+            })));
+            return;
+        }
+    };
+
+    let state = depot.obtain::<AppState>().unwrap();
+    match state.di_container.bookmark_service.get_bookmarks(user_id, &query).await {
+        Ok(bookmarks) => res.render(Json(bookmarks)),
+        Err(e) => {
+            res.status_code(StatusCode::INTERNAL_SERVER_ERROR).render(Json(e));
+        }
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/bookmarks/folders",
+    responses(
+        (status = 200, description = "Folders used by the authenticated user with their bookmark counts", body = ApiResponse<Vec<BookmarkFolderResponse>>),
+        (status = 401, description = "Unauthorized")
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "Bookmarks"
+)]
+#[handler]
+pub async fn get_bookmark_folders(depot: &mut Depot, res: &mut Response) {
+    let Some(user_id) = current_user_id(depot) else {
+        return unauthorized(res);
+    };
+
+    let state = depot.obtain::<AppState>().unwrap();
+    match state.di_container.bookmark_service.get_folders(user_id).await {
+        Ok(folders) => res.render(Json(folders)),
+        Err(e) => {
+            res.status_code(StatusCode::INTERNAL_SERVER_ERROR).render(Json(e));
+        }
+    }
+}
+
+#[utoipa::path(
+    put,
+    path = "/api/posts/{id}/bookmark",
+    params(
+        ("id" = i32, Path, description = "Post ID")
+    ),
+    request_body = SaveBookmarkRequest,
+    responses(
+        (status = 200, description = "Post saved, saving it again replaces its folder and note", body = ApiResponse<BookmarkResponse>),
+        (status = 400, description = "Invalid request body or folder"),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Post not found")
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "Bookmarks"
+)]
+#[handler]
+pub async fn save_bookmark(req: &mut Request, depot: &mut Depot, res: &mut Response) {
+    let Some(user_id) = current_user_id(depot) else {
+        return unauthorized(res);
+    };
+
+    let body = match req.parse_body::<SaveBookmarkRequest>().await {
+        Ok(body) => body,
+        Err(_) => {
+            res.status_code(StatusCode::BAD_REQUEST).render(Json(json!({"status": "fail", "message": "Invalid request body"})));
+            return;
+        }
+    };
+
+    let state = depot.obtain::<AppState>().unwrap();
+    let post_id: i32 = req.param("id").unwrap_or_default();
+
+    match state.di_container.bookmark_service.save_bookmark(user_id, post_id, &body).await {
+        Ok(Some(bookmark)) => res.render(Json(bookmark)),
+        Ok(None) => {
+            res.status_code(StatusCode::NOT_FOUND).render(Json(json!({
+                "status": "fail",
+                "message": "Post not found"
+            })));
+        }
+        Err(e) if e.status == "fail" => {
+            res.status_code(StatusCode::BAD_REQUEST).render(Json(e));
+        }
+        Err(e) => {
+            res.status_code(StatusCode::INTERNAL_SERVER_ERROR).render(Json(e));
+        }
+    }
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/posts/{id}/bookmark",
+    params(
+        ("id" = i32, Path, description = "Post ID")
+    ),
+    responses(
+        (status = 200, description = "Bookmark removed", body = Value),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "The post was not bookmarked")
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "Bookmarks"
+)]
+#[handler]
+pub async fn remove_bookmark(req: &mut Request, depot: &mut Depot, res: &mut Response) {
+    let Some(user_id) = current_user_id(depot) else {
+        return unauthorized(res);
+    };
+
+    let state = depot.obtain::<AppState>().unwrap();
+    let post_id: i32 = req.param("id").unwrap_or_default();
+
+    match state.di_container.bookmark_service.remove_bookmark(user_id, post_id).await {
+        Ok(Some(response)) => res.render(Json(response)),
+        Ok(None) => {
+            res.status_code(StatusCode::NOT_FOUND).render(Json(json!({
+                "status": "fail",
+                "message": "Bookmark not found"
+            })));
+        }
+        Err(e) => {
+            res.status_code(StatusCode::INTERNAL_SERVER_ERROR).render(Json(e));
+        }
+    }
+}
+
+pub fn bookmark_routes() -> Router {
+    Router::new()
+        .push(Router::with_path("api/bookmarks").get(get_bookmarks))
+        .push(Router::with_path("api/bookmarks/folders").get(get_bookmark_folders))
+        .push(Router::with_path("api/posts/{id:num}/bookmark").put(save_bookmark))
+        .push(Router::with_path("api/posts/{id:num}/bookmark").delete(remove_bookmark))
+        .hoop(jwt_auth())
+}
@@ -1,7 +1,7 @@
 use salvo::prelude::*;
 use serde_json::json;
 use crate::{
-    domain::{ApiResponse, CategoryResponse, CreateCategoryRequest, UpdateCategoryRequest}, middleware::jwt_auth, state::AppState
+    domain::{ApiResponse, CategoryDeleteStrategy, CategoryDeletionResponse, CategoryMergeResponse, CategoryResponse, CategoryTreeResponse, CreateCategoryRequest, DeleteCategoryQuery, MergeCategoriesRequest, SlugLookup, SlugRedirectResponse, UpdateCategoryRequest}, handler::render_slug_redirect, middleware::{jwt_auth, require_admin}, state::AppState
 };
 
 
@@ -59,6 +59,87 @@ pub async fn get_category(req: &mut Request, depot: &mut Depot, res: &mut Respon
     }
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/categories/slug/{slug}",
+    tag = "Categories",
+    params(
+        ("slug" = String, Path, description = "Category slug, current or previous")
+    ),
+    responses(
+        (status = 200, description = "Successfully retrieved category, or the canonical slug when requested as JSON", body = ApiResponse<CategoryResponse>),
+        (status = 301, description = "Slug has changed, redirect to the canonical slug", body = ApiResponse<SlugRedirectResponse>),
+        (status = 404, description = "Category not found"),
+        (status = 500, description = "Internal server error", body = String),
+    )
+)]
+#[handler]
+pub async fn get_category_by_slug(req: &mut Request, depot: &mut Depot, res: &mut Response) {
+    let state = depot.obtain::<AppState>().unwrap();
+    let slug: String = req.param("slug").unwrap_or_default();
+
+    match state.di_container.category_service.get_category_by_slug(&slug).await {
+        Ok(Some(SlugLookup::Found(category))) => res.render(Json(category)),
+        Ok(Some(SlugLookup::Moved(redirect))) => render_slug_redirect(req, res, redirect),
+        Ok(None) => {
+            res.status_code(StatusCode::NOT_FOUND).render(Json(json!({"status": "fail", "message": "Category not found"})));
+        }
+        Err(e) => {
+            res.status_code(StatusCode::INTERNAL_SERVER_ERROR).render(Json(json!(e)));
+        }
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/categories/tree",
+    tag = "Categories",
+    responses(
+        (status = 200, description = "All categories nested under their parents, with post counts", body = ApiResponse<Vec<CategoryTreeResponse>>),
+        (status = 500, description = "Internal server error", body = String),
+    )
+)]
+#[handler]
+pub async fn get_category_tree(depot: &mut Depot, res: &mut Response) {
+    let state = depot.obtain::<AppState>().unwrap();
+
+    match state.di_container.category_service.get_category_tree().await {
+        Ok(tree) => res.render(Json(tree)),
+        Err(e) => {
+            res.status_code(StatusCode::INTERNAL_SERVER_ERROR).render(Json(json!(e)));
+        }
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/categories/{id}/tree",
+    tag = "Categories",
+    params(
+        ("id" = i32, Path, description = "ID of the category at the root of the subtree")
+    ),
+    responses(
+        (status = 200, description = "A category and its descendants, with post counts", body = ApiResponse<CategoryTreeResponse>),
+        (status = 404, description = "Category not found"),
+        (status = 500, description = "Internal server error", body = String),
+    )
+)]
+#[handler]
+pub async fn get_category_subtree(req: &mut Request, depot: &mut Depot, res: &mut Response) {
+    let state = depot.obtain::<AppState>().unwrap();
+    let id: i32 = req.param("id").unwrap_or_default();
+
+    match state.di_container.category_service.get_category_subtree(id).await {
+        Ok(Some(subtree)) => res.render(Json(subtree)),
+        Ok(None) => {
+            res.status_code(StatusCode::NOT_FOUND).render(Json(json!({"status": "fail", "message": "Category not found"})));
+        }
+        Err(e) => {
+            res.status_code(StatusCode::INTERNAL_SERVER_ERROR).render(Json(json!(e)));
+        }
+    }
+}
+
 #[utoipa::path(
     post,
     path = "/api/categories",
@@ -69,6 +150,9 @@ pub async fn get_category(req: &mut Request, depot: &mut Depot, res: &mut Respon
     request_body = CreateCategoryRequest,
     responses(
         (status = 200, description = "Category created successfully", body = ApiResponse<CategoryResponse>),
+        (status = 400, description = "Invalid request body or parent category"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Admin role required"),
         (status = 500, description = "Internal server error", body = String),
     )
 )]
@@ -87,6 +171,9 @@ pub async fn create_category(req: &mut Request, depot: &mut Depot, res: &mut Res
         Ok(category) => {
             res.status_code(StatusCode::CREATED).render(Json(category));
         }
+        Err(e) if e.status == "fail" => {
+            res.status_code(StatusCode::BAD_REQUEST).render(Json(e));
+        }
         Err(e) => {
             res.status_code(StatusCode::INTERNAL_SERVER_ERROR).render(Json(json!(e)));
         }
@@ -106,6 +193,10 @@ pub async fn create_category(req: &mut Request, depot: &mut Depot, res: &mut Res
     request_body = UpdateCategoryRequest,
     responses(
         (status = 200, description = "Category updated successfully", body = ApiResponse<CategoryResponse>),
+        (status = 400, description = "Invalid request body, or the parent would create a cycle"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Admin role required"),
+        (status = 404, description = "Category not found"),
         (status = 500, description = "Internal server error", body = String),
     )
 )]
@@ -129,6 +220,9 @@ pub async fn update_category(req: &mut Request, depot: &mut Depot, res: &mut Res
         Ok(None) => {
             res.status_code(StatusCode::NOT_FOUND).render(Json(json!({"status": "fail", "message": "Category not found"})));
         }
+        Err(e) if e.status == "fail" => {
+            res.status_code(StatusCode::BAD_REQUEST).render(Json(e));
+        }
         Err(e) => {
             res.status_code(StatusCode::INTERNAL_SERVER_ERROR).render(Json(json!(e)));
         }
@@ -143,21 +237,44 @@ pub async fn update_category(req: &mut Request, depot: &mut Depot, res: &mut Res
         ("bearer_auth" = [])
     ),
     params(
-        ("id" = i32, Path, description = "Category ID")
+        ("id" = i32, Path, description = "Category ID"),
+        DeleteCategoryQuery
     ),
     responses(
-        (status = 200, description = "Category deleted successfully", body = Value),
+        (status = 200, description = "Category moved to the trash, with the number of posts reassigned or trashed", body = ApiResponse<CategoryDeletionResponse>),
+        (status = 400, description = "Invalid strategy or missing target_id"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Admin role required"),
+        (status = 404, description = "Category not found"),
+        (status = 409, description = "Category still has posts or the target category is invalid"),
         (status = 500, description = "Internal server error", body = String),
     )
 )]
 #[handler]
 pub async fn delete_category(req: &mut Request, depot: &mut Depot, res: &mut Response) {
+    let query = match req.parse_queries::<DeleteCategoryQuery>() {
+        Ok(query) => query,
+        Err(e) => {
+            res.status_code(StatusCode::BAD_REQUEST).render(Json(json!({"status": "fail", "message": format!("Invalid query: {}", e)})));
+            return;
+        }
+    };
+
+    if query.strategy == Some(CategoryDeleteStrategy::Reassign) && query.target_id.is_none() {
+        res.status_code(StatusCode::BAD_REQUEST).render(Json(json!({"status": "fail", "message": "target_id is required to reassign posts"})));
+        return;
+    }
+
     let state = depot.obtain::<AppState>().unwrap();
     let id: i32 = req.param("id").unwrap_or_default();
 
-    match state.di_container.category_service.delete_category(id).await {
-        Ok(_) => {
-            res.status_code(StatusCode::OK).render(Json(json!({"status": "success", "message": "Category deleted successfully"})));
+    match state.di_container.category_service.delete_category(id, &query).await {
+        Ok(Some(response)) => res.render(Json(response)),
+        Ok(None) => {
+            res.status_code(StatusCode::NOT_FOUND).render(Json(json!({"status": "fail", "message": "Category not found"})));
+        }
+        Err(e) if e.status == "fail" => {
+            res.status_code(StatusCode::CONFLICT).render(Json(e));
         }
         Err(e) => {
             res.status_code(StatusCode::INTERNAL_SERVER_ERROR).render(Json(json!({"status": "error", "message": e.to_string()})));
@@ -165,19 +282,70 @@ pub async fn delete_category(req: &mut Request, depot: &mut Depot, res: &mut Res
     }
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/categories/merge",
+    tag = "Categories",
+    security(
+        ("bearer_auth" = [])
+    ),
+    request_body = MergeCategoriesRequest,
+    responses(
+        (status = 200, description = "Sources merged into the target, or the changes a dry run would make", body = ApiResponse<CategoryMergeResponse>),
+        (status = 400, description = "Invalid request body, no sources, or the target is also a source"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Admin role required"),
+        (status = 404, description = "Target or source category not found"),
+        (status = 500, description = "Internal server error", body = String),
+    )
+)]
+#[handler]
+pub async fn merge_categories(req: &mut Request, depot: &mut Depot, res: &mut Response) {
+    let state = depot.obtain::<AppState>().unwrap();
+    let body = match req.parse_body::<MergeCategoriesRequest>().await {
+        Ok(body) => body,
+        Err(_) => {
+            res.status_code(StatusCode::BAD_REQUEST).render(Json(json!({"status": "fail", "message": "Invalid request body"})));
+            return;
+        }
+    };
+
+    match state.di_container.category_service.merge_categories(&body).await {
+        Ok(Some(report)) => res.render(Json(report)),
+        Ok(None) => {
+            res.status_code(StatusCode::NOT_FOUND).render(Json(json!({"status": "fail", "message": "Category not found"})));
+        }
+        Err(e) if e.status == "fail" => {
+            res.status_code(StatusCode::BAD_REQUEST).render(Json(e));
+        }
+        Err(e) => {
+            res.status_code(StatusCode::INTERNAL_SERVER_ERROR).render(Json(json!(e)));
+        }
+    }
+}
+
 pub fn category_routes() -> Router {
     let protected_routes = Router::new()
-        .push(Router::with_path("api/categories/{id}").get(get_category))
-        .push(Router::with_path("api/categories").post(create_category))
-        .push(Router::with_path("api/categories/{id}").put(update_category))
-        .push(Router::with_path("api/categories/{id}").delete(delete_category))
+        .push(Router::with_path("api/categories/{id:num}").get(get_category))
         .hoop(jwt_auth());
         
 
+    let admin_routes = Router::new()
+        .push(Router::with_path("api/categories").post(create_category))
+        .push(Router::with_path("api/categories/{id:num}").put(update_category))
+        .push(Router::with_path("api/categories/{id:num}").delete(delete_category))
+        .push(Router::with_path("api/categories/merge").post(merge_categories))
+        .hoop(jwt_auth())
+        .hoop(require_admin);
+
     let public_routes = Router::new()
-        .push(Router::with_path("api/categories").get(get_categories));
+        .push(Router::with_path("api/categories").get(get_categories))
+        .push(Router::with_path("api/categories/tree").get(get_category_tree))
+        .push(Router::with_path("api/categories/{id:num}/tree").get(get_category_subtree))
+        .push(Router::with_path("api/categories/slug/{slug}").get(get_category_by_slug));
 
-    return Router::new()
+    Router::new()
+        .push(admin_routes)
         .push(protected_routes)
-        .push(public_routes);
+        .push(public_routes)
 }
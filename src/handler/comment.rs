@@ -2,14 +2,14 @@ use salvo::prelude::*;
 use serde_json::json;
 use crate::{
     
-    domain::{ApiResponse, CommentResponse, CreateCommentRequest, UpdateCommentRequest}, middleware::jwt_auth, state::AppState
+    domain::{ApiResponse, CommentEdit, CommentFormat, CommentModerationResponse, CommentPageResponse, CommentResponse, CommentRevisionResponse, CommentThreadResponse, CreateCommentRequest, FlatCommentResponse, ModerateCommentsRequest, ModerationQueueQuery, PostCommentsQuery, UpdateCommentRequest}, entities::sea_orm_active_enums::CommentStatus, middleware::{current_user_id, jwt_auth, require_moderator}, state::AppState
 };
 
 #[utoipa::path(
     get,
     path = "/api/comments",
     responses(
-        (status = 200, description = "Get all comments", body = ApiResponse<Vec<CommentResponse>>)
+        (status = 200, description = "Approved comments plus the viewer's own pending ones", body = ApiResponse<Vec<CommentResponse>>)
     ),
     security(
         ("bearer_auth" = [])
@@ -19,7 +19,7 @@ use crate::{
 #[handler]
 pub async fn get_comments(depot: &mut Depot, res: &mut Response) {
     let state = depot.obtain::<AppState>().unwrap();
-    match state.di_container.comment_service.get_comments().await {
+    match state.di_container.comment_service.get_comments(current_user_id(depot)).await {
         Ok(comments) => res.render(Json(comments)),
         Err(e) => {
             res.status_code(StatusCode::INTERNAL_SERVER_ERROR).render(Json(json!({
@@ -48,7 +48,7 @@ pub async fn get_comment(req: &mut Request, depot: &mut Depot, res: &mut Respons
     let state = depot.obtain::<AppState>().unwrap();
     let comment_id: i32 = req.param("id").unwrap_or_default();
 
-    match state.di_container.comment_service.get_comment(comment_id).await {
+    match state.di_container.comment_service.get_comment(comment_id, current_user_id(depot)).await {
         Ok(Some(comment)) => res.render(Json(comment)),
         Ok(None) => {
             res.status_code(StatusCode::NOT_FOUND).render(Json(json!({
@@ -62,13 +62,65 @@ pub async fn get_comment(req: &mut Request, depot: &mut Depot, res: &mut Respons
     }
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/posts/{id}/comments",
+    params(
+        ("id" = i32, Path, description = "Post ID"),
+        PostCommentsQuery
+    ),
+    responses(
+        (status = 200, description = "Approved comments plus the viewer's own pending ones, as nested threads", body = ApiResponse<CommentPageResponse<CommentThreadResponse>>),
+        (status = 200, description = "Comments in thread order when format=flat", body = ApiResponse<CommentPageResponse<FlatCommentResponse>>),
+        (status = 400, description = "Invalid format, sort or page"),
+        (status = 404, description = "Post not found")
+    ),
+    tag = "Comments"
+)]
+#[handler]
+pub async fn get_post_comments(req: &mut Request, depot: &mut Depot, res: &mut Response) {
+    let query = match req.parse_queries::<PostCommentsQuery>() {
+        Ok(query) => query,
+        Err(e) => {
+            res.status_code(StatusCode::BAD_REQUEST).render(Json(json!({
+                "status": "fail",
+                "message": format!("Invalid query: {}", e)
+            })));
+            return;
+        }
+    };
+
+    let state = depot.obtain::<AppState>().unwrap();
+    let post_id: i32 = req.param("id").unwrap_or_default();
+    let viewer_id = current_user_id(depot);
+    let comment_service = &state.di_container.comment_service;
+
+    let result = match query.format {
+        CommentFormat::Tree => comment_service.get_post_comment_tree(post_id, viewer_id, &query).await.map(|threads| threads.map(|threads| json!(threads))),
+        CommentFormat::Flat => comment_service.get_post_comment_list(post_id, viewer_id, &query).await.map(|comments| comments.map(|comments| json!(comments))),
+    };
+
+    match result {
+        Ok(Some(comments)) => res.render(Json(comments)),
+        Ok(None) => {
+            res.status_code(StatusCode::NOT_FOUND).render(Json(json!({
+                "status": "fail",
+                "message": "Post not found"
+            })));
+        }
+        Err(e) => {
+            res.status_code(StatusCode::INTERNAL_SERVER_ERROR).render(Json(e));
+        }
+    }
+}
+
 #[utoipa::path(
     post,
     path = "/api/comments",
     request_body = CreateCommentRequest,
     responses(
-        (status = 201, description = "Comment created", body = ApiResponse<CommentResponse>),
-        (status = 400, description = "Invalid request body")
+        (status = 201, description = "Comment created, pending when its category requires approval", body = ApiResponse<CommentResponse>),
+        (status = 400, description = "Invalid request body, parent or reply depth")
     ),
     tag = "Comments"
 )]
@@ -83,10 +135,13 @@ pub async fn create_comment(req: &mut Request, depot: &mut Depot, res: &mut Resp
         }
     };
 
-    match state.di_container.comment_service.create_comment(&body).await {
+    match state.di_container.comment_service.create_comment(&body, current_user_id(depot)).await {
         Ok(comment) => {
             res.status_code(StatusCode::CREATED).render(Json(comment));
         }
+        Err(e) if e.status == "fail" => {
+            res.status_code(StatusCode::BAD_REQUEST).render(Json(e));
+        }
         Err(e) => {
             res.status_code(StatusCode::INTERNAL_SERVER_ERROR).render(Json(e));
         }
@@ -99,17 +154,32 @@ pub async fn create_comment(req: &mut Request, depot: &mut Depot, res: &mut Resp
     path = "/api/comments/{id}",
     request_body = UpdateCommentRequest,
     responses(
-        (status = 200, description = "Comment updated", body = ApiResponse<CommentResponse>),
+        (status = 200, description = "Comment updated, the previous text is kept in its history. An author's edit goes back to moderation when the category requires approval", body = ApiResponse<CommentResponse>),
+        (status = 400, description = "Invalid request body"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Not the author, or the edit window has passed"),
         (status = 404, description = "Comment not found")
     ),
     params(
         ("id" = i32, Path, description = "Comment ID")
     ),
+    security(
+        ("bearer_auth" = [])
+    ),
     tag = "Comments"
 )]
 #[handler]
 pub async fn update_comment(req: &mut Request, depot: &mut Depot, res: &mut Response) {
+    let Some(editor_id) = current_user_id(depot) else {
+        res.status_code(StatusCode::UNAUTHORIZED).render(Json(json!({
+            "status": "fail",
+            "message": "Unauthorized"
+        })));
+        return;
+    };
+
     let state = depot.obtain::<AppState>().unwrap();
+    let comment_id: i32 = req.param("id").unwrap_or_default();
     let body = match req.parse_body::<UpdateCommentRequest>().await {
         Ok(body) => body,
         Err(_) => {
@@ -118,8 +188,53 @@ pub async fn update_comment(req: &mut Request, depot: &mut Depot, res: &mut Resp
         }
     };
 
-    match state.di_container.comment_service.update_comment(&body).await {
-        Ok(Some(comment)) => res.render(Json(comment)),
+    match state.di_container.comment_service.update_comment(comment_id, &body, editor_id).await {
+        Ok(Some(CommentEdit::Edited(comment))) => res.render(Json(comment)),
+        Ok(Some(CommentEdit::Forbidden(message))) => {
+            res.status_code(StatusCode::FORBIDDEN).render(Json(json!({
+                "status": "fail",
+                "message": message
+            })));
+        }
+        Ok(None) => {
+            res.status_code(StatusCode::NOT_FOUND).render(Json(json!({
+                "status": "fail",
+                "message": "Comment not found"
+            })));
+        }
+        Err(e) if e.status == "fail" => {
+            res.status_code(StatusCode::BAD_REQUEST).render(Json(e));
+        }
+        Err(e) => {
+            res.status_code(StatusCode::INTERNAL_SERVER_ERROR).render(Json(e));
+        }
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/comments/{id}/revisions",
+    responses(
+        (status = 200, description = "Previous versions of the comment, newest first", body = ApiResponse<Vec<CommentRevisionResponse>>),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Moderator role required"),
+        (status = 404, description = "Comment not found")
+    ),
+    params(
+        ("id" = i32, Path, description = "Comment ID")
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "Comments"
+)]
+#[handler]
+pub async fn get_comment_revisions(req: &mut Request, depot: &mut Depot, res: &mut Response) {
+    let state = depot.obtain::<AppState>().unwrap();
+    let comment_id: i32 = req.param("id").unwrap_or_default();
+
+    match state.di_container.comment_service.get_comment_revisions(comment_id).await {
+        Ok(Some(revisions)) => res.render(Json(revisions)),
         Ok(None) => {
             res.status_code(StatusCode::NOT_FOUND).render(Json(json!({
                 "status": "fail",
@@ -163,15 +278,106 @@ pub async fn delete_comment(req: &mut Request, depot: &mut Depot, res: &mut Resp
     }
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/comments/moderation",
+    params(ModerationQueueQuery),
+    responses(
+        (status = 200, description = "Comments with the requested status, oldest first", body = ApiResponse<Vec<CommentResponse>>),
+        (status = 400, description = "Invalid status"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Moderator role required")
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "Comments"
+)]
+#[handler]
+pub async fn get_moderation_queue(req: &mut Request, depot: &mut Depot, res: &mut Response) {
+    let query = match req.parse_queries::<ModerationQueueQuery>() {
+        Ok(query) => query,
+        Err(e) => {
+            res.status_code(StatusCode::BAD_REQUEST).render(Json(json!({
+                "status": "fail",
+                "message": format!("Invalid query: {}", e)
+            })));
+            return;
+        }
+    };
+
+    let state = depot.obtain::<AppState>().unwrap();
+    let status = query.status.unwrap_or(CommentStatus::Pending);
+
+    match state.di_container.comment_service.get_moderation_queue(status).await {
+        Ok(comments) => res.render(Json(comments)),
+        Err(e) => {
+            res.status_code(StatusCode::INTERNAL_SERVER_ERROR).render(Json(e));
+        }
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/comments/moderation",
+    request_body = ModerateCommentsRequest,
+    responses(
+        (status = 200, description = "Comments approved, rejected or marked as spam", body = ApiResponse<CommentModerationResponse>),
+        (status = 400, description = "Invalid request body"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Moderator role required")
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "Comments"
+)]
+#[handler]
+pub async fn moderate_comments(req: &mut Request, depot: &mut Depot, res: &mut Response) {
+    let body = match req.parse_body::<ModerateCommentsRequest>().await {
+        Ok(body) => body,
+        Err(_) => {
+            res.status_code(StatusCode::BAD_REQUEST).render(Json(json!({"status": "fail", "message": "Invalid request body"})));
+            return;
+        }
+    };
+
+    let state = depot.obtain::<AppState>().unwrap();
+
+    match state.di_container.comment_service.moderate_comments(&body).await {
+        Ok(result) => res.render(Json(result)),
+        Err(e) if e.status == "fail" => {
+            res.status_code(StatusCode::BAD_REQUEST).render(Json(e));
+        }
+        Err(e) => {
+            res.status_code(StatusCode::INTERNAL_SERVER_ERROR).render(Json(e));
+        }
+    }
+}
+
 pub fn comment_routes() -> Router {
     let protected_routes = Router::new()
         .push(Router::with_path("api/comments").get(get_comments))
-        .push(Router::with_path("api/comments/{id}").get(get_comment))
+        .push(Router::with_path("api/comments/{id:num}").get(get_comment))
         .push(Router::with_path("api/comments").post(create_comment))
-        .push(Router::with_path("api/comments/{id}").put(update_comment))
-        .push(Router::with_path("api/comments/{id}").delete(delete_comment))
+        .push(Router::with_path("api/comments/{id:num}").put(update_comment))
+        .push(Router::with_path("api/comments/{id:num}").delete(delete_comment))
+        .hoop(jwt_auth());
+
+    let moderator_routes = Router::new()
+        .push(Router::with_path("api/comments/moderation").get(get_moderation_queue))
+        .push(Router::with_path("api/comments/moderation").post(moderate_comments))
+        .push(Router::with_path("api/comments/{id:num}/revisions").get(get_comment_revisions))
+        .hoop(jwt_auth())
+        .hoop(require_moderator);
+
+    // Anonymous readers are let through, signed in ones also see their own pending comments
+    let public_routes = Router::new()
+        .push(Router::with_path("api/posts/{id:num}/comments").get(get_post_comments))
         .hoop(jwt_auth());
 
-    return Router::new()
-        .push(protected_routes);
+    Router::new()
+        .push(moderator_routes)
+        .push(public_routes)
+        .push(protected_routes)
 }
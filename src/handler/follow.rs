@@ -0,0 +1,264 @@
+use salvo::prelude::*;
+use serde_json::json;
+use crate::{
+    domain::{ApiResponse, FeedQuery, FeedResponse, FollowingResponse},
+    middleware::{current_user_id, jwt_auth},
+    state::AppState,
+};
+
+fn unauthorized(res: &mut Response) {
+    res.status_code(StatusCode::UNAUTHORIZED).render(Json(json!({
+        "status": "fail",
+        "message": "Unauthorized"
+    })));
+}
+
+#[utoipa::path(
+    put,
+    path = "/api/users/{id}/follow",
+    params(
+        ("id" = i32, Path, description = "User ID")
+    ),
+    responses(
+        (status = 200, description = "User followed, following again changes nothing", body = Value),
+        (status = 400, description = "Users cannot follow themselves"),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "User not found")
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "Follows"
+)]
+#[handler]
+pub async fn follow_user(req: &mut Request, depot: &mut Depot, res: &mut Response) {
+    let Some(user_id) = current_user_id(depot) else {
+        return unauthorized(res);
+    };
+
+    let state = depot.obtain::<AppState>().unwrap();
+    let id: i32 = req.param("id").unwrap_or_default();
+
+    match state.di_container.follow_service.follow_user(user_id, id).await {
+        Ok(Some(response)) => res.render(Json(response)),
+        Ok(None) => {
+            res.status_code(StatusCode::NOT_FOUND).render(Json(json!({
+                "status": "fail",
+                "message": "User not found"
+            })));
+        }
+        Err(e) if e.status == "fail" => {
+            res.status_code(StatusCode::BAD_REQUEST).render(Json(e));
+        }
+        Err(e) => {
+            res.status_code(StatusCode::INTERNAL_SERVER_ERROR).render(Json(e));
+        }
+    }
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/users/{id}/follow",
+    params(
+        ("id" = i32, Path, description = "User ID")
+    ),
+    responses(
+        (status = 200, description = "User unfollowed", body = Value),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Not following this user")
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "Follows"
+)]
+#[handler]
+pub async fn unfollow_user(req: &mut Request, depot: &mut Depot, res: &mut Response) {
+    let Some(user_id) = current_user_id(depot) else {
+        return unauthorized(res);
+    };
+
+    let state = depot.obtain::<AppState>().unwrap();
+    let id: i32 = req.param("id").unwrap_or_default();
+
+    match state.di_container.follow_service.unfollow_user(user_id, id).await {
+        Ok(Some(response)) => res.render(Json(response)),
+        Ok(None) => {
+            res.status_code(StatusCode::NOT_FOUND).render(Json(json!({
+                "status": "fail",
+                "message": "Not following this user"
+            })));
+        }
+        Err(e) if e.status == "fail" => {
+            res.status_code(StatusCode::BAD_REQUEST).render(Json(e));
+        }
+        Err(e) => {
+            res.status_code(StatusCode::INTERNAL_SERVER_ERROR).render(Json(e));
+        }
+    }
+}
+
+#[utoipa::path(
+    put,
+    path = "/api/categories/{id}/follow",
+    params(
+        ("id" = i32, Path, description = "Category ID")
+    ),
+    responses(
+        (status = 200, description = "Category followed, following again changes nothing", body = Value),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Category not found")
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "Follows"
+)]
+#[handler]
+pub async fn follow_category(req: &mut Request, depot: &mut Depot, res: &mut Response) {
+    let Some(user_id) = current_user_id(depot) else {
+        return unauthorized(res);
+    };
+
+    let state = depot.obtain::<AppState>().unwrap();
+    let id: i32 = req.param("id").unwrap_or_default();
+
+    match state.di_container.follow_service.follow_category(user_id, id).await {
+        Ok(Some(response)) => res.render(Json(response)),
+        Ok(None) => {
+            res.status_code(StatusCode::NOT_FOUND).render(Json(json!({
+                "status": "fail",
+                "message": "Category not found"
+            })));
+        }
+        Err(e) if e.status == "fail" => {
+            res.status_code(StatusCode::BAD_REQUEST).render(Json(e));
+        }
+        Err(e) => {
+            res.status_code(StatusCode::INTERNAL_SERVER_ERROR).render(Json(e));
+        }
+    }
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/categories/{id}/follow",
+    params(
+        ("id" = i32, Path, description = "Category ID")
+    ),
+    responses(
+        (status = 200, description = "Category unfollowed", body = Value),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Not following this category")
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "Follows"
+)]
+#[handler]
+pub async fn unfollow_category(req: &mut Request, depot: &mut Depot, res: &mut Response) {
+    let Some(user_id) = current_user_id(depot) else {
+        return unauthorized(res);
+    };
+
+    let state = depot.obtain::<AppState>().unwrap();
+    let id: i32 = req.param("id").unwrap_or_default();
+
+    match state.di_container.follow_service.unfollow_category(user_id, id).await {
+        Ok(Some(response)) => res.render(Json(response)),
+        Ok(None) => {
+            res.status_code(StatusCode::NOT_FOUND).render(Json(json!({
+                "status": "fail",
+                "message": "Not following this category"
+            })));
+        }
+        Err(e) if e.status == "fail" => {
+            res.status_code(StatusCode::BAD_REQUEST).render(Json(e));
+        }
+        Err(e) => {
+            res.status_code(StatusCode::INTERNAL_SERVER_ERROR).render(Json(e));
+        }
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/follows",
+    responses(
+        (status = 200, description = "Users and categories the authenticated user follows", body = ApiResponse<FollowingResponse>),
+        (status = 401, description = "Unauthorized")
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "Follows"
+)]
+#[handler]
+pub async fn get_following(depot: &mut Depot, res: &mut Response) {
+    let Some(user_id) = current_user_id(depot) else {
+        return unauthorized(res);
+    };
+
+    let state = depot.obtain::<AppState>().unwrap();
+    match state.di_container.follow_service.get_following(user_id).await {
+        Ok(following) => res.render(Json(following)),
+        Err(e) => {
+            res.status_code(StatusCode::INTERNAL_SERVER_ERROR).render(Json(e));
+        }
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/feed",
+    params(FeedQuery),
+    responses(
+        (status = 200, description = "Published posts from followed authors and categories, newest first", body = ApiResponse<FeedResponse>),
+        (status = 400, description = "Invalid query or cursor"),
+        (status = 401, description = "Unauthorized")
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "Follows"
+)]
+#[handler]
+pub async fn get_feed(req: &mut Request, depot: &mut Depot, res: &mut Response) {
+    let Some(user_id) = current_user_id(depot) else {
+        return unauthorized(res);
+    };
+
+    let query = match req.parse_queries::<FeedQuery>() {
+        Ok(query) => query,
+        Err(e) => {
+            res.status_code(StatusCode::BAD_REQUEST).render(Json(json!({
+                "status": "fail",
+                "message": format!("Invalid query: {}", e)
+            })));
+            return;
+        }
+    };
+
+    let state = depot.obtain::<AppState>().unwrap();
+    match state.di_container.follow_service.get_feed(user_id, &query).await {
+        Ok(feed) => res.render(Json(feed)),
+        Err(e) if e.status == "fail" => {
+            res.status_code(StatusCode::BAD_REQUEST).render(Json(e));
+        }
+        Err(e) => {
+            res.status_code(StatusCode::INTERNAL_SERVER_ERROR).render(Json(e));
+        }
+    }
+}
+
+pub fn follow_routes() -> Router {
+    Router::new()
+        .push(Router::with_path("api/feed").get(get_feed))
+        .push(Router::with_path("api/follows").get(get_following))
+        .push(Router::with_path("api/users/{id:num}/follow").put(follow_user))
+        .push(Router::with_path("api/users/{id:num}/follow").delete(unfollow_user))
+        .push(Router::with_path("api/categories/{id:num}/follow").put(follow_category))
+        .push(Router::with_path("api/categories/{id:num}/follow").delete(unfollow_category))
+        .hoop(jwt_auth())
+}
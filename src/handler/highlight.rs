@@ -0,0 +1,50 @@
+use salvo::prelude::*;
+use serde_json::json;
+use crate::{domain::ApiResponse, utils::{highlight_css, highlight_themes}};
+
+#[utoipa::path(
+    get,
+    path = "/api/highlight/theme.css",
+    params(
+        ("theme" = Option<String>, Query, description = "Theme name, defaults to the configured theme")
+    ),
+    responses(
+        (status = 200, description = "Stylesheet for highlighted code blocks", content_type = "text/css", body = String),
+        (status = 404, description = "Theme not found")
+    ),
+    tag = "Highlight"
+)]
+#[handler]
+pub async fn get_theme_css(req: &mut Request, res: &mut Response) {
+    let theme = req.query::<String>("theme");
+
+    match highlight_css(theme.as_deref()) {
+        Some(css) => res.render(Text::Css(css)),
+        None => {
+            res.status_code(StatusCode::NOT_FOUND).render(Json(json!({"status": "fail", "message": "Theme not found"})));
+        }
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/highlight/themes",
+    responses(
+        (status = 200, description = "Available highlight themes", body = ApiResponse<Vec<String>>)
+    ),
+    tag = "Highlight"
+)]
+#[handler]
+pub async fn get_themes(res: &mut Response) {
+    res.render(Json(ApiResponse {
+        status: "success".to_string(),
+        message: "Themes retrieved successfully".to_string(),
+        data: highlight_themes(),
+    }));
+}
+
+pub fn highlight_routes() -> Router {
+    Router::new()
+        .push(Router::with_path("api/highlight/theme.css").get(get_theme_css))
+        .push(Router::with_path("api/highlight/themes").get(get_themes))
+}
@@ -0,0 +1,180 @@
+use salvo::prelude::*;
+use serde_json::json;
+use crate::{
+    domain::{ApiResponse, MediaResponse, UpdateMediaRequest}, middleware::{current_user_id, jwt_auth}, state::AppState
+};
+
+#[utoipa::path(
+    get,
+    path = "/api/media",
+    params(
+        ("q" = Option<String>, Query, description = "Search the original file name and alt text"),
+        ("mine" = Option<bool>, Query, description = "Only list media uploaded by the current user")
+    ),
+    responses(
+        (status = 200, description = "List or search the media library, newest first", body = ApiResponse<Vec<MediaResponse>>),
+        (status = 401, description = "Unauthorized")
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "Media"
+)]
+#[handler]
+pub async fn get_media(req: &mut Request, depot: &mut Depot, res: &mut Response) {
+    let Some(user_id) = current_user_id(depot) else {
+        res.status_code(StatusCode::UNAUTHORIZED).render(Json(json!({"status": "fail", "message": "Unauthorized"})));
+        return;
+    };
+
+    let state = depot.obtain::<AppState>().unwrap();
+    let query = req.query::<String>("q");
+    let owner = req.query::<bool>("mine").unwrap_or(false).then_some(user_id);
+
+    match state.di_container.media_service.get_media(owner, query.as_deref()).await {
+        Ok(media) => res.render(Json(media)),
+        Err(e) => {
+            res.status_code(StatusCode::INTERNAL_SERVER_ERROR).render(Json(e));
+        }
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/media/{id}",
+    params(
+        ("id" = i32, Path, description = "Media ID")
+    ),
+    responses(
+        (status = 200, description = "Get a media item", body = ApiResponse<MediaResponse>),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Media not found")
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "Media"
+)]
+#[handler]
+pub async fn get_media_item(req: &mut Request, depot: &mut Depot, res: &mut Response) {
+    if current_user_id(depot).is_none() {
+        res.status_code(StatusCode::UNAUTHORIZED).render(Json(json!({"status": "fail", "message": "Unauthorized"})));
+        return;
+    }
+
+    let state = depot.obtain::<AppState>().unwrap();
+    let media_id: i32 = req.param("id").unwrap_or_default();
+
+    match state.di_container.media_service.get_media_item(media_id).await {
+        Ok(Some(media)) => res.render(Json(media)),
+        Ok(None) => {
+            res.status_code(StatusCode::NOT_FOUND).render(Json(json!({"status": "fail", "message": "Media not found"})));
+        }
+        Err(e) => {
+            res.status_code(StatusCode::INTERNAL_SERVER_ERROR).render(Json(e));
+        }
+    }
+}
+
+#[utoipa::path(
+    put,
+    path = "/api/media/{id}",
+    params(
+        ("id" = i32, Path, description = "Media ID")
+    ),
+    request_body = UpdateMediaRequest,
+    responses(
+        (status = 200, description = "Media updated successfully", body = ApiResponse<MediaResponse>),
+        (status = 400, description = "Invalid request body"),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Media not found")
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "Media"
+)]
+#[handler]
+pub async fn update_media(req: &mut Request, depot: &mut Depot, res: &mut Response) {
+    let Some(user_id) = current_user_id(depot) else {
+        res.status_code(StatusCode::UNAUTHORIZED).render(Json(json!({"status": "fail", "message": "Unauthorized"})));
+        return;
+    };
+
+    let state = depot.obtain::<AppState>().unwrap();
+    let media_id: i32 = req.param("id").unwrap_or_default();
+    let mut body = match req.parse_body::<UpdateMediaRequest>().await {
+        Ok(body) => body,
+        Err(_) => {
+            res.status_code(StatusCode::BAD_REQUEST).render(Json(json!({"status": "fail", "message": "Invalid request body"})));
+            return;
+        }
+    };
+
+    body.id = Some(media_id);
+
+    match state.di_container.media_service.update_media(user_id, &body).await {
+        Ok(Some(media)) => res.render(Json(media)),
+        Ok(None) => {
+            res.status_code(StatusCode::NOT_FOUND).render(Json(json!({"status": "fail", "message": "Media not found"})));
+        }
+        Err(e) => {
+            res.status_code(StatusCode::INTERNAL_SERVER_ERROR).render(Json(e));
+        }
+    }
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/media/{id}",
+    params(
+        ("id" = i32, Path, description = "Media ID"),
+        ("force" = Option<bool>, Query, description = "Delete even when posts still use the media")
+    ),
+    responses(
+        (status = 200, description = "Media deleted successfully"),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Media not found"),
+        (status = 409, description = "Media is still referenced by posts")
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "Media"
+)]
+#[handler]
+pub async fn delete_media(req: &mut Request, depot: &mut Depot, res: &mut Response) {
+    let Some(user_id) = current_user_id(depot) else {
+        res.status_code(StatusCode::UNAUTHORIZED).render(Json(json!({"status": "fail", "message": "Unauthorized"})));
+        return;
+    };
+
+    let state = depot.obtain::<AppState>().unwrap();
+    let media_id: i32 = req.param("id").unwrap_or_default();
+    let force = req.query::<bool>("force").unwrap_or(false);
+
+    match state.di_container.media_service.delete_media(user_id, media_id, force).await {
+        Ok(Some(response)) => res.render(Json(response)),
+        Ok(None) => {
+            res.status_code(StatusCode::NOT_FOUND).render(Json(json!({"status": "fail", "message": "Media not found"})));
+        }
+        Err(e) if e.status == "fail" => {
+            res.status_code(StatusCode::CONFLICT).render(Json(e));
+        }
+        Err(e) => {
+            res.status_code(StatusCode::INTERNAL_SERVER_ERROR).render(Json(e));
+        }
+    }
+}
+
+pub fn media_routes() -> Router {
+    Router::new()
+        .push(Router::with_path("api/media").get(get_media))
+        .push(
+            Router::with_path("api/media/{id}")
+                .get(get_media_item)
+                .put(update_media)
+                .delete(delete_media),
+        )
+        .hoop(jwt_auth())
+}
@@ -1,11 +1,24 @@
 mod auth;
+mod bookmark;
 mod category;
 mod comment;
+mod follow;
+mod highlight;
+mod media;
+mod notification;
 mod posts;
+mod post_revision;
+mod reaction;
+mod tag;
+mod trash;
+mod upload;
 mod user;
 
 use std::sync::Arc;
 
+use crate::domain::SlugRedirectResponse;
+use crate::jobs::{spawn_body_html_backfill, spawn_media_gc, spawn_scheduled_publisher, spawn_trash_purge};
+use crate::middleware::current_user_id;
 use crate::state::AppState;
 use salvo::prelude::*;
 use salvo::http::header::{self, HeaderValue};
@@ -13,11 +26,22 @@ use salvo::http::response::ResBody;
 use utoipa::openapi::security::SecurityScheme;
 use utoipa::{Modify, OpenApi};
 use utoipa_swagger_ui::Config;
+use serde_json::json;
 
 pub use self::auth::auth_routes;
+pub use self::bookmark::bookmark_routes;
 pub use self::category::category_routes;
 pub use self::comment::comment_routes;
+pub use self::follow::follow_routes;
+pub use self::highlight::highlight_routes;
+pub use self::media::media_routes;
+pub use self::notification::notification_routes;
 pub use self::posts::post_routes;
+pub use self::post_revision::post_revision_routes;
+pub use self::reaction::reaction_routes;
+pub use self::tag::tag_routes;
+pub use self::trash::trash_routes;
+pub use self::upload::upload_routes;
 pub use self::user::user_routes;
 
 #[derive(OpenApi)]
@@ -32,28 +56,84 @@ pub use self::user::user_routes;
         user::delete_user,
         category::get_categories,
         category::get_category,
+        category::get_category_by_slug,
+        category::get_category_tree,
+        category::get_category_subtree,
         category::create_category,
         category::update_category,
         category::delete_category,
+        category::merge_categories,
         comment::get_comments,
         comment::get_comment,
+        comment::get_post_comments,
+        comment::get_moderation_queue,
+        comment::moderate_comments,
         comment::create_comment,
         comment::update_comment,
+        comment::get_comment_revisions,
         comment::delete_comment,
         posts::get_posts,
+        posts::get_my_posts,
         posts::get_post,
+        posts::get_post_by_slug,
         posts::get_post_relation,
         posts::create_post,
         posts::update_post,
         posts::delete_post,
+        reaction::react_to_post,
+        reaction::remove_post_reaction,
+        reaction::vote_comment,
+        reaction::remove_comment_vote,
+        bookmark::get_bookmarks,
+        bookmark::get_bookmark_folders,
+        bookmark::save_bookmark,
+        bookmark::remove_bookmark,
+        follow::follow_user,
+        follow::unfollow_user,
+        follow::follow_category,
+        follow::unfollow_category,
+        follow::get_following,
+        follow::get_feed,
+        notification::get_notifications,
+        notification::get_unread_count,
+        notification::mark_notification_read,
+        notification::mark_all_notifications_read,
+        notification::get_notification_preferences,
+        notification::update_notification_preferences,
+        post_revision::get_revisions,
+        post_revision::diff_revisions,
+        post_revision::restore_revision,
+        tag::get_tags,
+        tag::get_tag_posts,
+        tag::rename_tag,
+        tag::merge_tag,
+        highlight::get_theme_css,
+        highlight::get_themes,
+        upload::upload_image,
+        upload::get_upload,
+        media::get_media,
+        media::get_media_item,
+        media::update_media,
+        media::delete_media,
+        trash::get_trash,
+        trash::restore_item,
     ),
     modifiers(&SecurityAddon),
     tags(
         (name = "Auth", description = "Authentication endpoints."),
         (name = "Categories", description = "Categories management endpoints."),
         (name = "Posts", description = "Posts management endpoints."),
+        (name = "Tags", description = "Tags management endpoints."),
         (name = "Comments", description = "Comments management endpoints."),
-        (name = "Users", description = "Users management endpoints.")
+        (name = "Reactions", description = "Post reactions and comment votes."),
+        (name = "Bookmarks", description = "Posts saved by readers for later."),
+        (name = "Follows", description = "Followed authors and categories and the home feed."),
+        (name = "Notifications", description = "In-app notifications and their preferences."),
+        (name = "Users", description = "Users management endpoints."),
+        (name = "Highlight", description = "Code highlighting stylesheets."),
+        (name = "Uploads", description = "Image upload endpoints."),
+        (name = "Media", description = "Media library endpoints."),
+        (name = "Trash", description = "Soft deleted items, admin only.")
     )
 )]
 struct ApiDoc;
@@ -79,12 +159,27 @@ impl AppRouter {
     pub async fn serve(port: u16, app_state: AppState) -> Result<(), Box<dyn std::error::Error>> {
         let config = Arc::new(Config::from("/api-doc/openapi.json"));
 
+        spawn_body_html_backfill(app_state.di_container.post_service.clone());
+        spawn_scheduled_publisher(app_state.di_container.post_service.clone());
+        spawn_media_gc(app_state.di_container.media_service.clone());
+        spawn_trash_purge(app_state.di_container.trash_service.clone());
+
         let router = Router::new()
             .hoop(affix_state::inject(app_state.clone()))
             .push(auth_routes())
             .push(category_routes())
             .push(comment_routes())
             .push(post_routes())
+            .push(post_revision_routes())
+            .push(reaction_routes())
+            .push(bookmark_routes())
+            .push(follow_routes())
+            .push(notification_routes())
+            .push(tag_routes())
+            .push(highlight_routes())
+            .push(upload_routes(app_state.upload_config.max_size))
+            .push(media_routes())
+            .push(trash_routes())
             .push(user_routes())
             .push(Router::with_path("/api-doc/openapi.json").get(openapi_json))
             .push(
@@ -103,6 +198,61 @@ impl AppRouter {
     }
 }
 
+// Renders the rejection and returns None unless the current user is the post's author or an admin
+pub(crate) async fn authorize_post_editor(post_id: i32, depot: &mut Depot, res: &mut Response, message: &str) -> Option<i32> {
+    let Some(user_id) = current_user_id(depot) else {
+        res.status_code(StatusCode::UNAUTHORIZED).render(Json(json!({"status": "fail", "message": "Unauthorized"})));
+        return None;
+    };
+
+    let state = depot.obtain::<AppState>().unwrap();
+
+    match state.di_container.post_service.can_manage(post_id, user_id).await {
+        Ok(Some(true)) => Some(user_id),
+        Ok(Some(false)) => {
+            res.status_code(StatusCode::FORBIDDEN).render(Json(json!({"status": "fail", "message": message})));
+            None
+        }
+        Ok(None) => {
+            res.status_code(StatusCode::NOT_FOUND).render(Json(json!({"status": "fail", "message": "Post not found"})));
+            None
+        }
+        Err(e) => {
+            res.status_code(StatusCode::INTERNAL_SERVER_ERROR).render(Json(e));
+            None
+        }
+    }
+}
+
+// Browsers follow a permanent redirect, API clients asking for JSON get the canonical slug instead
+pub(crate) fn render_slug_redirect(req: &Request, res: &mut Response, redirect: SlugRedirectResponse) {
+    let wants_json = req
+        .accept()
+        .iter()
+        .any(|mime| mime.essence_str() == "application/json");
+
+    let location = HeaderValue::from_str(&redirect.location).ok();
+
+    match location {
+        Some(location) if !wants_json => {
+            res.status_code(StatusCode::MOVED_PERMANENTLY);
+            res.headers_mut().insert(header::LOCATION, location);
+            res.render(Json(json!({
+                "status": "redirect",
+                "message": "Resource has moved",
+                "data": redirect
+            })));
+        }
+        _ => {
+            res.status_code(StatusCode::OK).render(Json(json!({
+                "status": "redirect",
+                "message": "Resource has moved",
+                "data": redirect
+            })));
+        }
+    }
+}
+
 #[handler]
 async fn hello(res: &mut Response) {
     res.render("Hello");
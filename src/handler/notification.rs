@@ -0,0 +1,225 @@
+use salvo::prelude::*;
+use serde_json::json;
+use crate::{
+    domain::{
+        ApiResponse, MarkAllReadResponse, NotificationListQuery, NotificationPageResponse, NotificationPreferenceResponse,
+        NotificationResponse, UnreadCountResponse, UpdateNotificationPreferencesRequest,
+    },
+    middleware::{current_user_id, jwt_auth},
+    state::AppState,
+};
+
+fn unauthorized(res: &mut Response) {
+    res.status_code(StatusCode::UNAUTHORIZED).render(Json(json!({
+        "status": "fail",
+        "message": "Unauthorized"
+    })));
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/notifications",
+    params(NotificationListQuery),
+    responses(
+        (status = 200, description = "Notifications of the authenticated user, newest first", body = ApiResponse<NotificationPageResponse>),
+        (status = 400, description = "Invalid query"),
+        (status = 401, description = "Unauthorized")
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "Notifications"
+)]
+#[handler]
+pub async fn get_notifications(req: &mut Request, depot: &mut Depot, res: &mut Response) {
+    let Some(user_id) = current_user_id(depot) else {
+        return unauthorized(res);
+    };
+
+    let query = match req.parse_queries::<NotificationListQuery>() {
+        Ok(query) => query,
+        Err(e) => {
+            res.status_code(StatusCode::BAD_REQUEST).render(Json(json!({
+                "status": "fail",
+                "message": format!("Invalid query: {}", e)
+            })));
+            return;
+        }
+    };
+
+    let state = depot.obtain::<AppState>().unwrap();
+    match state.di_container.notification_service.get_notifications(user_id, &query).await {
+        Ok(notifications) => res.render(Json(notifications)),
+        Err(e) => {
+            res.status_code(StatusCode::INTERNAL_SERVER_ERROR).render(Json(e));
+        }
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/notifications/unread-count",
+    responses(
+        (status = 200, description = "Number of unread notifications", body = ApiResponse<UnreadCountResponse>),
+        (status = 401, description = "Unauthorized")
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "Notifications"
+)]
+#[handler]
+pub async fn get_unread_count(depot: &mut Depot, res: &mut Response) {
+    let Some(user_id) = current_user_id(depot) else {
+        return unauthorized(res);
+    };
+
+    let state = depot.obtain::<AppState>().unwrap();
+    match state.di_container.notification_service.get_unread_count(user_id).await {
+        Ok(count) => res.render(Json(count)),
+        Err(e) => {
+            res.status_code(StatusCode::INTERNAL_SERVER_ERROR).render(Json(e));
+        }
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/notifications/{id}/read",
+    params(
+        ("id" = i32, Path, description = "Notification ID")
+    ),
+    responses(
+        (status = 200, description = "Notification marked as read", body = ApiResponse<NotificationResponse>),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Notification not found")
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "Notifications"
+)]
+#[handler]
+pub async fn mark_notification_read(req: &mut Request, depot: &mut Depot, res: &mut Response) {
+    let Some(user_id) = current_user_id(depot) else {
+        return unauthorized(res);
+    };
+
+    let state = depot.obtain::<AppState>().unwrap();
+    let notification_id: i32 = req.param("id").unwrap_or_default();
+
+    match state.di_container.notification_service.mark_read(user_id, notification_id).await {
+        Ok(Some(notification)) => res.render(Json(notification)),
+        Ok(None) => {
+            res.status_code(StatusCode::NOT_FOUND).render(Json(json!({
+                "status": "fail",
+                "message": "Notification not found"
+            })));
+        }
+        Err(e) => {
+            res.status_code(StatusCode::INTERNAL_SERVER_ERROR).render(Json(e));
+        }
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/notifications/read-all",
+    responses(
+        (status = 200, description = "Every notification marked as read", body = ApiResponse<MarkAllReadResponse>),
+        (status = 401, description = "Unauthorized")
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "Notifications"
+)]
+#[handler]
+pub async fn mark_all_notifications_read(depot: &mut Depot, res: &mut Response) {
+    let Some(user_id) = current_user_id(depot) else {
+        return unauthorized(res);
+    };
+
+    let state = depot.obtain::<AppState>().unwrap();
+    match state.di_container.notification_service.mark_all_read(user_id).await {
+        Ok(response) => res.render(Json(response)),
+        Err(e) => {
+            res.status_code(StatusCode::INTERNAL_SERVER_ERROR).render(Json(e));
+        }
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/notifications/preferences",
+    responses(
+        (status = 200, description = "Every notification kind and whether it is enabled", body = ApiResponse<Vec<NotificationPreferenceResponse>>),
+        (status = 401, description = "Unauthorized")
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "Notifications"
+)]
+#[handler]
+pub async fn get_notification_preferences(depot: &mut Depot, res: &mut Response) {
+    let Some(user_id) = current_user_id(depot) else {
+        return unauthorized(res);
+    };
+
+    let state = depot.obtain::<AppState>().unwrap();
+    match state.di_container.notification_service.get_preferences(user_id).await {
+        Ok(preferences) => res.render(Json(preferences)),
+        Err(e) => {
+            res.status_code(StatusCode::INTERNAL_SERVER_ERROR).render(Json(e));
+        }
+    }
+}
+
+#[utoipa::path(
+    put,
+    path = "/api/notifications/preferences",
+    request_body = UpdateNotificationPreferencesRequest,
+    responses(
+        (status = 200, description = "Preferences saved, every kind is returned", body = ApiResponse<Vec<NotificationPreferenceResponse>>),
+        (status = 400, description = "Invalid request body"),
+        (status = 401, description = "Unauthorized")
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "Notifications"
+)]
+#[handler]
+pub async fn update_notification_preferences(req: &mut Request, depot: &mut Depot, res: &mut Response) {
+    let Some(user_id) = current_user_id(depot) else {
+        return unauthorized(res);
+    };
+
+    let body = match req.parse_body::<UpdateNotificationPreferencesRequest>().await {
+        Ok(body) => body,
+        Err(_) => {
+            res.status_code(StatusCode::BAD_REQUEST).render(Json(json!({"status": "fail", "message": "Invalid request body"})));
+            return;
+        }
+    };
+
+    let state = depot.obtain::<AppState>().unwrap();
+    match state.di_container.notification_service.update_preferences(user_id, &body).await {
+        Ok(preferences) => res.render(Json(preferences)),
+        Err(e) => {
+            res.status_code(StatusCode::INTERNAL_SERVER_ERROR).render(Json(e));
+        }
+    }
+}
+
+pub fn notification_routes() -> Router {
+    Router::new()
+        .push(Router::with_path("api/notifications").get(get_notifications))
+        .push(Router::with_path("api/notifications/unread-count").get(get_unread_count))
+        .push(Router::with_path("api/notifications/read-all").post(mark_all_notifications_read))
+        .push(Router::with_path("api/notifications/preferences").get(get_notification_preferences))
+        .push(Router::with_path("api/notifications/preferences").put(update_notification_preferences))
+        .push(Router::with_path("api/notifications/{id:num}/read").post(mark_notification_read))
+        .hoop(jwt_auth())
+}
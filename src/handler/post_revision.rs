@@ -0,0 +1,131 @@
+use salvo::prelude::*;
+use serde_json::json;
+use crate::{
+    domain::{ApiResponse, PostResponse, PostRevisionDiffResponse, PostRevisionResponse}, handler::authorize_post_editor, middleware::jwt_auth, state::AppState
+};
+
+#[utoipa::path(
+    get,
+    path = "/api/posts/{id}/revisions",
+    params(
+        ("id" = i32, Path, description = "Post ID")
+    ),
+    responses(
+        (status = 200, description = "Get the revision history of a post, newest first", body = ApiResponse<Vec<PostRevisionResponse>>),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Not the author of the post or an admin"),
+        (status = 404, description = "Post not found")
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "Posts"
+)]
+#[handler]
+pub async fn get_revisions(req: &mut Request, depot: &mut Depot, res: &mut Response) {
+    let post_id: i32 = req.param("id").unwrap_or_default();
+    if authorize_post_editor(post_id, depot, res, "Only the author or an admin can manage revisions").await.is_none() {
+        return;
+    }
+
+    let state = depot.obtain::<AppState>().unwrap();
+
+    match state.di_container.post_revision_service.get_revisions(post_id).await {
+        Ok(revisions) => res.render(Json(revisions)),
+        Err(e) => {
+            res.status_code(StatusCode::INTERNAL_SERVER_ERROR).render(Json(e));
+        }
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/posts/{id}/revisions/diff",
+    params(
+        ("id" = i32, Path, description = "Post ID"),
+        ("from" = i32, Query, description = "Revision ID to diff from"),
+        ("to" = i32, Query, description = "Revision ID to diff to")
+    ),
+    responses(
+        (status = 200, description = "Line diff between two revisions", body = ApiResponse<PostRevisionDiffResponse>),
+        (status = 400, description = "Missing revision IDs"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Not the author of the post or an admin"),
+        (status = 404, description = "Post or revision not found")
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "Posts"
+)]
+#[handler]
+pub async fn diff_revisions(req: &mut Request, depot: &mut Depot, res: &mut Response) {
+    let post_id: i32 = req.param("id").unwrap_or_default();
+    if authorize_post_editor(post_id, depot, res, "Only the author or an admin can manage revisions").await.is_none() {
+        return;
+    }
+
+    let state = depot.obtain::<AppState>().unwrap();
+
+    let (Some(from), Some(to)) = (req.query::<i32>("from"), req.query::<i32>("to")) else {
+        res.status_code(StatusCode::BAD_REQUEST).render(Json(json!({"status": "fail", "message": "Query parameters from and to are required"})));
+        return;
+    };
+
+    match state.di_container.post_revision_service.diff_revisions(post_id, from, to).await {
+        Ok(Some(diff)) => res.render(Json(diff)),
+        Ok(None) => {
+            res.status_code(StatusCode::NOT_FOUND).render(Json(json!({"status": "fail", "message": "Revision not found"})));
+        }
+        Err(e) => {
+            res.status_code(StatusCode::INTERNAL_SERVER_ERROR).render(Json(e));
+        }
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/posts/{id}/revisions/{revision_id}/restore",
+    params(
+        ("id" = i32, Path, description = "Post ID"),
+        ("revision_id" = i32, Path, description = "Revision ID to restore")
+    ),
+    responses(
+        (status = 200, description = "Post restored to the revision", body = ApiResponse<PostResponse>),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Not the author of the post or an admin"),
+        (status = 404, description = "Post or revision not found")
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "Posts"
+)]
+#[handler]
+pub async fn restore_revision(req: &mut Request, depot: &mut Depot, res: &mut Response) {
+    let post_id: i32 = req.param("id").unwrap_or_default();
+    let Some(editor_id) = authorize_post_editor(post_id, depot, res, "Only the author or an admin can manage revisions").await else {
+        return;
+    };
+
+    let state = depot.obtain::<AppState>().unwrap();
+    let revision_id: i32 = req.param("revision_id").unwrap_or_default();
+
+    match state.di_container.post_revision_service.restore_revision(post_id, revision_id, editor_id).await {
+        Ok(Some(post)) => res.render(Json(post)),
+        Ok(None) => {
+            res.status_code(StatusCode::NOT_FOUND).render(Json(json!({"status": "fail", "message": "Revision not found"})));
+        }
+        Err(e) => {
+            res.status_code(StatusCode::INTERNAL_SERVER_ERROR).render(Json(e));
+        }
+    }
+}
+
+pub fn post_revision_routes() -> Router {
+    Router::new()
+        .push(Router::with_path("api/posts/{id}/revisions").get(get_revisions))
+        .push(Router::with_path("api/posts/{id}/revisions/diff").get(diff_revisions))
+        .push(Router::with_path("api/posts/{id}/revisions/{revision_id}/restore").post(restore_revision))
+        .hoop(jwt_auth())
+}
@@ -1,22 +1,40 @@
 use salvo::prelude::*;
 use serde_json::json;
 use crate::{
-    domain::{ApiResponse, CreatePostRequest, PostRelationResponse, PostResponse, UpdatePostRequest}, middleware::jwt_auth, state::AppState
+    domain::{ApiResponse, CreatePostRequest, PostAggregateResponse, PostListQuery, PostRelationQuery, PostResponse, SlugLookup, SlugRedirectResponse, UpdatePostRequest}, entities::sea_orm_active_enums::PostStatus, handler::{authorize_post_editor, render_slug_redirect}, middleware::{current_user_id, jwt_auth}, state::AppState
 };
 
 
+fn wants_include(req: &Request, name: &str) -> bool {
+    req.query::<String>("include")
+        .is_some_and(|include| include.split(',').any(|item| item.trim() == name))
+}
+
 #[utoipa::path(
     get,
     path = "/posts",
+    params(PostListQuery),
     responses(
-        (status = 200, description = "Get list of posts", body = ApiResponse<Vec<PostResponse>>)
+        (status = 200, description = "Get list of posts", body = ApiResponse<Vec<PostResponse>>),
+        (status = 400, description = "Invalid sort or order")
     ),
     tag = "Posts"
 )]
 #[handler] 
-pub async fn get_posts(depot: &mut Depot, res: &mut Response) {
+pub async fn get_posts(req: &mut Request, depot: &mut Depot, res: &mut Response) {
+    let query = match req.parse_queries::<PostListQuery>() {
+        Ok(query) => query,
+        Err(e) => {
+            res.status_code(StatusCode::BAD_REQUEST).render(Json(json!({
+                "status": "fail",
+                "message": format!("Invalid query: {}", e)
+            })));
+            return;
+        }
+    };
+
     let state = depot.obtain::<AppState>().unwrap();
-    match state.di_container.post_service.get_all_posts().await {
+    match state.di_container.post_service.get_all_posts(&query, current_user_id(depot)).await {
         Ok(posts) => res.render(Json(posts)),
         Err(e) => {
             res.status_code(StatusCode::INTERNAL_SERVER_ERROR).render(Json(json!(e)));
@@ -24,9 +42,49 @@ pub async fn get_posts(depot: &mut Depot, res: &mut Response) {
     }
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/posts/me",
+    params(
+        ("status" = Option<PostStatus>, Query, description = "Only return posts with this status")
+    ),
+    responses(
+        (status = 200, description = "Get posts written by the authenticated user, drafts included", body = ApiResponse<Vec<PostResponse>>),
+        (status = 401, description = "Unauthorized")
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "Posts"
+)]
+#[handler]
+pub async fn get_my_posts(req: &mut Request, depot: &mut Depot, res: &mut Response) {
+    let Some(user_id) = current_user_id(depot) else {
+        res.status_code(StatusCode::UNAUTHORIZED).render(Json(json!({
+            "status": "fail",
+            "message": "Unauthorized"
+        })));
+        return;
+    };
+
+    let state = depot.obtain::<AppState>().unwrap();
+    let status = req.query::<PostStatus>("status");
+
+    match state.di_container.post_service.get_author_posts(user_id, status).await {
+        Ok(posts) => res.render(Json(posts)),
+        Err(e) => {
+            res.status_code(StatusCode::INTERNAL_SERVER_ERROR).render(Json(e));
+        }
+    }
+}
+
 #[utoipa::path(
     get,
     path = "/api/posts/{id}",
+    params(
+        ("id" = i32, Path, description = "Post ID"),
+        ("include" = Option<String>, Query, description = "Comma separated extras to include, supports toc")
+    ),
     responses(
         (status = 200, description = "Get post by ID", body = ApiResponse<PostResponse>),
         (status = 404, description = "Post not found")
@@ -38,8 +96,52 @@ pub async fn get_post(req: &mut Request, depot: &mut Depot, res: &mut Response)
     let state = depot.obtain::<AppState>().unwrap();
     let post_id: i32 = req.param("id").unwrap_or_default();
 
-    match state.di_container.post_service.get_post(post_id).await {
-        Ok(Some(post)) => res.render(Json(post)),
+    match state.di_container.post_service.get_post(post_id, current_user_id(depot)).await {
+        Ok(Some(mut post)) => {
+            if wants_include(req, "toc") {
+                post.data = post.data.with_toc();
+            }
+            res.render(Json(post))
+        }
+        Ok(None) => {
+            res.status_code(StatusCode::NOT_FOUND).render(Json(json!({
+                "status": "fail",
+                "message": "Post not found"
+            })));
+        }
+        Err(e) => {
+            res.status_code(StatusCode::INTERNAL_SERVER_ERROR).render(Json(e));
+        }
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/posts/slug/{slug}",
+    params(
+        ("slug" = String, Path, description = "Post slug, current or previous"),
+        ("include" = Option<String>, Query, description = "Comma separated extras to include, supports toc")
+    ),
+    responses(
+        (status = 200, description = "Get post by slug, or the canonical slug when requested as JSON", body = ApiResponse<PostResponse>),
+        (status = 301, description = "Slug has changed, redirect to the canonical slug", body = ApiResponse<SlugRedirectResponse>),
+        (status = 404, description = "Post not found")
+    ),
+    tag = "Posts"
+)]
+#[handler]
+pub async fn get_post_by_slug(req: &mut Request, depot: &mut Depot, res: &mut Response) {
+    let state = depot.obtain::<AppState>().unwrap();
+    let slug: String = req.param("slug").unwrap_or_default();
+
+    match state.di_container.post_service.get_post_by_slug(&slug, current_user_id(depot)).await {
+        Ok(Some(SlugLookup::Found(mut post))) => {
+            if wants_include(req, "toc") {
+                post.data = post.data.with_toc();
+            }
+            res.render(Json(post))
+        }
+        Ok(Some(SlugLookup::Moved(redirect))) => render_slug_redirect(req, res, redirect),
         Ok(None) => {
             res.status_code(StatusCode::NOT_FOUND).render(Json(json!({
                 "status": "fail",
@@ -55,21 +157,43 @@ pub async fn get_post(req: &mut Request, depot: &mut Depot, res: &mut Response)
 #[utoipa::path(
     get,
     path = "/api/posts/{id}/relation",
+    params(
+        ("id" = i32, Path, description = "Post ID"),
+        PostRelationQuery
+    ),
     responses(
-        (status = 200, description = "Get related posts", body = ApiResponse<Vec<PostRelationResponse>>),
+        (status = 200, description = "Post with its author, category and a page of comment threads", body = ApiResponse<PostAggregateResponse>),
+        (status = 400, description = "Invalid sort or page"),
         (status = 404, description = "Post not found")
     ),
     tag = "Posts"
 )]
 #[handler]
 pub async fn get_post_relation(req: &mut Request, depot: &mut Depot, res: &mut Response) {
+    let query = match req.parse_queries::<PostRelationQuery>() {
+        Ok(query) => query,
+        Err(e) => {
+            res.status_code(StatusCode::BAD_REQUEST).render(Json(json!({
+                "status": "fail",
+                "message": format!("Invalid query: {}", e)
+            })));
+            return;
+        }
+    };
+
     let state = depot.obtain::<AppState>().unwrap();
     let post_id: i32 = req.param("id").unwrap_or_default();
 
-    match state.di_container.post_service.get_post_relation(post_id).await {
-        Ok(posts) => res.render(Json(posts)),
+    match state.di_container.post_service.get_post_relation(post_id, current_user_id(depot), &query).await {
+        Ok(Some(post)) => res.render(Json(post)),
+        Ok(None) => {
+            res.status_code(StatusCode::NOT_FOUND).render(Json(json!({
+                "status": "fail",
+                "message": "Post not found"
+            })));
+        }
         Err(e) => {
-            res.status_code(StatusCode::INTERNAL_SERVER_ERROR).render(Json(json!(e)));
+            res.status_code(StatusCode::INTERNAL_SERVER_ERROR).render(Json(e));
         }
     }
 }
@@ -80,7 +204,7 @@ pub async fn get_post_relation(req: &mut Request, depot: &mut Depot, res: &mut R
     request_body = CreatePostRequest,
     responses(
         (status = 201, description = "Post created successfully", body = ApiResponse<PostResponse>),
-        (status = 400, description = "Invalid request body"),
+        (status = 400, description = "Invalid request body or schedule"),
         (status = 500, description = "Internal server error")
     ),
     security(
@@ -91,7 +215,7 @@ pub async fn get_post_relation(req: &mut Request, depot: &mut Depot, res: &mut R
 #[handler]
 pub async fn create_post(req: &mut Request, depot: &mut Depot, res: &mut Response) {
     let state = depot.obtain::<AppState>().unwrap();
-    let body = match req.parse_body::<CreatePostRequest>().await {
+    let mut body = match req.parse_body::<CreatePostRequest>().await {
         Ok(body) => body,
         Err(_) => {
             res.status_code(StatusCode::BAD_REQUEST).render(Json(json!({"status": "fail", "message": "Invalid request body"})));
@@ -100,6 +224,8 @@ pub async fn create_post(req: &mut Request, depot: &mut Depot, res: &mut Respons
         }
     };
 
+    body.editor_id = current_user_id(depot);
+
     match state.di_container.post_service.create_post(&body).await {
         Ok(post) => {
             res.status_code(StatusCode::CREATED).render(Json({
@@ -110,6 +236,9 @@ pub async fn create_post(req: &mut Request, depot: &mut Depot, res: &mut Respons
                 })
             }));
         }
+        Err(e) if e.status == "fail" => {
+            res.status_code(StatusCode::BAD_REQUEST).render(Json(e));
+        }
         Err(e) => {
             res.status_code(StatusCode::INTERNAL_SERVER_ERROR).render(Json(e));
         }
@@ -122,7 +251,10 @@ pub async fn create_post(req: &mut Request, depot: &mut Depot, res: &mut Respons
     request_body = UpdatePostRequest,
     responses(
         (status = 200, description = "Post updated successfully", body = ApiResponse<PostResponse>),
-        (status = 400, description = "Invalid request body"),
+        (status = 400, description = "Invalid request body or schedule"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Not the author of the post or an admin"),
+        (status = 404, description = "Post not found"),
         (status = 5000, description = "Internal server error")
     ),
     security(
@@ -132,8 +264,12 @@ pub async fn create_post(req: &mut Request, depot: &mut Depot, res: &mut Respons
 )]
 #[handler]
 pub async fn update_post(req: &mut Request, depot: &mut Depot, res: &mut Response) {
-    let state = depot.obtain::<AppState>().unwrap();
     let post_id: i32 = req.param("id").unwrap_or_default();
+    let Some(editor_id) = authorize_post_editor(post_id, depot, res, "Only the author or an admin can edit this post").await else {
+        return;
+    };
+
+    let state = depot.obtain::<AppState>().unwrap();
     let mut body = match req.parse_body::<UpdatePostRequest>().await {
         Ok(body) => body,
         Err(_) => {
@@ -143,9 +279,13 @@ pub async fn update_post(req: &mut Request, depot: &mut Depot, res: &mut Respons
     };
 
     body.post_id = Some(post_id);
+    body.editor_id = Some(editor_id);
 
     match state.di_container.post_service.update_post(&body).await {
         Ok(post) => res.render(Json(post)),
+        Err(e) if e.status == "fail" => {
+            res.status_code(StatusCode::BAD_REQUEST).render(Json(e));
+        }
         Err(e) => {
             res.status_code(StatusCode::INTERNAL_SERVER_ERROR).render(Json(e));
         }
@@ -157,6 +297,8 @@ pub async fn update_post(req: &mut Request, depot: &mut Depot, res: &mut Respons
     path = "/api/posts/{id}",
     responses(
         (status = 200, description = "Post deleted successfully"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Not the author of the post or an admin"),
         (status = 404, description = "Post not found"),
         (status = 500, description = "Internal server error")
     ),
@@ -167,8 +309,12 @@ pub async fn update_post(req: &mut Request, depot: &mut Depot, res: &mut Respons
 )]
 #[handler]
 pub async fn delete_post(req: &mut Request, depot: &mut Depot, res: &mut Response) {
-    let state = depot.obtain::<AppState>().unwrap();
     let post_id: i32 = req.param("id").unwrap_or_default();
+    if authorize_post_editor(post_id, depot, res, "Only the author or an admin can delete this post").await.is_none() {
+        return;
+    }
+
+    let state = depot.obtain::<AppState>().unwrap();
 
     match state.di_container.post_service.delete_post(post_id).await {
         Ok(_) => {
@@ -186,18 +332,26 @@ pub async fn delete_post(req: &mut Request, depot: &mut Depot, res: &mut Respons
 pub fn post_routes() -> Router {
     let protected_routes = Router::new()
         .push(Router::with_path("api/posts").post(create_post))
+        .push(Router::with_path("api/posts/me").get(get_my_posts))
         .push(Router::with_path("api/posts/{id}").put(update_post))
         .push(Router::with_path("api/posts/{id}").delete(delete_post))
         
         .hoop(jwt_auth());
 
+        // Public, the token is only read to include the reader's own reactions
         let public_routes = Router::new()
         .push(Router::with_path("api/posts").get(get_posts))
-        .push(Router::with_path("api/posts/{id}").get(get_post))
-        .push(Router::with_path("api/posts/{id}/relation").get(get_post_relation));
-    
+        .push(Router::with_path("api/posts/{id:num}").get(get_post))
+        .push(Router::with_path("api/posts/slug/{slug}").get(get_post_by_slug))
+        .hoop(jwt_auth());
+
+    // Signed in readers also get their own pending comments
+    let relation_routes = Router::new()
+        .push(Router::with_path("api/posts/{id:num}/relation").get(get_post_relation))
+        .hoop(jwt_auth());
 
-   return Router::new()
+   Router::new()
         .push(protected_routes)
-        .push(public_routes);
+        .push(public_routes)
+        .push(relation_routes)
 }
@@ -0,0 +1,198 @@
+use salvo::prelude::*;
+use serde_json::json;
+use crate::{
+    domain::{ApiResponse, CommentVoteRequest, CommentVoteResponse, PostReactionsResponse},
+    entities::sea_orm_active_enums::ReactionKind,
+    middleware::{current_user_id, jwt_auth},
+    state::AppState,
+};
+
+fn unauthorized(res: &mut Response) {
+    res.status_code(StatusCode::UNAUTHORIZED).render(Json(json!({
+        "status": "fail",
+        "message": "Unauthorized"
+    })));
+}
+
+fn not_found(res: &mut Response, message: &str) {
+    res.status_code(StatusCode::NOT_FOUND).render(Json(json!({
+        "status": "fail",
+        "message": message
+    })));
+}
+
+fn reaction_kind(req: &mut Request, res: &mut Response) -> Option<ReactionKind> {
+    let kind = req.param::<ReactionKind>("kind");
+    if kind.is_none() {
+        res.status_code(StatusCode::BAD_REQUEST).render(Json(json!({
+            "status": "fail",
+            "message": "Unknown reaction kind, expected like, love, insightful or funny"
+        })));
+    }
+    kind
+}
+
+#[utoipa::path(
+    put,
+    path = "/api/posts/{id}/reactions/{kind}",
+    params(
+        ("id" = i32, Path, description = "Post ID"),
+        ("kind" = ReactionKind, Path, description = "Reaction kind")
+    ),
+    responses(
+        (status = 200, description = "Reaction added, adding it again changes nothing", body = ApiResponse<PostReactionsResponse>),
+        (status = 400, description = "Unknown reaction kind"),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Post not found")
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "Reactions"
+)]
+#[handler]
+pub async fn react_to_post(req: &mut Request, depot: &mut Depot, res: &mut Response) {
+    let Some(user_id) = current_user_id(depot) else {
+        return unauthorized(res);
+    };
+    let Some(kind) = reaction_kind(req, res) else {
+        return;
+    };
+
+    let state = depot.obtain::<AppState>().unwrap();
+    let post_id: i32 = req.param("id").unwrap_or_default();
+
+    match state.di_container.reaction_service.react_to_post(post_id, user_id, kind).await {
+        Ok(Some(reactions)) => res.render(Json(reactions)),
+        Ok(None) => not_found(res, "Post not found"),
+        Err(e) => {
+            res.status_code(StatusCode::INTERNAL_SERVER_ERROR).render(Json(e));
+        }
+    }
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/posts/{id}/reactions/{kind}",
+    params(
+        ("id" = i32, Path, description = "Post ID"),
+        ("kind" = ReactionKind, Path, description = "Reaction kind")
+    ),
+    responses(
+        (status = 200, description = "Reaction removed", body = ApiResponse<PostReactionsResponse>),
+        (status = 400, description = "Unknown reaction kind"),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Post not found")
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "Reactions"
+)]
+#[handler]
+pub async fn remove_post_reaction(req: &mut Request, depot: &mut Depot, res: &mut Response) {
+    let Some(user_id) = current_user_id(depot) else {
+        return unauthorized(res);
+    };
+    let Some(kind) = reaction_kind(req, res) else {
+        return;
+    };
+
+    let state = depot.obtain::<AppState>().unwrap();
+    let post_id: i32 = req.param("id").unwrap_or_default();
+
+    match state.di_container.reaction_service.remove_post_reaction(post_id, user_id, kind).await {
+        Ok(Some(reactions)) => res.render(Json(reactions)),
+        Ok(None) => not_found(res, "Post not found"),
+        Err(e) => {
+            res.status_code(StatusCode::INTERNAL_SERVER_ERROR).render(Json(e));
+        }
+    }
+}
+
+#[utoipa::path(
+    put,
+    path = "/api/comments/{id}/vote",
+    params(
+        ("id" = i32, Path, description = "Comment ID")
+    ),
+    request_body = CommentVoteRequest,
+    responses(
+        (status = 200, description = "Vote recorded, replacing any earlier vote", body = ApiResponse<CommentVoteResponse>),
+        (status = 400, description = "Invalid request body"),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Comment not found")
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "Reactions"
+)]
+#[handler]
+pub async fn vote_comment(req: &mut Request, depot: &mut Depot, res: &mut Response) {
+    let Some(user_id) = current_user_id(depot) else {
+        return unauthorized(res);
+    };
+
+    let body = match req.parse_body::<CommentVoteRequest>().await {
+        Ok(body) => body,
+        Err(_) => {
+            res.status_code(StatusCode::BAD_REQUEST).render(Json(json!({"status": "fail", "message": "Invalid request body"})));
+            return;
+        }
+    };
+
+    let state = depot.obtain::<AppState>().unwrap();
+    let comment_id: i32 = req.param("id").unwrap_or_default();
+
+    match state.di_container.reaction_service.vote_comment(comment_id, user_id, body.direction).await {
+        Ok(Some(votes)) => res.render(Json(votes)),
+        Ok(None) => not_found(res, "Comment not found"),
+        Err(e) => {
+            res.status_code(StatusCode::INTERNAL_SERVER_ERROR).render(Json(e));
+        }
+    }
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/comments/{id}/vote",
+    params(
+        ("id" = i32, Path, description = "Comment ID")
+    ),
+    responses(
+        (status = 200, description = "Vote removed", body = ApiResponse<CommentVoteResponse>),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Comment not found")
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "Reactions"
+)]
+#[handler]
+pub async fn remove_comment_vote(req: &mut Request, depot: &mut Depot, res: &mut Response) {
+    let Some(user_id) = current_user_id(depot) else {
+        return unauthorized(res);
+    };
+
+    let state = depot.obtain::<AppState>().unwrap();
+    let comment_id: i32 = req.param("id").unwrap_or_default();
+
+    match state.di_container.reaction_service.remove_comment_vote(comment_id, user_id).await {
+        Ok(Some(votes)) => res.render(Json(votes)),
+        Ok(None) => not_found(res, "Comment not found"),
+        Err(e) => {
+            res.status_code(StatusCode::INTERNAL_SERVER_ERROR).render(Json(e));
+        }
+    }
+}
+
+pub fn reaction_routes() -> Router {
+    Router::new()
+        .push(Router::with_path("api/posts/{id:num}/reactions/{kind}").put(react_to_post))
+        .push(Router::with_path("api/posts/{id:num}/reactions/{kind}").delete(remove_post_reaction))
+        .push(Router::with_path("api/comments/{id:num}/vote").put(vote_comment))
+        .push(Router::with_path("api/comments/{id:num}/vote").delete(remove_comment_vote))
+        .hoop(jwt_auth())
+}
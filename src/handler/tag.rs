@@ -0,0 +1,159 @@
+use salvo::prelude::*;
+use serde_json::json;
+use crate::{
+    domain::{ApiResponse, MergeTagRequest, PostResponse, RenameTagRequest, TagResponse}, middleware::{current_user_id, jwt_auth, require_admin}, state::AppState
+};
+
+#[utoipa::path(
+    get,
+    path = "/api/tags",
+    responses(
+        (status = 200, description = "Get list of tags with their post counts", body = ApiResponse<Vec<TagResponse>>)
+    ),
+    tag = "Tags"
+)]
+#[handler]
+pub async fn get_tags(depot: &mut Depot, res: &mut Response) {
+    let state = depot.obtain::<AppState>().unwrap();
+
+    match state.di_container.tag_service.get_tags().await {
+        Ok(tags) => res.render(Json(tags)),
+        Err(e) => {
+            res.status_code(StatusCode::INTERNAL_SERVER_ERROR).render(Json(e));
+        }
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/tags/{slug}/posts",
+    params(
+        ("slug" = String, Path, description = "Tag slug")
+    ),
+    responses(
+        (status = 200, description = "Get published posts with the tag", body = ApiResponse<Vec<PostResponse>>)
+    ),
+    tag = "Tags"
+)]
+#[handler]
+pub async fn get_tag_posts(req: &mut Request, depot: &mut Depot, res: &mut Response) {
+    let state = depot.obtain::<AppState>().unwrap();
+    let slug: String = req.param("slug").unwrap_or_default();
+
+    match state.di_container.post_service.get_posts_by_tag(&slug, current_user_id(depot)).await {
+        Ok(posts) => res.render(Json(posts)),
+        Err(e) => {
+            res.status_code(StatusCode::INTERNAL_SERVER_ERROR).render(Json(e));
+        }
+    }
+}
+
+#[utoipa::path(
+    put,
+    path = "/api/tags/{id}",
+    params(
+        ("id" = i32, Path, description = "Tag ID")
+    ),
+    request_body = RenameTagRequest,
+    responses(
+        (status = 200, description = "Tag renamed successfully", body = ApiResponse<TagResponse>),
+        (status = 400, description = "Invalid request body, a name without letters or digits, or a slug already in use"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Admin role required"),
+        (status = 404, description = "Tag not found")
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "Tags"
+)]
+#[handler]
+pub async fn rename_tag(req: &mut Request, depot: &mut Depot, res: &mut Response) {
+    let state = depot.obtain::<AppState>().unwrap();
+    let tag_id: i32 = req.param("id").unwrap_or_default();
+    let mut body = match req.parse_body::<RenameTagRequest>().await {
+        Ok(body) => body,
+        Err(_) => {
+            res.status_code(StatusCode::BAD_REQUEST).render(Json(json!({"status": "fail", "message": "Invalid request body"})));
+            return;
+        }
+    };
+
+    body.id = Some(tag_id);
+
+    match state.di_container.tag_service.rename_tag(&body).await {
+        Ok(Some(tag)) => res.render(Json(tag)),
+        Ok(None) => {
+            res.status_code(StatusCode::NOT_FOUND).render(Json(json!({"status": "fail", "message": "Tag not found"})));
+        }
+        Err(e) if e.status == "fail" => {
+            res.status_code(StatusCode::BAD_REQUEST).render(Json(e));
+        }
+        Err(e) => {
+            res.status_code(StatusCode::INTERNAL_SERVER_ERROR).render(Json(e));
+        }
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/tags/{id}/merge",
+    params(
+        ("id" = i32, Path, description = "ID of the tag to merge away")
+    ),
+    request_body = MergeTagRequest,
+    responses(
+        (status = 200, description = "Tag merged into the target tag", body = ApiResponse<TagResponse>),
+        (status = 400, description = "Invalid request body or the tag is merged into itself"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Admin role required"),
+        (status = 404, description = "Tag not found")
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "Tags"
+)]
+#[handler]
+pub async fn merge_tag(req: &mut Request, depot: &mut Depot, res: &mut Response) {
+    let state = depot.obtain::<AppState>().unwrap();
+    let tag_id: i32 = req.param("id").unwrap_or_default();
+    let body = match req.parse_body::<MergeTagRequest>().await {
+        Ok(body) => body,
+        Err(_) => {
+            res.status_code(StatusCode::BAD_REQUEST).render(Json(json!({"status": "fail", "message": "Invalid request body"})));
+            return;
+        }
+    };
+
+    match state.di_container.tag_service.merge_tag(tag_id, &body).await {
+        Ok(Some(tag)) => res.render(Json(tag)),
+        Ok(None) => {
+            res.status_code(StatusCode::NOT_FOUND).render(Json(json!({"status": "fail", "message": "Tag not found"})));
+        }
+        Err(e) if e.status == "fail" => {
+            res.status_code(StatusCode::BAD_REQUEST).render(Json(e));
+        }
+        Err(e) => {
+            res.status_code(StatusCode::INTERNAL_SERVER_ERROR).render(Json(e));
+        }
+    }
+}
+
+pub fn tag_routes() -> Router {
+    // Tags are shared by every author, so renaming or merging one is an admin task
+    let admin_routes = Router::new()
+        .push(Router::with_path("api/tags/{id}").put(rename_tag))
+        .push(Router::with_path("api/tags/{id}/merge").post(merge_tag))
+        .hoop(jwt_auth())
+        .hoop(require_admin);
+
+    let public_routes = Router::new()
+        .push(Router::with_path("api/tags").get(get_tags))
+        .push(Router::with_path("api/tags/{slug}/posts").get(get_tag_posts))
+        .hoop(jwt_auth());
+
+    Router::new()
+        .push(admin_routes)
+        .push(public_routes)
+}
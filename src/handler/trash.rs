@@ -0,0 +1,84 @@
+use salvo::prelude::*;
+use serde_json::json;
+use crate::{
+    domain::{ApiResponse, TrashKind, TrashResponse}, middleware::{jwt_auth, require_admin}, state::AppState
+};
+
+#[utoipa::path(
+    get,
+    path = "/api/trash",
+    responses(
+        (status = 200, description = "List soft deleted posts, categories, comments and users, most recently deleted first", body = ApiResponse<TrashResponse>),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Admin role required")
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "Trash"
+)]
+#[handler]
+pub async fn get_trash(depot: &mut Depot, res: &mut Response) {
+    let state = depot.obtain::<AppState>().unwrap();
+
+    match state.di_container.trash_service.get_trash().await {
+        Ok(trash) => res.render(Json(trash)),
+        Err(e) => {
+            res.status_code(StatusCode::INTERNAL_SERVER_ERROR).render(Json(e));
+        }
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/trash/{kind}/{id}/restore",
+    params(
+        ("kind" = TrashKind, Path, description = "Kind of item to restore"),
+        ("id" = i32, Path, description = "ID of the trashed item")
+    ),
+    responses(
+        (status = 200, description = "Move an item out of the trash", body = Value),
+        (status = 400, description = "Unknown kind"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Admin role required"),
+        (status = 404, description = "Item not found in trash")
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "Trash"
+)]
+#[handler]
+pub async fn restore_item(req: &mut Request, depot: &mut Depot, res: &mut Response) {
+    let Some(kind) = req.param::<TrashKind>("kind") else {
+        res.status_code(StatusCode::BAD_REQUEST).render(Json(json!({
+            "status": "fail",
+            "message": "Kind must be one of posts, categories, comments or users"
+        })));
+        return;
+    };
+
+    let state = depot.obtain::<AppState>().unwrap();
+    let id: i32 = req.param("id").unwrap_or_default();
+
+    match state.di_container.trash_service.restore(kind, id).await {
+        Ok(Some(response)) => res.render(Json(response)),
+        Ok(None) => {
+            res.status_code(StatusCode::NOT_FOUND).render(Json(json!({
+                "status": "fail",
+                "message": "Item not found in trash"
+            })));
+        }
+        Err(e) => {
+            res.status_code(StatusCode::INTERNAL_SERVER_ERROR).render(Json(e));
+        }
+    }
+}
+
+pub fn trash_routes() -> Router {
+    Router::new()
+        .hoop(jwt_auth())
+        .hoop(require_admin)
+        .push(Router::with_path("api/trash").get(get_trash))
+        .push(Router::with_path("api/trash/{kind}/{id}/restore").post(restore_item))
+}
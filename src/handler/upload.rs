@@ -0,0 +1,125 @@
+use salvo::http::header::{self, HeaderValue};
+use salvo::prelude::*;
+use salvo::size_limiter;
+use serde_json::json;
+use crate::{
+    domain::{ApiResponse, UploadResponse}, middleware::{current_user_id, jwt_auth}, state::AppState
+};
+
+#[utoipa::path(
+    post,
+    path = "/api/uploads",
+    request_body(content = String, content_type = "multipart/form-data", description = "Image in the file field"),
+    responses(
+        (status = 200, description = "Image stored and added to the media library, use the returned url as a post img", body = ApiResponse<UploadResponse>),
+        (status = 400, description = "Missing file or unsupported image type"),
+        (status = 401, description = "Unauthorized"),
+        (status = 413, description = "File too large")
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "Uploads"
+)]
+#[handler]
+pub async fn upload_image(req: &mut Request, depot: &mut Depot, res: &mut Response) {
+    let Some(user_id) = current_user_id(depot) else {
+        res.status_code(StatusCode::UNAUTHORIZED).render(Json(json!({"status": "fail", "message": "Unauthorized"})));
+        return;
+    };
+
+    let state = depot.obtain::<AppState>().unwrap();
+
+    let Some(file) = req.file("file").await else {
+        res.status_code(StatusCode::BAD_REQUEST).render(Json(json!({"status": "fail", "message": "Multipart field file is required"})));
+        return;
+    };
+
+    let bytes = match tokio::fs::read(file.path()).await {
+        Ok(bytes) => bytes,
+        Err(_) => {
+            res.status_code(StatusCode::BAD_REQUEST).render(Json(json!({"status": "fail", "message": "Could not read uploaded file"})));
+            return;
+        }
+    };
+
+    let original_name = file.name().unwrap_or_default().to_string();
+
+    match state.di_container.upload_service.upload_image(user_id, &original_name, &bytes).await {
+        Ok(upload) => res.render(Json(upload)),
+        Err(e) if e.status == "fail" => {
+            res.status_code(StatusCode::BAD_REQUEST).render(Json(e));
+        }
+        Err(e) => {
+            res.status_code(StatusCode::INTERNAL_SERVER_ERROR).render(Json(e));
+        }
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/uploads/{filename}",
+    params(
+        ("filename" = String, Path, description = "Stored file name")
+    ),
+    responses(
+        (status = 200, description = "The stored file"),
+        (status = 304, description = "Not modified"),
+        (status = 404, description = "File not found")
+    ),
+    tag = "Uploads"
+)]
+#[handler]
+pub async fn get_upload(req: &mut Request, depot: &mut Depot, res: &mut Response) {
+    let state = depot.obtain::<AppState>().unwrap();
+    let filename: String = req.param("filename").unwrap_or_default();
+
+    // File names are content hashes, so the name doubles as a strong validator
+    let etag = format!("\"{}\"", filename.split('.').next().unwrap_or_default());
+    let cache_control = HeaderValue::from_static("public, max-age=31536000, immutable");
+
+    if req.headers().get(header::IF_NONE_MATCH).and_then(|value| value.to_str().ok()) == Some(etag.as_str()) {
+        res.status_code(StatusCode::NOT_MODIFIED);
+        res.headers_mut().insert(header::CACHE_CONTROL, cache_control);
+        return;
+    }
+
+    match state.di_container.upload_service.get_upload(&filename).await {
+        Ok(Some(file)) => {
+            let headers = res.headers_mut();
+            headers.insert(header::CACHE_CONTROL, cache_control);
+            if let Ok(content_type) = HeaderValue::from_str(&file.content_type) {
+                headers.insert(header::CONTENT_TYPE, content_type);
+            }
+            if let Ok(etag) = HeaderValue::from_str(&etag) {
+                headers.insert(header::ETAG, etag);
+            }
+            headers.insert(header::X_CONTENT_TYPE_OPTIONS, HeaderValue::from_static("nosniff"));
+
+            let _ = res.write_body(file.bytes);
+        }
+        Ok(None) => {
+            res.status_code(StatusCode::NOT_FOUND).render(Json(json!({"status": "fail", "message": "File not found"})));
+        }
+        Err(e) => {
+            res.status_code(StatusCode::INTERNAL_SERVER_ERROR).render(Json(e));
+        }
+    }
+}
+
+pub fn upload_routes(max_size: usize) -> Router {
+    let protected_routes = Router::new()
+        .push(
+            Router::with_path("api/uploads")
+                .hoop(size_limiter::max_size(max_size as u64))
+                .post(upload_image),
+        )
+        .hoop(jwt_auth());
+
+    let public_routes = Router::new()
+        .push(Router::with_path("uploads/{filename}").get(get_upload));
+
+    Router::new()
+        .push(protected_routes)
+        .push(public_routes)
+}
@@ -155,6 +155,6 @@ pub fn user_routes() -> Router {
         .push(Router::with_path("api/user/{email}").delete(delete_user))
         .hoop(jwt_auth());
 
-    return Router::new()
-        .push(protected_routes);
+    Router::new()
+        .push(protected_routes)
 }
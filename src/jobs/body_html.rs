@@ -0,0 +1,15 @@
+use tokio::task::JoinHandle;
+use tracing::{error, info};
+
+use crate::abstract_trait::DynPostsService;
+
+// Posts saved before HTML was cached, or whose cache a migration dropped, are rendered once at startup
+pub fn spawn_body_html_backfill(post_service: DynPostsService) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        match post_service.backfill_body_html().await {
+            Ok(0) => {}
+            Ok(count) => info!("Rendered the body HTML of {} posts", count),
+            Err(e) => error!("Failed to render missing body HTML: {}", e),
+        }
+    })
+}
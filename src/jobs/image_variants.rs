@@ -0,0 +1,61 @@
+use sea_orm::Set;
+use tokio::task::JoinHandle;
+use tracing::{error, info};
+
+use crate::abstract_trait::{DynImageVariantRepository, DynStorage};
+use crate::entities::image_variants;
+use crate::utils::{render_variants, ImageSize};
+
+// Resizing a large photo takes long enough that it must not hold up the upload request
+pub fn spawn_image_variants(
+    storage: DynStorage,
+    repository: DynImageVariantRepository,
+    source_key: String,
+    bytes: Vec<u8>,
+    sizes: Vec<ImageSize>,
+) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let (stem, extension) = source_key.rsplit_once('.').unwrap_or((source_key.as_str(), ""));
+        let (stem, extension) = (stem.to_string(), extension.to_string());
+
+        let rendered = tokio::task::spawn_blocking(move || render_variants(&bytes, &extension, &sizes)).await;
+
+        let variants = match rendered {
+            Ok(Ok(variants)) => variants,
+            Ok(Err(e)) => {
+                error!("Failed to render variants of {}: {}", source_key, e);
+                return;
+            }
+            Err(e) => {
+                error!("Image processing task for {} panicked: {}", source_key, e);
+                return;
+            }
+        };
+
+        for variant in &variants {
+            let storage_key = format!("{}-{}.{}", stem, variant.name, variant.extension);
+
+            if let Err(e) = storage.put(&storage_key, &variant.bytes).await {
+                error!("Failed to store image variant {}: {}", storage_key, e);
+                continue;
+            }
+
+            let record = image_variants::ActiveModel {
+                source_key: Set(source_key.clone()),
+                name: Set(variant.name.clone()),
+                storage_key: Set(storage_key.clone()),
+                content_type: Set(variant.content_type.to_string()),
+                width: Set(variant.width as i32),
+                height: Set(variant.height as i32),
+                size: Set(variant.bytes.len() as i32),
+                ..Default::default()
+            };
+
+            if let Err(e) = repository.create(record).await {
+                error!("Failed to record image variant {}: {}", storage_key, e);
+            }
+        }
+
+        info!("Stored {} variants of {}", variants.len(), source_key);
+    })
+}
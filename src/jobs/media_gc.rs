@@ -0,0 +1,22 @@
+use std::time::Duration;
+
+use tokio::task::JoinHandle;
+use tracing::error;
+
+use crate::abstract_trait::DynMediaService;
+
+const GC_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+pub fn spawn_media_gc(media_service: DynMediaService) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(GC_INTERVAL);
+
+        loop {
+            interval.tick().await;
+
+            if let Err(e) = media_service.collect_garbage().await {
+                error!("Failed to collect unreferenced media files: {}", e);
+            }
+        }
+    })
+}
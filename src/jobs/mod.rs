@@ -0,0 +1,11 @@
+mod body_html;
+mod image_variants;
+mod media_gc;
+mod publisher;
+mod trash_purge;
+
+pub use self::body_html::spawn_body_html_backfill;
+pub use self::image_variants::spawn_image_variants;
+pub use self::media_gc::spawn_media_gc;
+pub use self::publisher::spawn_scheduled_publisher;
+pub use self::trash_purge::spawn_trash_purge;
@@ -0,0 +1,24 @@
+use std::time::Duration;
+
+use tokio::task::JoinHandle;
+use tracing::{error, info};
+
+use crate::abstract_trait::DynPostsService;
+
+const PUBLISH_INTERVAL: Duration = Duration::from_secs(30);
+
+pub fn spawn_scheduled_publisher(post_service: DynPostsService) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(PUBLISH_INTERVAL);
+
+        loop {
+            interval.tick().await;
+
+            match post_service.publish_scheduled_posts().await {
+                Ok(0) => {}
+                Ok(count) => info!("Published {} scheduled posts", count),
+                Err(e) => error!("Failed to publish scheduled posts: {}", e),
+            }
+        }
+    })
+}
@@ -0,0 +1,22 @@
+use std::time::Duration;
+
+use tokio::task::JoinHandle;
+use tracing::error;
+
+use crate::abstract_trait::DynTrashService;
+
+const PURGE_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+pub fn spawn_trash_purge(trash_service: DynTrashService) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(PURGE_INTERVAL);
+
+        loop {
+            interval.tick().await;
+
+            if let Err(e) = trash_service.purge_expired().await {
+                error!("Failed to purge expired trash: {}", e);
+            }
+        }
+    })
+}
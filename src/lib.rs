@@ -8,4 +8,6 @@ pub mod config;
 pub mod state;
 pub mod handler;
 pub mod migrations;
-pub mod middleware;
\ No newline at end of file
+pub mod middleware;
+pub mod jobs;
+pub mod storage;
\ No newline at end of file
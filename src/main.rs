@@ -1,10 +1,10 @@
 use dotenv::dotenv;
 
-use example_salvo_seaorm::config::{Config, ConnectionManager};
+use example_salvo_seaorm::config::{CommentConfig, Config, ConnectionManager, UploadConfig};
 use example_salvo_seaorm::handler::AppRouter;
-use example_salvo_seaorm::migrations::m20220101_000001_create_table::Migration;
+use example_salvo_seaorm::migrations::Migrator;
 use example_salvo_seaorm::state::AppState;
-use example_salvo_seaorm::utils::tracing;
+use example_salvo_seaorm::utils::{init_highlighter, tracing};
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
@@ -14,13 +14,23 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     let config = Config::init();
 
+    init_highlighter(&config.highlight_theme, config.highlight_languages.clone());
+
     let db_pool =
-        ConnectionManager::new_pool::<Migration>(&config.database_url, config.run_migrations)
+        ConnectionManager::new_pool::<Migrator>(&config.database_url, config.run_migrations)
             .await?;
 
     let port = config.port;
 
-    let state = AppState::new(db_pool, &config.jwt_secret);
+    let upload_config = UploadConfig::new(&config.upload_dir, config.upload_max_size, config.image_sizes.clone());
+
+    let comment_config = CommentConfig::new(
+        config.comment_max_depth,
+        config.comment_require_approval,
+        config.comment_edit_window_minutes,
+    );
+
+    let state = AppState::new(db_pool, &config.jwt_secret, upload_config, config.trash_retention_days, comment_config);
 
     println!("🚀 Server started successfully");
 
@@ -1,12 +1,65 @@
-use salvo::jwt_auth::{ConstDecoder, HeaderFinder, JwtAuth};
+use salvo::jwt_auth::{ConstDecoder, HeaderFinder, JwtAuth, JwtAuthDepotExt, JwtAuthState};
+use salvo::prelude::*;
+use serde_json::json;
 
 use crate::config::Claims;
+use crate::entities::sea_orm_active_enums::UserRole;
+use crate::state::AppState;
 
 pub fn jwt_auth() -> JwtAuth<Claims, ConstDecoder> {
     let secret_key =
         std::env::var("JWT_SECRET").expect("SECRET_KEY must be set in environment variables");
 
-    return JwtAuth::new(ConstDecoder::from_secret(secret_key.as_bytes()))
+    JwtAuth::new(ConstDecoder::from_secret(secret_key.as_bytes()))
         .finders(vec![Box::new(HeaderFinder::new())])
-        .force_passed(true);
+        .force_passed(true)
+}
+
+// `jwt_auth` lets anonymous requests through, handlers decide what a missing user means
+pub fn current_user_id(depot: &Depot) -> Option<i32> {
+    match depot.jwt_auth_state() {
+        JwtAuthState::Authorized => depot
+            .jwt_auth_data::<Claims>()
+            .map(|data| data.claims.user_id as i32),
+        _ => None,
+    }
+}
+// Runs after `jwt_auth` and stops the request unless the account has one of `roles`
+async fn require_role(depot: &mut Depot, res: &mut Response, ctrl: &mut FlowCtrl, roles: &[UserRole], message: &str) {
+    let Some(user_id) = current_user_id(depot) else {
+        res.status_code(StatusCode::UNAUTHORIZED).render(Json(json!({
+            "status": "fail",
+            "message": "Unauthorized"
+        })));
+        ctrl.skip_rest();
+        return;
+    };
+
+    let state = depot.obtain::<AppState>().unwrap();
+
+    match state.di_container.user_service.find_by_id(user_id).await {
+        Ok(Some(user)) if roles.contains(&user.data.role) => {}
+        Ok(_) => {
+            res.status_code(StatusCode::FORBIDDEN).render(Json(json!({
+                "status": "fail",
+                "message": message
+            })));
+            ctrl.skip_rest();
+        }
+        Err(e) => {
+            res.status_code(StatusCode::INTERNAL_SERVER_ERROR).render(Json(e));
+            ctrl.skip_rest();
+        }
+    }
+}
+
+#[handler]
+pub async fn require_admin(depot: &mut Depot, res: &mut Response, ctrl: &mut FlowCtrl) {
+    require_role(depot, res, ctrl, &[UserRole::Admin], "Admin role required").await;
+}
+
+// Admins can moderate too
+#[handler]
+pub async fn require_moderator(depot: &mut Depot, res: &mut Response, ctrl: &mut FlowCtrl) {
+    require_role(depot, res, ctrl, &[UserRole::Moderator, UserRole::Admin], "Moderator role required").await;
 }
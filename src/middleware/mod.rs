@@ -1,3 +1,3 @@
 mod auth;
 
-pub use self::auth::jwt_auth;
\ No newline at end of file
+pub use self::auth::{current_user_id, jwt_auth, require_admin, require_moderator};
\ No newline at end of file
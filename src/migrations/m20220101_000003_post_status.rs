@@ -0,0 +1,74 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Existing posts were public, so they start out published
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Posts::Table)
+                    .add_column(
+                        ColumnDef::new(Posts::Status)
+                            .string_len(16)
+                            .not_null()
+                            .default("published"),
+                    )
+                    .add_column(ColumnDef::new(Posts::PublishedAt).timestamp_with_time_zone().null())
+                    .add_column(ColumnDef::new(Posts::PublishAt).timestamp_with_time_zone().null())
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .get_connection()
+            .execute_unprepared("UPDATE posts SET published_at = CURRENT_TIMESTAMP WHERE status = 'published'")
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-posts-status-publish_at")
+                    .table(Posts::Table)
+                    .col(Posts::Status)
+                    .col(Posts::PublishAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx-posts-status-publish_at")
+                    .table(Posts::Table)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Posts::Table)
+                    .drop_column(Posts::Status)
+                    .drop_column(Posts::PublishedAt)
+                    .drop_column(Posts::PublishAt)
+                    .to_owned(),
+            )
+            .await?;
+        Ok(())
+    }
+}
+
+#[derive(Iden)]
+enum Posts {
+    Table,
+    Status,
+    PublishedAt,
+    PublishAt,
+}
\ No newline at end of file
@@ -0,0 +1,109 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Create post_revisions table
+        manager
+            .create_table(
+                Table::create()
+                    .table(PostRevisions::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(PostRevisions::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(PostRevisions::PostId).integer().not_null())
+                    .col(ColumnDef::new(PostRevisions::UserId).integer().null())
+                    .col(ColumnDef::new(PostRevisions::Title).string().not_null())
+                    .col(ColumnDef::new(PostRevisions::Body).text().not_null())
+                    .col(ColumnDef::new(PostRevisions::Img).string().not_null())
+                    .col(ColumnDef::new(PostRevisions::CategoryId).integer().not_null())
+                    .col(
+                        ColumnDef::new(PostRevisions::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-post_revision-post_id")
+                            .from(PostRevisions::Table, PostRevisions::PostId)
+                            .to(Posts::Table, Posts::Id)
+                            .on_update(ForeignKeyAction::Cascade)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-post_revision-user_id")
+                            .from(PostRevisions::Table, PostRevisions::UserId)
+                            .to(Users::Table, Users::Id)
+                            .on_update(ForeignKeyAction::Cascade)
+                            .on_delete(ForeignKeyAction::SetNull),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-post_revisions-post_id")
+                    .table(PostRevisions::Table)
+                    .col(PostRevisions::PostId)
+                    .to_owned(),
+            )
+            .await?;
+
+        // Every existing post starts its history with its current content
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"
+                INSERT INTO post_revisions (post_id, user_id, title, body, img, category_id)
+                SELECT id, user_id, title, body, img, category_id FROM posts
+                "#,
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(PostRevisions::Table).to_owned())
+            .await?;
+        Ok(())
+    }
+}
+
+#[derive(Iden)]
+enum Users {
+    Table,
+    Id,
+}
+
+#[derive(Iden)]
+enum Posts {
+    Table,
+    Id,
+}
+
+#[derive(Iden)]
+enum PostRevisions {
+    Table,
+    Id,
+    PostId,
+    UserId,
+    Title,
+    Body,
+    Img,
+    CategoryId,
+    CreatedAt,
+}
\ No newline at end of file
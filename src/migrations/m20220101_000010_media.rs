@@ -0,0 +1,143 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Create media table, several rows may share a file since files are named by content
+        manager
+            .create_table(
+                Table::create()
+                    .table(Media::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(Media::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(Media::UserId).integer().null())
+                    .col(ColumnDef::new(Media::Filename).string().not_null())
+                    .col(ColumnDef::new(Media::OriginalName).string().not_null())
+                    .col(ColumnDef::new(Media::Mime).string_len(64).not_null())
+                    .col(ColumnDef::new(Media::Size).integer().not_null())
+                    .col(ColumnDef::new(Media::Width).integer().null())
+                    .col(ColumnDef::new(Media::Height).integer().null())
+                    .col(ColumnDef::new(Media::AltText).string().not_null().default(""))
+                    .col(
+                        ColumnDef::new(Media::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-media-user_id")
+                            .from(Media::Table, Media::UserId)
+                            .to(Users::Table, Users::Id)
+                            .on_update(ForeignKeyAction::Cascade)
+                            .on_delete(ForeignKeyAction::SetNull),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-media-filename")
+                    .table(Media::Table)
+                    .col(Media::Filename)
+                    .to_owned(),
+            )
+            .await?;
+
+        // Create post_media join table
+        manager
+            .create_table(
+                Table::create()
+                    .table(PostMedia::Table)
+                    .if_not_exists()
+                    .col(ColumnDef::new(PostMedia::PostId).integer().not_null())
+                    .col(ColumnDef::new(PostMedia::MediaId).integer().not_null())
+                    .primary_key(Index::create().col(PostMedia::PostId).col(PostMedia::MediaId))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-post_media-post_id")
+                            .from(PostMedia::Table, PostMedia::PostId)
+                            .to(Posts::Table, Posts::Id)
+                            .on_update(ForeignKeyAction::Cascade)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-post_media-media_id")
+                            .from(PostMedia::Table, PostMedia::MediaId)
+                            .to(Media::Table, Media::Id)
+                            .on_update(ForeignKeyAction::Cascade)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-post_media-media_id")
+                    .table(PostMedia::Table)
+                    .col(PostMedia::MediaId)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(PostMedia::Table).to_owned())
+            .await?;
+        manager
+            .drop_table(Table::drop().table(Media::Table).to_owned())
+            .await?;
+        Ok(())
+    }
+}
+
+#[derive(Iden)]
+enum Media {
+    Table,
+    Id,
+    UserId,
+    Filename,
+    OriginalName,
+    Mime,
+    Size,
+    Width,
+    Height,
+    AltText,
+    CreatedAt,
+}
+
+#[derive(Iden)]
+enum PostMedia {
+    Table,
+    PostId,
+    MediaId,
+}
+
+#[derive(Iden)]
+enum Posts {
+    Table,
+    Id,
+}
+
+#[derive(Iden)]
+enum Users {
+    Table,
+    Id,
+}
@@ -0,0 +1,114 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+fn tables() -> [(&'static str, DynIden); 4] {
+    [
+        ("users", Users::Table.into_iden()),
+        ("posts", Posts::Table.into_iden()),
+        ("categories", Categories::Table.into_iden()),
+        ("comments", Comments::Table.into_iden()),
+    ]
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        for (name, table) in tables() {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(table.clone())
+                        .add_column(ColumnDef::new(SoftDelete::DeletedAt).timestamp_with_time_zone().null())
+                        .to_owned(),
+                )
+                .await?;
+
+            // The purge job scans by deletion time
+            manager
+                .create_index(
+                    Index::create()
+                        .name(format!("idx-{}-deleted_at", name))
+                        .table(table)
+                        .col(SoftDelete::DeletedAt)
+                        .to_owned(),
+                )
+                .await?;
+        }
+
+        // Admins manage the trash, promote accounts by setting their role to 'admin'
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Users::Table)
+                    .add_column(
+                        ColumnDef::new(Users::Role)
+                            .string_len(16)
+                            .not_null()
+                            .default("user"),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Users::Table)
+                    .drop_column(Users::Role)
+                    .to_owned(),
+            )
+            .await?;
+
+        for (name, table) in tables() {
+            manager
+                .drop_index(
+                    Index::drop()
+                        .name(format!("idx-{}-deleted_at", name))
+                        .table(table.clone())
+                        .to_owned(),
+                )
+                .await?;
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(table)
+                        .drop_column(SoftDelete::DeletedAt)
+                        .to_owned(),
+                )
+                .await?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Iden)]
+enum Users {
+    Table,
+    Role,
+}
+
+#[derive(Iden)]
+enum Posts {
+    Table,
+}
+
+#[derive(Iden)]
+enum Categories {
+    Table,
+}
+
+#[derive(Iden)]
+enum Comments {
+    Table,
+}
+
+#[derive(Iden)]
+enum SoftDelete {
+    DeletedAt,
+}
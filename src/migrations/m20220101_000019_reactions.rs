@@ -0,0 +1,169 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(PostReactions::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(PostReactions::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(PostReactions::PostId).integer().not_null())
+                    .col(ColumnDef::new(PostReactions::UserId).integer().not_null())
+                    .col(ColumnDef::new(PostReactions::Kind).string_len(16).not_null())
+                    .col(
+                        ColumnDef::new(PostReactions::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-post_reaction-post_id")
+                            .from(PostReactions::Table, PostReactions::PostId)
+                            .to(Posts::Table, Posts::Id)
+                            .on_update(ForeignKeyAction::Cascade)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-post_reaction-user_id")
+                            .from(PostReactions::Table, PostReactions::UserId)
+                            .to(Users::Table, Users::Id)
+                            .on_update(ForeignKeyAction::Cascade)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // One reaction of each kind per reader, it also serves the per post counts
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-post_reactions-post_id-user_id-kind")
+                    .table(PostReactions::Table)
+                    .col(PostReactions::PostId)
+                    .col(PostReactions::UserId)
+                    .col(PostReactions::Kind)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        // value is 1 for an upvote and -1 for a downvote
+        manager
+            .create_table(
+                Table::create()
+                    .table(CommentVotes::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(CommentVotes::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(CommentVotes::CommentId).integer().not_null())
+                    .col(ColumnDef::new(CommentVotes::UserId).integer().not_null())
+                    .col(ColumnDef::new(CommentVotes::Value).small_integer().not_null())
+                    .col(
+                        ColumnDef::new(CommentVotes::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-comment_vote-comment_id")
+                            .from(CommentVotes::Table, CommentVotes::CommentId)
+                            .to(Comments::Table, Comments::Id)
+                            .on_update(ForeignKeyAction::Cascade)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-comment_vote-user_id")
+                            .from(CommentVotes::Table, CommentVotes::UserId)
+                            .to(Users::Table, Users::Id)
+                            .on_update(ForeignKeyAction::Cascade)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // A reader votes once per comment, changing their mind updates the row
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-comment_votes-comment_id-user_id")
+                    .table(CommentVotes::Table)
+                    .col(CommentVotes::CommentId)
+                    .col(CommentVotes::UserId)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(CommentVotes::Table).to_owned())
+            .await?;
+        manager
+            .drop_table(Table::drop().table(PostReactions::Table).to_owned())
+            .await?;
+        Ok(())
+    }
+}
+
+#[derive(Iden)]
+enum Users {
+    Table,
+    Id,
+}
+
+#[derive(Iden)]
+enum Posts {
+    Table,
+    Id,
+}
+
+#[derive(Iden)]
+enum Comments {
+    Table,
+    Id,
+}
+
+#[derive(Iden)]
+enum PostReactions {
+    Table,
+    Id,
+    PostId,
+    UserId,
+    Kind,
+    CreatedAt,
+}
+
+#[derive(Iden)]
+enum CommentVotes {
+    Table,
+    Id,
+    CommentId,
+    UserId,
+    Value,
+    CreatedAt,
+}
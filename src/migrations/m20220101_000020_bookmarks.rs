@@ -0,0 +1,113 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Bookmarks::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(Bookmarks::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(Bookmarks::UserId).integer().not_null())
+                    .col(ColumnDef::new(Bookmarks::PostId).integer().not_null())
+                    .col(ColumnDef::new(Bookmarks::Folder).string_len(64).null())
+                    .col(ColumnDef::new(Bookmarks::Note).text().null())
+                    .col(
+                        ColumnDef::new(Bookmarks::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(
+                        ColumnDef::new(Bookmarks::UpdatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-bookmark-user_id")
+                            .from(Bookmarks::Table, Bookmarks::UserId)
+                            .to(Users::Table, Users::Id)
+                            .on_update(ForeignKeyAction::Cascade)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-bookmark-post_id")
+                            .from(Bookmarks::Table, Bookmarks::PostId)
+                            .to(Posts::Table, Posts::Id)
+                            .on_update(ForeignKeyAction::Cascade)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // A post is saved once per reader, saving it again moves it or changes the note
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-bookmarks-user_id-post_id")
+                    .table(Bookmarks::Table)
+                    .col(Bookmarks::UserId)
+                    .col(Bookmarks::PostId)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-bookmarks-user_id-folder")
+                    .table(Bookmarks::Table)
+                    .col(Bookmarks::UserId)
+                    .col(Bookmarks::Folder)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(Bookmarks::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(Iden)]
+enum Users {
+    Table,
+    Id,
+}
+
+#[derive(Iden)]
+enum Posts {
+    Table,
+    Id,
+}
+
+#[derive(Iden)]
+enum Bookmarks {
+    Table,
+    Id,
+    UserId,
+    PostId,
+    Folder,
+    Note,
+    CreatedAt,
+    UpdatedAt,
+}
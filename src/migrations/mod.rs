@@ -1,12 +1,58 @@
 pub use sea_orm_migration::prelude::*;
 
 pub mod m20220101_000001_create_table;
+pub mod m20220101_000002_slug_history;
+pub mod m20220101_000003_post_status;
+pub mod m20220101_000004_post_revisions;
+pub mod m20220101_000005_tags;
+pub mod m20220101_000006_post_body_html;
+pub mod m20220101_000007_refresh_body_html;
+pub mod m20220101_000008_highlight_body_html;
+pub mod m20220101_000009_image_variants;
+pub mod m20220101_000010_media;
+pub mod m20220101_000011_timestamps;
+pub mod m20220101_000012_soft_delete;
+pub mod m20220101_000013_restrict_post_category;
+pub mod m20220101_000014_category_parent;
+pub mod m20220101_000015_category_details;
+pub mod m20220101_000016_comment_parent;
+pub mod m20220101_000017_comment_moderation;
+pub mod m20220101_000018_comment_revisions;
+pub mod m20220101_000019_reactions;
+pub mod m20220101_000020_bookmarks;
+pub mod m20220101_000021_follows;
+pub mod m20220101_000022_notifications;
+pub mod m20220101_000023_post_revision_format_tags;
 
 pub struct Migrator;
 
 #[async_trait::async_trait]
 impl MigratorTrait for Migrator {
     fn migrations() -> Vec<Box<dyn MigrationTrait>> {
-        vec![Box::new(m20220101_000001_create_table::Migration)]
+        vec![
+            Box::new(m20220101_000001_create_table::Migration),
+            Box::new(m20220101_000002_slug_history::Migration),
+            Box::new(m20220101_000003_post_status::Migration),
+            Box::new(m20220101_000004_post_revisions::Migration),
+            Box::new(m20220101_000005_tags::Migration),
+            Box::new(m20220101_000006_post_body_html::Migration),
+            Box::new(m20220101_000007_refresh_body_html::Migration),
+            Box::new(m20220101_000008_highlight_body_html::Migration),
+            Box::new(m20220101_000009_image_variants::Migration),
+            Box::new(m20220101_000010_media::Migration),
+            Box::new(m20220101_000011_timestamps::Migration),
+            Box::new(m20220101_000012_soft_delete::Migration),
+            Box::new(m20220101_000013_restrict_post_category::Migration),
+            Box::new(m20220101_000014_category_parent::Migration),
+            Box::new(m20220101_000015_category_details::Migration),
+            Box::new(m20220101_000016_comment_parent::Migration),
+            Box::new(m20220101_000017_comment_moderation::Migration),
+            Box::new(m20220101_000018_comment_revisions::Migration),
+            Box::new(m20220101_000019_reactions::Migration),
+            Box::new(m20220101_000020_bookmarks::Migration),
+            Box::new(m20220101_000021_follows::Migration),
+            Box::new(m20220101_000022_notifications::Migration),
+            Box::new(m20220101_000023_post_revision_format_tags::Migration),
+        ]
     }
-}
+}
\ No newline at end of file
@@ -0,0 +1,121 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use sea_orm::sea_query::OnConflict;
+use sea_orm::{ColumnTrait, DatabaseConnection, DbErr, EntityTrait, PaginatorTrait, QueryFilter, QueryOrder, QuerySelect, Set};
+
+use crate::abstract_trait::BookmarkRepositoryTrait;
+use crate::entities::{bookmarks, posts, prelude::{Bookmarks, Posts}, sea_orm_active_enums::PostStatus};
+
+pub struct BookmarkRepository {
+    db_pool: DatabaseConnection,
+}
+
+impl BookmarkRepository {
+    pub fn new(db_pool: DatabaseConnection) -> Self {
+        Self { db_pool }
+    }
+}
+
+#[async_trait]
+impl BookmarkRepositoryTrait for BookmarkRepository {
+    async fn save(&self, user_id: i32, post_id: i32, folder: Option<String>, note: Option<String>) -> Result<bookmarks::Model, DbErr> {
+        let now = Utc::now().fixed_offset();
+        let bookmark = bookmarks::ActiveModel {
+            user_id: Set(user_id),
+            post_id: Set(post_id),
+            folder: Set(folder),
+            note: Set(note),
+            created_at: Set(now),
+            updated_at: Set(now),
+            ..Default::default()
+        };
+
+        // Saving a bookmarked post again keeps its position in the list and replaces folder and note
+        Bookmarks::insert(bookmark)
+            .on_conflict(
+                OnConflict::columns([bookmarks::Column::UserId, bookmarks::Column::PostId])
+                    .update_columns([bookmarks::Column::Folder, bookmarks::Column::Note, bookmarks::Column::UpdatedAt])
+                    .to_owned(),
+            )
+            .exec_with_returning(&self.db_pool)
+            .await
+    }
+
+    async fn delete(&self, user_id: i32, post_id: i32) -> Result<u64, DbErr> {
+        let result = Bookmarks::delete_many()
+            .filter(bookmarks::Column::UserId.eq(user_id))
+            .filter(bookmarks::Column::PostId.eq(post_id))
+            .exec(&self.db_pool)
+            .await?;
+
+        Ok(result.rows_affected)
+    }
+
+    async fn find_page(
+        &self,
+        user_id: i32,
+        folder: Option<&str>,
+        page: u64,
+        per_page: u64,
+    ) -> Result<(Vec<(bookmarks::Model, posts::Model)>, u64), DbErr> {
+        let mut query = Bookmarks::find()
+            .find_also_related(Posts)
+            .filter(bookmarks::Column::UserId.eq(user_id))
+            .filter(posts::Column::DeletedAt.is_null())
+            .filter(posts::Column::Status.eq(PostStatus::Published));
+
+        if let Some(folder) = folder {
+            query = query.filter(bookmarks::Column::Folder.eq(folder));
+        }
+
+        let paginator = query
+            .order_by_desc(bookmarks::Column::CreatedAt)
+            .order_by_desc(bookmarks::Column::Id)
+            .paginate(&self.db_pool, per_page);
+
+        let total = paginator.num_items().await?;
+
+        // Pages past the end are empty, which also keeps SeaORM's page * per_page from overflowing
+        if (page - 1).checked_mul(per_page).is_none_or(|offset| offset >= total) {
+            return Ok((Vec::new(), total));
+        }
+
+        let bookmarks = paginator
+            .fetch_page(page - 1)
+            .await?
+            .into_iter()
+            .filter_map(|(bookmark, post)| post.map(|post| (bookmark, post)))
+            .collect();
+
+        Ok((bookmarks, total))
+    }
+
+    async fn find_folders(&self, user_id: i32) -> Result<Vec<(String, i64)>, DbErr> {
+        Bookmarks::find()
+            .select_only()
+            .column(bookmarks::Column::Folder)
+            .column_as(bookmarks::Column::Id.count(), "count")
+            .filter(bookmarks::Column::UserId.eq(user_id))
+            .filter(bookmarks::Column::Folder.is_not_null())
+            .group_by(bookmarks::Column::Folder)
+            .order_by_asc(bookmarks::Column::Folder)
+            .into_tuple()
+            .all(&self.db_pool)
+            .await
+    }
+
+    async fn find_bookmarked(&self, post_ids: &[i32], user_id: i32) -> Result<Vec<i32>, DbErr> {
+        if post_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        Bookmarks::find()
+            .select_only()
+            .column(bookmarks::Column::PostId)
+            .filter(bookmarks::Column::PostId.is_in(post_ids.iter().copied()))
+            .filter(bookmarks::Column::UserId.eq(user_id))
+            .into_tuple()
+            .all(&self.db_pool)
+            .await
+    }
+}
@@ -1,9 +1,16 @@
 use async_trait::async_trait;
-use sea_orm::{ActiveModelTrait, DatabaseConnection, DbErr, EntityTrait,  Set};
+use chrono::{DateTime, Utc};
+use sea_orm::sea_query::{Expr, Query};
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, Condition, ConnectionTrait, DatabaseConnection, DbBackend, DbErr,
+    EntityTrait, FromQueryResult, JoinType, PaginatorTrait, QueryFilter, QueryOrder, QueryResult,
+    QuerySelect, RelationTrait, Select, Set, Statement, TransactionTrait,
+};
 
-use crate::domain::{CreateCategoryRequest, UpdateCategoryRequest};
-use crate::entities::{categories, prelude::Categories,};
+use crate::domain::{CategoryDeleteStrategy, CategoryMergeResponse, CreateCategoryRequest, MergeCategoriesRequest, UpdateCategoryRequest};
+use crate::entities::{categories, category_slug_history, posts, prelude::{Categories, CategorySlugHistory, Posts}, sea_orm_active_enums::PostStatus};
 use crate::abstract_trait::CategoryRepositoryTrait;
+use crate::utils::generate_slug;
 
 
 
@@ -11,30 +18,161 @@ pub struct CategoryRepository{
     db_pool: DatabaseConnection
 }
 
+struct CategoryPostCount {
+    category: categories::Model,
+    post_count: i64,
+}
+
+impl FromQueryResult for CategoryPostCount {
+    fn from_query_result(row: &QueryResult, pre: &str) -> Result<Self, DbErr> {
+        Ok(Self {
+            category: categories::Model::from_query_result(row, pre)?,
+            post_count: row.try_get(pre, "post_count")?,
+        })
+    }
+}
+
 
 impl CategoryRepository{
     pub fn new(db_pool: DatabaseConnection) -> Self{
         Self { db_pool }
     }
+
+    // Counts published posts with a single grouped join instead of a query per category
+    fn with_post_count(select: Select<Categories>) -> Select<Categories> {
+        select
+            .column_as(posts::Column::Id.count(), "post_count")
+            .join(
+                JoinType::LeftJoin,
+                categories::Relation::Posts.def().on_condition(|_, right| {
+                    Condition::all()
+                        .add(Expr::col((right.clone(), posts::Column::Status)).eq(PostStatus::Published))
+                        .add(Expr::col((right, posts::Column::DeletedAt)).is_null())
+                }),
+            )
+            .group_by(categories::Column::Id)
+    }
+
+    async fn unique_slug<C: ConnectionTrait>(
+        db: &C,
+        name: &str,
+        category_id: Option<i32>,
+    ) -> Result<String, DbErr> {
+        let base = match generate_slug(name) {
+            slug if slug.is_empty() => "category".to_string(),
+            slug => slug,
+        };
+
+        let mut candidate = base.clone();
+        let mut suffix = 1;
+
+        loop {
+            let mut query = Categories::find().filter(categories::Column::Slug.eq(candidate.as_str()));
+
+            if let Some(id) = category_id {
+                query = query.filter(categories::Column::Id.ne(id));
+            }
+
+            if query.count(db).await? == 0 {
+                return Ok(candidate);
+            }
+
+            suffix += 1;
+            candidate = format!("{}-{}", base, suffix);
+        }
+    }
+
+    // The parent must exist and must not sit below the category itself, which would close a cycle
+    async fn check_parent<C: ConnectionTrait>(
+        db: &C,
+        category_id: Option<i32>,
+        parent_id: i32,
+    ) -> Result<(), DbErr> {
+        let mut ancestor = Categories::find_active_by_id(parent_id)
+            .one(db)
+            .await?
+            .ok_or(DbErr::Custom("Parent category not found".to_string()))?;
+
+        let Some(category_id) = category_id else {
+            return Ok(());
+        };
+
+        loop {
+            if ancestor.id == category_id {
+                return Err(DbErr::Custom("A category cannot be moved below itself".to_string()));
+            }
+
+            let Some(next) = ancestor.parent_id else {
+                return Ok(());
+            };
+
+            match Categories::find_by_id(next).one(db).await? {
+                Some(parent) => ancestor = parent,
+                None => return Ok(()),
+            }
+        }
+    }
 }
 
 #[async_trait]
 impl CategoryRepositoryTrait for CategoryRepository {
-    async fn find_all(&self) -> Result<Vec<categories::Model>, DbErr> {
-        Categories::find()
+    async fn find_all(&self) -> Result<Vec<(categories::Model, i64)>, DbErr> {
+        let rows = Self::with_post_count(Categories::find_active())
+            .order_by_asc(categories::Column::DisplayOrder)
+            .order_by_asc(categories::Column::Name)
+            .into_model::<CategoryPostCount>()
             .all(&self.db_pool)
-            .await
+            .await?;
+
+        Ok(rows.into_iter().map(|row| (row.category, row.post_count)).collect())
     }
 
-    async fn find_by_id(&self, id: i32) -> Result<Option<categories::Model>, DbErr> {
-        Categories::find_by_id(id)
+    async fn find_by_id(&self, id: i32) -> Result<Option<(categories::Model, i64)>, DbErr> {
+        let row = Self::with_post_count(Categories::find_active_by_id(id))
+            .into_model::<CategoryPostCount>()
             .one(&self.db_pool)
-            .await
+            .await?;
+
+        Ok(row.map(|row| (row.category, row.post_count)))
+    }
+
+    async fn find_by_slug(&self, slug: &str) -> Result<Option<(categories::Model, i64)>, DbErr> {
+        let row = Self::with_post_count(Categories::find_active().filter(categories::Column::Slug.eq(slug)))
+            .into_model::<CategoryPostCount>()
+            .one(&self.db_pool)
+            .await?;
+
+        Ok(row.map(|row| (row.category, row.post_count)))
+    }
+
+    async fn find_slug_redirect(&self, slug: &str) -> Result<Option<String>, DbErr> {
+        let history = CategorySlugHistory::find()
+            .filter(category_slug_history::Column::Slug.eq(slug))
+            .find_also_related(Categories)
+            .one(&self.db_pool)
+            .await?;
+
+        Ok(history
+            .and_then(|(_, category)| category)
+            .filter(|category| category.deleted_at.is_none())
+            .map(|category| category.slug))
     }
 
     async fn create(&self, input: &CreateCategoryRequest) -> Result<categories::Model, DbErr> {
+        if let Some(parent_id) = input.parent_id {
+            Self::check_parent(&self.db_pool, None, parent_id).await?;
+        }
+
+        let slug = Self::unique_slug(&self.db_pool, &input.name, None).await?;
+
         let category = categories::ActiveModel {
             name: Set(input.name.clone()),
+            slug: Set(slug),
+            parent_id: Set(input.parent_id),
+            description: Set(input.description.clone().unwrap_or_default()),
+            display_order: Set(input.display_order.unwrap_or(0)),
+            cover_img: Set(input.cover_img.clone()),
+            comment_moderation: Set(input.comment_moderation),
             ..Default::default() 
         };
 
@@ -47,26 +185,292 @@ impl CategoryRepositoryTrait for CategoryRepository {
             None => return Err(DbErr::Custom("Category ID is required".to_string())), 
         };
 
-        let mut category: categories::ActiveModel = Categories::find_by_id(id)
-            .one(&self.db_pool)
+        let txn = self.db_pool.begin().await?;
+
+        let existing = Categories::find_active_by_id(id)
+            .one(&txn)
             .await?
-            .ok_or(DbErr::Custom("Category not found".to_string()))?
-            .into();
+            .ok_or(DbErr::RecordNotFound("Category not found".to_string()))?;
+
+        let old_slug = existing.slug.clone();
+        let mut category: categories::ActiveModel = existing.into();
 
         if let Some(name) = &input.name {
+            let new_slug = Self::unique_slug(&txn, name, Some(id)).await?;
+
+            if new_slug != old_slug {
+                CategorySlugHistory::delete_many()
+                    .filter(category_slug_history::Column::Slug.is_in([old_slug.as_str(), new_slug.as_str()]))
+                    .exec(&txn)
+                    .await?;
+
+                category_slug_history::ActiveModel {
+                    category_id: Set(id),
+                    slug: Set(old_slug),
+                    ..Default::default()
+                }
+                .insert(&txn)
+                .await?;
+            }
+
             category.name = Set(name.clone());
+            category.slug = Set(new_slug);
         }
 
-        category.update(&self.db_pool).await
+        if let Some(parent_id) = input.parent_id {
+            if let Some(parent_id) = parent_id {
+                Self::check_parent(&txn, Some(id), parent_id).await?;
+            }
+
+            category.parent_id = Set(parent_id);
+        }
+
+        if let Some(description) = &input.description {
+            category.description = Set(description.clone());
+        }
+
+        if let Some(display_order) = input.display_order {
+            category.display_order = Set(display_order);
+        }
+
+        if let Some(cover_img) = &input.cover_img {
+            category.cover_img = Set(cover_img.clone());
+        }
+
+        if let Some(comment_moderation) = input.comment_moderation {
+            category.comment_moderation = Set(comment_moderation);
+        }
+
+        let category = category.update(&txn).await?;
+
+        txn.commit().await?;
+
+        Ok(category)
+    }
+
+    async fn delete(&self, id: i32, strategy: CategoryDeleteStrategy, target_id: Option<i32>) -> Result<u64, DbErr> {
+        let txn = self.db_pool.begin().await?;
+
+        let mut category: categories::ActiveModel = Categories::find_active_by_id(id)
+            .one(&txn)
+            .await?
+            .ok_or(DbErr::RecordNotFound("Category not found".to_string()))?
+            .into();
+
+        let now: DateTime<Utc> = Utc::now();
+        let posts_in_category = Posts::find_active().filter(posts::Column::CategoryId.eq(id));
+
+        let affected = match strategy {
+            CategoryDeleteStrategy::Refuse => {
+                let count = posts_in_category.count(&txn).await?;
+
+                if count > 0 {
+                    return Err(DbErr::Custom(format!(
+                        "Category still has {} posts, reassign or delete them first",
+                        count
+                    )));
+                }
+
+                0
+            }
+            CategoryDeleteStrategy::Reassign => {
+                let target_id = target_id
+                    .filter(|target_id| *target_id != id)
+                    .ok_or(DbErr::Custom("A different target category is required".to_string()))?;
+
+                Categories::find_active_by_id(target_id)
+                    .one(&txn)
+                    .await?
+                    .ok_or(DbErr::Custom("Target category not found".to_string()))?;
+
+                // Trashed posts move too, otherwise they would keep the category from ever being purged
+                Posts::update_many()
+                    .col_expr(posts::Column::CategoryId, Expr::value(target_id))
+                    .col_expr(posts::Column::UpdatedAt, Expr::value(now))
+                    .filter(posts::Column::CategoryId.eq(id))
+                    .exec(&txn)
+                    .await?
+                    .rows_affected
+            }
+            CategoryDeleteStrategy::DeletePosts => {
+                Posts::update_many()
+                    .col_expr(posts::Column::DeletedAt, Expr::value(now))
+                    .filter(posts::Column::CategoryId.eq(id))
+                    .filter(posts::Column::DeletedAt.is_null())
+                    .exec(&txn)
+                    .await?
+                    .rows_affected
+            }
+        };
+
+        category.deleted_at = Set(Some(now.into()));
+        category.update(&txn).await?;
+
+        txn.commit().await?;
+
+        Ok(affected)
+    }
+
+    async fn merge(&self, input: &MergeCategoriesRequest) -> Result<CategoryMergeResponse, DbErr> {
+        let mut source_ids = input.source_ids.clone();
+        source_ids.sort_unstable();
+        source_ids.dedup();
+
+        if source_ids.is_empty() {
+            return Err(DbErr::Custom("At least one source category is required".to_string()));
+        }
+
+        if source_ids.contains(&input.target_id) {
+            return Err(DbErr::Custom("The target category cannot also be a source".to_string()));
+        }
+
+        let txn = self.db_pool.begin().await?;
+
+        let target = Categories::find_active_by_id(input.target_id)
+            .one(&txn)
+            .await?
+            .ok_or(DbErr::RecordNotFound("Target category not found".to_string()))?;
+
+        let sources = Categories::find_active()
+            .filter(categories::Column::Id.is_in(source_ids.clone()))
+            .all(&txn)
+            .await?;
+
+        if sources.len() != source_ids.len() {
+            return Err(DbErr::RecordNotFound("Source category not found".to_string()));
+        }
+
+        let moved_posts = Posts::update_many()
+            .col_expr(posts::Column::CategoryId, Expr::value(target.id))
+            .col_expr(posts::Column::UpdatedAt, Expr::value(Utc::now()))
+            .filter(posts::Column::CategoryId.is_in(source_ids.clone()))
+            .exec(&txn)
+            .await?
+            .rows_affected;
+
+        // The target may sit below one of the sources, lift it to the closest ancestor that survives
+        let mut parent_id = target.parent_id;
+        while let Some(id) = parent_id.filter(|id| source_ids.contains(id)) {
+            parent_id = sources.iter().find(|source| source.id == id).and_then(|source| source.parent_id);
+        }
+
+        if parent_id != target.parent_id {
+            let mut target: categories::ActiveModel = target.clone().into();
+            target.parent_id = Set(parent_id);
+            target.update(&txn).await?;
+        }
+
+        let moved_children = Categories::update_many()
+            .col_expr(categories::Column::ParentId, Expr::value(target.id))
+            .filter(categories::Column::ParentId.is_in(source_ids.clone()))
+            .filter(categories::Column::Id.ne(target.id))
+            .exec(&txn)
+            .await?
+            .rows_affected;
+
+        // Old slugs of the sources keep redirecting, now to the target
+        let mut redirected_slugs: Vec<String> = CategorySlugHistory::find()
+            .filter(category_slug_history::Column::CategoryId.is_in(source_ids.clone()))
+            .all(&txn)
+            .await?
+            .into_iter()
+            .map(|history| history.slug)
+            .collect();
+
+        CategorySlugHistory::update_many()
+            .col_expr(category_slug_history::Column::CategoryId, Expr::value(target.id))
+            .filter(category_slug_history::Column::CategoryId.is_in(source_ids.clone()))
+            .exec(&txn)
+            .await?;
+
+        let source_slugs: Vec<String> = sources.iter().map(|source| source.slug.clone()).collect();
+
+        CategorySlugHistory::delete_many()
+            .filter(category_slug_history::Column::Slug.is_in(source_slugs.clone()))
+            .exec(&txn)
+            .await?;
+
+        CategorySlugHistory::insert_many(source_slugs.iter().map(|slug| category_slug_history::ActiveModel {
+            category_id: Set(target.id),
+            slug: Set(slug.clone()),
+            ..Default::default()
+        }))
+        .exec_without_returning(&txn)
+        .await?;
+
+        redirected_slugs.extend(source_slugs);
+        redirected_slugs.sort();
+
+        // Followers of a source follow the target instead, deleting the sources would cascade them away
+        for source_id in &source_ids {
+            txn.execute(Statement::from_sql_and_values(
+                DbBackend::Postgres,
+                r#"
+                INSERT INTO category_follows (user_id, category_id, created_at)
+                SELECT user_id, $1, created_at FROM category_follows WHERE category_id = $2
+                ON CONFLICT DO NOTHING
+                "#,
+                [target.id.into(), (*source_id).into()],
+            ))
+            .await?;
+        }
+
+        // A merged category has nothing left worth restoring, so it skips the trash
+        Categories::delete_many()
+            .filter(categories::Column::Id.is_in(source_ids.clone()))
+            .exec(&txn)
+            .await?;
+
+        if input.dry_run {
+            txn.rollback().await?;
+        } else {
+            txn.commit().await?;
+        }
+
+        Ok(CategoryMergeResponse {
+            target_id: target.id,
+            source_ids,
+            dry_run: input.dry_run,
+            moved_posts,
+            moved_children,
+            redirected_slugs,
+        })
+    }
+
+    async fn find_trashed(&self) -> Result<Vec<(categories::Model, i64)>, DbErr> {
+        let rows = Self::with_post_count(Categories::find().filter(categories::Column::DeletedAt.is_not_null()))
+            .order_by_desc(categories::Column::DeletedAt)
+            .into_model::<CategoryPostCount>()
+            .all(&self.db_pool)
+            .await?;
+
+        Ok(rows.into_iter().map(|row| (row.category, row.post_count)).collect())
     }
 
-    async fn delete(&self, id: i32) -> Result<(), DbErr> {
-        let category: categories::ActiveModel = Categories::find_by_id(id)
+    async fn restore(&self, id: i32) -> Result<categories::Model, DbErr> {
+        let mut category: categories::ActiveModel = Categories::find_by_id(id)
+            .filter(categories::Column::DeletedAt.is_not_null())
             .one(&self.db_pool)
             .await?
-            .ok_or(DbErr::Custom("Category not found".to_string()))?
+            .ok_or(DbErr::RecordNotFound("Category not found in trash".to_string()))?
             .into();
 
-        category.delete(&self.db_pool).await.map(|_| ())
+        category.deleted_at = Set(None);
+        category.update(&self.db_pool).await
+    }
+
+    async fn purge_trashed(&self, deleted_before: DateTime<Utc>) -> Result<u64, DbErr> {
+        // Posts restrict deleting their category, so keep it while any post, even a trashed one, still points at it
+        let result = Categories::delete_many()
+            .filter(categories::Column::DeletedAt.lt(deleted_before))
+            .filter(
+                categories::Column::Id.not_in_subquery(
+                    Query::select().column(posts::Column::CategoryId).from(Posts).to_owned(),
+                ),
+            )
+            .exec(&self.db_pool)
+            .await?;
+
+        Ok(result.rows_affected)
     }
 }
@@ -1,14 +1,57 @@
 use async_trait::async_trait;
-use sea_orm::{ActiveModelTrait, DatabaseConnection, DbErr, EntityTrait,  Set};
+use chrono::{DateTime, Utc};
+use sea_orm::sea_query::{Expr, Query};
+use sea_orm::{ActiveModelTrait, ColumnTrait, ConnectionTrait, DatabaseConnection, DbBackend, DbErr, EntityTrait, QueryFilter, QueryOrder, QuerySelect, Set, Statement, TransactionTrait};
 
-use crate::domain::{CreateCommentRequest, UpdateCommentRequest};
-use crate::entities::{comments, prelude::Comments};
+use crate::domain::{CommentSort, CreateCommentRequest, UpdateCommentRequest};
+use crate::entities::{comment_revisions, comments, prelude::{CommentRevisions, Comments}, sea_orm_active_enums::CommentStatus};
 use crate::abstract_trait::CommentRepositoryTrait;
 
 pub struct CommentRepository {
     db_pool: DatabaseConnection,
 }
 
+// Threads of a post that have something to show, with the values they are sorted by. `closure`
+// pairs every comment with itself and each reply below it, `kept` holds the comments with a
+// visible comment in their subtree, the same rule `CommentThreadResponse::build` applies
+// with the comment service's `visible_to`. $1 is the post, $2 the viewer
+const THREAD_CTES: &str = r#"
+    WITH RECURSIVE closure (ancestor_id, id) AS (
+        SELECT id, id FROM comments WHERE id_post_comment = $1
+        UNION ALL
+        SELECT closure.ancestor_id, comments.id
+        FROM closure JOIN comments ON comments.parent_id = closure.id
+    ),
+    kept AS (
+        SELECT DISTINCT closure.ancestor_id AS id
+        FROM closure JOIN comments ON comments.id = closure.id
+        WHERE comments.deleted_at IS NULL
+            AND (comments.status = 'approved' OR (comments.status = 'pending' AND comments.user_id = $2))
+    ),
+    threads AS (
+        SELECT
+            roots.id,
+            roots.created_at,
+            (
+                SELECT COUNT(*) FROM closure JOIN kept ON kept.id = closure.id
+                WHERE closure.ancestor_id = roots.id AND closure.id <> roots.id
+            ) AS reply_count,
+            (SELECT COALESCE(SUM(value), 0) FROM comment_votes WHERE comment_id = roots.id) AS score
+        FROM comments roots JOIN kept ON kept.id = roots.id
+        WHERE roots.id_post_comment = $1 AND roots.parent_id IS NULL
+    )
+"#;
+
+// Ties keep the oldest first order the in-memory sort falls back to
+fn thread_order(sort: CommentSort) -> &'static str {
+    match sort {
+        CommentSort::Oldest => "created_at ASC, id ASC",
+        CommentSort::Newest => "created_at DESC, id DESC",
+        CommentSort::Top => "reply_count DESC, created_at ASC, id ASC",
+        CommentSort::Score => "score DESC, created_at ASC, id ASC",
+    }
+}
+
 impl CommentRepository {
     pub fn new(db_pool: DatabaseConnection) -> Self {
         Self { db_pool }
@@ -18,48 +61,236 @@ impl CommentRepository {
 #[async_trait]
 impl CommentRepositoryTrait for CommentRepository {
     async fn find_all(&self) -> Result<Vec<comments::Model>, DbErr> {
-        Comments::find()
+        Comments::find_active()
             .all(&self.db_pool)
             .await
     }
 
     async fn find_by_id(&self, id: i32) -> Result<Option<comments::Model>, DbErr> {
-        Comments::find_by_id(id)
+        Comments::find_active_by_id(id)
             .one(&self.db_pool)
             .await
     }
 
-    async fn create(&self, input: &CreateCommentRequest) -> Result<comments::Model, DbErr> {
+    async fn find_by_ids(&self, ids: &[i32]) -> Result<Vec<comments::Model>, DbErr> {
+        if ids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        Comments::find_active()
+            .filter(comments::Column::Id.is_in(ids.iter().copied()))
+            .all(&self.db_pool)
+            .await
+    }
+
+    async fn find_by_post(&self, post_id: i32) -> Result<Vec<comments::Model>, DbErr> {
+        // Trashed comments are included so their replies can hang off a placeholder
+        Comments::find()
+            .filter(comments::Column::IdPostComment.eq(post_id))
+            .order_by_asc(comments::Column::CreatedAt)
+            .order_by_asc(comments::Column::Id)
+            .all(&self.db_pool)
+            .await
+    }
+
+    async fn count_threads(&self, post_id: i32, viewer_id: Option<i32>) -> Result<u64, DbErr> {
+        let row = self
+            .db_pool
+            .query_one(Statement::from_sql_and_values(
+                DbBackend::Postgres,
+                format!("{} SELECT COUNT(*) AS total FROM threads", THREAD_CTES),
+                [post_id.into(), viewer_id.into()],
+            ))
+            .await?;
+
+        let total: i64 = match row {
+            Some(row) => row.try_get("", "total")?,
+            None => 0,
+        };
+
+        Ok(total as u64)
+    }
+
+    // Every comment of the threads on the page, hidden ones included so they can become placeholders
+    async fn find_thread_page(
+        &self,
+        post_id: i32,
+        viewer_id: Option<i32>,
+        sort: CommentSort,
+        offset: u64,
+        limit: u64,
+    ) -> Result<Vec<comments::Model>, DbErr> {
+        let sql = format!(
+            "{} , page AS (SELECT id FROM threads ORDER BY {} LIMIT $3 OFFSET $4)
+            SELECT comments.* FROM comments
+            JOIN closure ON closure.id = comments.id
+            JOIN page ON page.id = closure.ancestor_id",
+            THREAD_CTES,
+            thread_order(sort),
+        );
+
+        Comments::find()
+            .from_raw_sql(Statement::from_sql_and_values(
+                DbBackend::Postgres,
+                sql,
+                [
+                    post_id.into(),
+                    viewer_id.into(),
+                    i64::try_from(limit).unwrap_or(i64::MAX).into(),
+                    i64::try_from(offset).unwrap_or(i64::MAX).into(),
+                ],
+            ))
+            .all(&self.db_pool)
+            .await
+    }
+
+    async fn count_by_posts(&self, post_ids: &[i32]) -> Result<Vec<(i32, i64)>, DbErr> {
+        if post_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        Comments::find_active()
+            .select_only()
+            .column(comments::Column::IdPostComment)
+            .column_as(comments::Column::Id.count(), "comment_count")
+            .filter(comments::Column::IdPostComment.is_in(post_ids.iter().copied()))
+            .filter(comments::Column::Status.eq(CommentStatus::Approved))
+            .group_by(comments::Column::IdPostComment)
+            .into_tuple()
+            .all(&self.db_pool)
+            .await
+    }
+
+    async fn find_by_status(&self, status: CommentStatus) -> Result<Vec<comments::Model>, DbErr> {
+        // Oldest first so the queue is worked through in the order comments arrived
+        Comments::find_active()
+            .filter(comments::Column::Status.eq(status))
+            .order_by_asc(comments::Column::CreatedAt)
+            .order_by_asc(comments::Column::Id)
+            .all(&self.db_pool)
+            .await
+    }
+
+    async fn set_status(&self, ids: &[i32], status: CommentStatus) -> Result<u64, DbErr> {
+        let result = Comments::update_many()
+            .col_expr(comments::Column::Status, Expr::value(status))
+            .col_expr(comments::Column::UpdatedAt, Expr::value(Utc::now()))
+            .filter(comments::Column::Id.is_in(ids.iter().copied()))
+            .filter(comments::Column::DeletedAt.is_null())
+            .filter(comments::Column::Status.ne(status))
+            .exec(&self.db_pool)
+            .await?;
+
+        Ok(result.rows_affected)
+    }
+
+    async fn create(&self, input: &CreateCommentRequest, user_id: Option<i32>, status: CommentStatus) -> Result<comments::Model, DbErr> {
         let comment = comments::ActiveModel {
             id_post_comment: Set(input.id_post_comment),
             user_name_comment: Set(input.user_name_comment.clone()),
             comment: Set(input.comment.clone()),
+            parent_id: Set(input.parent_id),
+            user_id: Set(user_id),
+            status: Set(status),
             ..Default::default()
         };
 
         comment.insert(&self.db_pool).await
     }
 
-    async fn update(&self, input: &UpdateCommentRequest) -> Result<comments::Model, DbErr> {
-        let mut comment: comments::ActiveModel = Comments::find_by_id(input.id_post_comment)
-            .one(&self.db_pool)
+    async fn update(&self, id: i32, input: &UpdateCommentRequest, editor_id: Option<i32>, status: Option<CommentStatus>) -> Result<comments::Model, DbErr> {
+        let txn = self.db_pool.begin().await?;
+
+        let existing = Comments::find_active_by_id(id)
+            .one(&txn)
             .await?
-            .ok_or(DbErr::Custom("Comment not found".to_string()))?
-            .into();
+            .ok_or(DbErr::RecordNotFound("Comment not found".to_string()))?;
 
-        comment.user_name_comment = Set(input.user_name_comment.clone());
+        comment_revisions::ActiveModel {
+            comment_id: Set(existing.id),
+            user_id: Set(editor_id),
+            comment: Set(existing.comment.clone()),
+            ..Default::default()
+        }
+        .insert(&txn)
+        .await?;
+
+        let mut comment: comments::ActiveModel = existing.into();
         comment.comment = Set(input.comment.clone());
+        comment.edited_at = Set(Some(Utc::now().into()));
+        if let Some(status) = status {
+            comment.status = Set(status);
+        }
+        let comment = comment.update(&txn).await?;
 
-        comment.update(&self.db_pool).await
+        txn.commit().await?;
+
+        Ok(comment)
+    }
+
+    async fn find_revisions(&self, id: i32) -> Result<Vec<comment_revisions::Model>, DbErr> {
+        CommentRevisions::find()
+            .filter(comment_revisions::Column::CommentId.eq(id))
+            .order_by_desc(comment_revisions::Column::CreatedAt)
+            .order_by_desc(comment_revisions::Column::Id)
+            .all(&self.db_pool)
+            .await
     }
 
     async fn delete(&self, id: i32) -> Result<(), DbErr> {
-        let comment: comments::ActiveModel = Comments::find_by_id(id)
+        let mut comment: comments::ActiveModel = Comments::find_active_by_id(id)
             .one(&self.db_pool)
             .await?
-            .ok_or(DbErr::Custom("Comment not found".to_string()))?
+            .ok_or(DbErr::RecordNotFound("Comment not found".to_string()))?
             .into();
 
-        comment.delete(&self.db_pool).await.map(|_| ())
+        comment.deleted_at = Set(Some(Utc::now().into()));
+        comment.update(&self.db_pool).await.map(|_| ())
+    }
+
+    async fn find_trashed(&self) -> Result<Vec<comments::Model>, DbErr> {
+        Comments::find()
+            .filter(comments::Column::DeletedAt.is_not_null())
+            .order_by_desc(comments::Column::DeletedAt)
+            .all(&self.db_pool)
+            .await
+    }
+
+    async fn restore(&self, id: i32) -> Result<comments::Model, DbErr> {
+        let mut comment: comments::ActiveModel = Comments::find_by_id(id)
+            .filter(comments::Column::DeletedAt.is_not_null())
+            .one(&self.db_pool)
+            .await?
+            .ok_or(DbErr::RecordNotFound("Comment not found in trash".to_string()))?
+            .into();
+
+        comment.deleted_at = Set(None);
+        comment.update(&self.db_pool).await
+    }
+
+    async fn purge_trashed(&self, deleted_before: DateTime<Utc>) -> Result<u64, DbErr> {
+        // Leaves first, a comment is only purged once none of its replies are left
+        let mut purged = 0;
+
+        loop {
+            let result = Comments::delete_many()
+                .filter(comments::Column::DeletedAt.lt(deleted_before))
+                .filter(
+                    comments::Column::Id.not_in_subquery(
+                        Query::select()
+                            .column(comments::Column::ParentId)
+                            .from(Comments)
+                            .and_where(comments::Column::ParentId.is_not_null())
+                            .to_owned(),
+                    ),
+                )
+                .exec(&self.db_pool)
+                .await?;
+
+            if result.rows_affected == 0 {
+                return Ok(purged);
+            }
+            purged += result.rows_affected;
+        }
     }
 }
@@ -0,0 +1,97 @@
+use async_trait::async_trait;
+use sea_orm::sea_query::OnConflict;
+use sea_orm::{ColumnTrait, DatabaseConnection, DbErr, EntityTrait, QueryFilter, QueryOrder, QuerySelect, Set};
+
+use crate::abstract_trait::FollowRepositoryTrait;
+use crate::entities::{category_follows, prelude::{CategoryFollows, UserFollows}, user_follows};
+
+pub struct FollowRepository {
+    db_pool: DatabaseConnection,
+}
+
+impl FollowRepository {
+    pub fn new(db_pool: DatabaseConnection) -> Self {
+        Self { db_pool }
+    }
+}
+
+#[async_trait]
+impl FollowRepositoryTrait for FollowRepository {
+    async fn follow_user(&self, follower_id: i32, followed_id: i32) -> Result<(), DbErr> {
+        let follow = user_follows::ActiveModel {
+            follower_id: Set(follower_id),
+            followed_id: Set(followed_id),
+            ..Default::default()
+        };
+
+        UserFollows::insert(follow)
+            .on_conflict(
+                OnConflict::columns([user_follows::Column::FollowerId, user_follows::Column::FollowedId])
+                    .do_nothing()
+                    .to_owned(),
+            )
+            .exec_without_returning(&self.db_pool)
+            .await
+            .map(|_| ())
+    }
+
+    async fn unfollow_user(&self, follower_id: i32, followed_id: i32) -> Result<u64, DbErr> {
+        let result = UserFollows::delete_many()
+            .filter(user_follows::Column::FollowerId.eq(follower_id))
+            .filter(user_follows::Column::FollowedId.eq(followed_id))
+            .exec(&self.db_pool)
+            .await?;
+
+        Ok(result.rows_affected)
+    }
+
+    async fn follow_category(&self, user_id: i32, category_id: i32) -> Result<(), DbErr> {
+        let follow = category_follows::ActiveModel {
+            user_id: Set(user_id),
+            category_id: Set(category_id),
+            ..Default::default()
+        };
+
+        CategoryFollows::insert(follow)
+            .on_conflict(
+                OnConflict::columns([category_follows::Column::UserId, category_follows::Column::CategoryId])
+                    .do_nothing()
+                    .to_owned(),
+            )
+            .exec_without_returning(&self.db_pool)
+            .await
+            .map(|_| ())
+    }
+
+    async fn unfollow_category(&self, user_id: i32, category_id: i32) -> Result<u64, DbErr> {
+        let result = CategoryFollows::delete_many()
+            .filter(category_follows::Column::UserId.eq(user_id))
+            .filter(category_follows::Column::CategoryId.eq(category_id))
+            .exec(&self.db_pool)
+            .await?;
+
+        Ok(result.rows_affected)
+    }
+
+    async fn find_followed_user_ids(&self, user_id: i32) -> Result<Vec<i32>, DbErr> {
+        UserFollows::find()
+            .select_only()
+            .column(user_follows::Column::FollowedId)
+            .filter(user_follows::Column::FollowerId.eq(user_id))
+            .order_by_asc(user_follows::Column::FollowedId)
+            .into_tuple()
+            .all(&self.db_pool)
+            .await
+    }
+
+    async fn find_followed_category_ids(&self, user_id: i32) -> Result<Vec<i32>, DbErr> {
+        CategoryFollows::find()
+            .select_only()
+            .column(category_follows::Column::CategoryId)
+            .filter(category_follows::Column::UserId.eq(user_id))
+            .order_by_asc(category_follows::Column::CategoryId)
+            .into_tuple()
+            .all(&self.db_pool)
+            .await
+    }
+}
@@ -0,0 +1,56 @@
+use async_trait::async_trait;
+use sea_orm::sea_query::OnConflict;
+use sea_orm::{ColumnTrait, DatabaseConnection, DbErr, EntityTrait, QueryFilter, QueryOrder};
+
+use crate::abstract_trait::ImageVariantRepositoryTrait;
+use crate::entities::{image_variants, prelude::ImageVariants};
+
+pub struct ImageVariantRepository {
+    db_pool: DatabaseConnection,
+}
+
+impl ImageVariantRepository {
+    pub fn new(db_pool: DatabaseConnection) -> Self {
+        Self { db_pool }
+    }
+}
+
+#[async_trait]
+impl ImageVariantRepositoryTrait for ImageVariantRepository {
+    async fn create(&self, variant: image_variants::ActiveModel) -> Result<image_variants::Model, DbErr> {
+        // The same image can be uploaded twice while its variants are still being written
+        let storage_key = variant.storage_key.clone().unwrap();
+
+        ImageVariants::insert(variant)
+            .on_conflict(OnConflict::column(image_variants::Column::StorageKey).do_nothing().to_owned())
+            .exec_without_returning(&self.db_pool)
+            .await?;
+
+        ImageVariants::find()
+            .filter(image_variants::Column::StorageKey.eq(storage_key.as_str()))
+            .one(&self.db_pool)
+            .await?
+            .ok_or(DbErr::RecordNotFound(format!("Image variant {} not found", storage_key)))
+    }
+
+    async fn find_by_sources(&self, source_keys: &[String]) -> Result<Vec<image_variants::Model>, DbErr> {
+        if source_keys.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        ImageVariants::find()
+            .filter(image_variants::Column::SourceKey.is_in(source_keys.iter().map(String::as_str)))
+            .order_by_asc(image_variants::Column::Width)
+            .all(&self.db_pool)
+            .await
+    }
+
+    async fn find_all(&self) -> Result<Vec<image_variants::Model>, DbErr> {
+        ImageVariants::find().all(&self.db_pool).await
+    }
+
+    async fn delete(&self, id: i32) -> Result<(), DbErr> {
+        ImageVariants::delete_by_id(id).exec(&self.db_pool).await?;
+        Ok(())
+    }
+}
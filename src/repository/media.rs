@@ -0,0 +1,203 @@
+use async_trait::async_trait;
+use sea_orm::sea_query::{extension::postgres::PgExpr, Expr, OnConflict};
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, Condition, ConnectionTrait, DatabaseConnection, DbErr, EntityTrait,
+    PaginatorTrait, QueryFilter, QueryOrder, QuerySelect, Set,
+};
+
+use crate::abstract_trait::MediaRepositoryTrait;
+use crate::entities::{categories, media, post_media, posts, prelude::{Categories, Media, PostMedia, Posts, Users}, sea_orm_active_enums::UserRole};
+
+pub struct MediaRepository {
+    db_pool: DatabaseConnection,
+}
+
+impl MediaRepository {
+    pub fn new(db_pool: DatabaseConnection) -> Self {
+        Self { db_pool }
+    }
+}
+
+// Replaces the media attached to a post. Ids that do not exist are ignored, and so are uploads
+// of other users unless the editor is an admin or the media is already attached to the post.
+// Runs on the caller's connection so it joins the post's transaction.
+pub(crate) async fn sync_post_media<C: ConnectionTrait>(
+    db: &C,
+    post_id: i32,
+    media_ids: &[i32],
+    editor_id: Option<i32>,
+) -> Result<(), DbErr> {
+    let attached: Vec<i32> = PostMedia::find()
+        .select_only()
+        .column(post_media::Column::MediaId)
+        .filter(post_media::Column::PostId.eq(post_id))
+        .into_tuple()
+        .all(db)
+        .await?;
+
+    let is_admin = match editor_id {
+        Some(editor_id) => Users::find_by_id(editor_id)
+            .one(db)
+            .await?
+            .is_some_and(|editor| editor.role == UserRole::Admin),
+        None => false,
+    };
+
+    PostMedia::delete_many()
+        .filter(post_media::Column::PostId.eq(post_id))
+        .exec(db)
+        .await?;
+
+    let mut allowed = Condition::any().add(media::Column::Id.is_in(attached));
+    if let Some(editor_id) = editor_id {
+        allowed = allowed.add(media::Column::UserId.eq(editor_id));
+    }
+
+    let mut select = Media::find()
+        .select_only()
+        .column(media::Column::Id)
+        .filter(media::Column::Id.is_in(media_ids.iter().copied()));
+
+    if !is_admin {
+        select = select.filter(allowed);
+    }
+
+    let existing: Vec<i32> = select.into_tuple().all(db).await?;
+
+    if existing.is_empty() {
+        return Ok(());
+    }
+
+    PostMedia::insert_many(existing.into_iter().map(|media_id| post_media::ActiveModel {
+        post_id: Set(post_id),
+        media_id: Set(media_id),
+    }))
+    .on_conflict(
+        OnConflict::columns([post_media::Column::PostId, post_media::Column::MediaId])
+            .do_nothing()
+            .to_owned(),
+    )
+    .exec_without_returning(db)
+    .await?;
+
+    Ok(())
+}
+
+#[async_trait]
+impl MediaRepositoryTrait for MediaRepository {
+    async fn create(&self, media: media::ActiveModel) -> Result<media::Model, DbErr> {
+        media.insert(&self.db_pool).await
+    }
+
+    async fn find_by_id(&self, id: i32) -> Result<Option<media::Model>, DbErr> {
+        Media::find_by_id(id).one(&self.db_pool).await
+    }
+
+    async fn search(&self, user_id: Option<i32>, query: Option<&str>) -> Result<Vec<media::Model>, DbErr> {
+        let mut select = Media::find();
+
+        if let Some(user_id) = user_id {
+            select = select.filter(media::Column::UserId.eq(user_id));
+        }
+
+        if let Some(query) = query.map(str::trim).filter(|query| !query.is_empty()) {
+            let pattern = format!("%{}%", query.replace('\\', "\\\\").replace('%', "\\%").replace('_', "\\_"));
+            select = select.filter(
+                Condition::any()
+                    .add(Expr::col(media::Column::OriginalName).ilike(pattern.as_str()))
+                    .add(Expr::col(media::Column::AltText).ilike(pattern.as_str())),
+            );
+        }
+
+        select
+            .order_by_desc(media::Column::CreatedAt)
+            .order_by_desc(media::Column::Id)
+            .all(&self.db_pool)
+            .await
+    }
+
+    async fn find_by_posts(&self, post_ids: &[i32]) -> Result<Vec<(i32, media::Model)>, DbErr> {
+        if post_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let links = PostMedia::find()
+            .filter(post_media::Column::PostId.is_in(post_ids.iter().copied()))
+            .find_also_related(Media)
+            .order_by_asc(media::Column::Id)
+            .all(&self.db_pool)
+            .await?;
+
+        Ok(links
+            .into_iter()
+            .filter_map(|(link, media)| media.map(|media| (link.post_id, media)))
+            .collect())
+    }
+
+    async fn update_alt_text(&self, id: i32, alt_text: &str) -> Result<media::Model, DbErr> {
+        let mut media: media::ActiveModel = Media::find_by_id(id)
+            .one(&self.db_pool)
+            .await?
+            .ok_or(DbErr::RecordNotFound("Media not found".to_owned()))?
+            .into();
+
+        media.alt_text = Set(alt_text.to_string());
+        media.update(&self.db_pool).await
+    }
+
+    async fn delete(&self, id: i32) -> Result<(), DbErr> {
+        Media::delete_by_id(id).exec(&self.db_pool).await?;
+        Ok(())
+    }
+
+    async fn count_references(&self, id: i32, url: &str) -> Result<u64, DbErr> {
+        let attached = PostMedia::find()
+            .filter(post_media::Column::MediaId.eq(id))
+            .count(&self.db_pool)
+            .await?;
+
+        // Trashed posts still count, restoring one must not bring back a broken image
+        let as_image = Posts::find()
+            .filter(posts::Column::Img.eq(url))
+            .count(&self.db_pool)
+            .await?;
+
+        let as_cover = Categories::find()
+            .filter(categories::Column::CoverImg.eq(url))
+            .count(&self.db_pool)
+            .await?;
+
+        Ok(attached + as_image + as_cover)
+    }
+
+    async fn all_filenames(&self) -> Result<Vec<String>, DbErr> {
+        Media::find()
+            .select_only()
+            .column(media::Column::Filename)
+            .distinct()
+            .into_tuple()
+            .all(&self.db_pool)
+            .await
+    }
+
+    async fn all_post_images(&self) -> Result<Vec<String>, DbErr> {
+        Posts::find()
+            .select_only()
+            .column(posts::Column::Img)
+            .distinct()
+            .into_tuple()
+            .all(&self.db_pool)
+            .await
+    }
+
+    async fn all_category_covers(&self) -> Result<Vec<String>, DbErr> {
+        Categories::find()
+            .select_only()
+            .column(categories::Column::CoverImg)
+            .filter(categories::Column::CoverImg.is_not_null())
+            .distinct()
+            .into_tuple()
+            .all(&self.db_pool)
+            .await
+    }
+}
@@ -2,8 +2,27 @@ mod category;
 mod posts;
 mod comment;
 mod user;
+mod post_revision;
+mod tag;
+mod image_variant;
+mod media;
+mod reaction;
+mod bookmark;
+mod follow;
+mod notification;
 
 pub use self::category::CategoryRepository;
 pub use self::posts::PostRepository;
 pub use self::comment::CommentRepository;
-pub use self::user::UserRepository;
\ No newline at end of file
+pub use self::user::UserRepository;
+pub use self::post_revision::PostRevisionRepository;
+pub use self::tag::TagRepository;
+pub use self::image_variant::ImageVariantRepository;
+pub use self::media::MediaRepository;
+pub use self::reaction::ReactionRepository;
+pub use self::bookmark::BookmarkRepository;
+pub use self::follow::FollowRepository;
+pub use self::notification::NotificationRepository;
+
+pub(crate) use self::media::sync_post_media;
+pub(crate) use self::tag::{post_tag_names, sync_post_tags};
\ No newline at end of file
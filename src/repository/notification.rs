@@ -0,0 +1,127 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use sea_orm::sea_query::{Expr, OnConflict};
+use sea_orm::{ActiveModelTrait, ColumnTrait, DatabaseConnection, DbErr, EntityTrait, PaginatorTrait, QueryFilter, QueryOrder, QuerySelect, Set};
+
+use crate::abstract_trait::NotificationRepositoryTrait;
+use crate::domain::NewNotification;
+use crate::entities::{notification_preferences, notifications, prelude::{NotificationPreferences, Notifications}, sea_orm_active_enums::NotificationKind};
+
+pub struct NotificationRepository {
+    db_pool: DatabaseConnection,
+}
+
+impl NotificationRepository {
+    pub fn new(db_pool: DatabaseConnection) -> Self {
+        Self { db_pool }
+    }
+}
+
+#[async_trait]
+impl NotificationRepositoryTrait for NotificationRepository {
+    async fn create(&self, input: &NewNotification) -> Result<notifications::Model, DbErr> {
+        notifications::ActiveModel {
+            user_id: Set(input.user_id),
+            actor_id: Set(input.actor_id),
+            kind: Set(input.kind),
+            post_id: Set(input.post_id),
+            comment_id: Set(input.comment_id),
+            message: Set(input.message.clone()),
+            ..Default::default()
+        }
+        .insert(&self.db_pool)
+        .await
+    }
+
+    async fn find_page(&self, user_id: i32, unread_only: bool, page: u64, per_page: u64) -> Result<(Vec<notifications::Model>, u64), DbErr> {
+        let mut query = Notifications::find().filter(notifications::Column::UserId.eq(user_id));
+
+        if unread_only {
+            query = query.filter(notifications::Column::ReadAt.is_null());
+        }
+
+        let paginator = query
+            .order_by_desc(notifications::Column::CreatedAt)
+            .order_by_desc(notifications::Column::Id)
+            .paginate(&self.db_pool, per_page);
+
+        let total = paginator.num_items().await?;
+
+        // Pages past the end are empty, which also keeps SeaORM's page * per_page from overflowing
+        if (page - 1).checked_mul(per_page).is_none_or(|offset| offset >= total) {
+            return Ok((Vec::new(), total));
+        }
+
+        let notifications = paginator.fetch_page(page - 1).await?;
+
+        Ok((notifications, total))
+    }
+
+    async fn count_unread(&self, user_id: i32) -> Result<u64, DbErr> {
+        Notifications::find()
+            .filter(notifications::Column::UserId.eq(user_id))
+            .filter(notifications::Column::ReadAt.is_null())
+            .count(&self.db_pool)
+            .await
+    }
+
+    async fn mark_read(&self, user_id: i32, id: i32) -> Result<Option<notifications::Model>, DbErr> {
+        let Some(notification) = Notifications::find_by_id(id)
+            .filter(notifications::Column::UserId.eq(user_id))
+            .one(&self.db_pool)
+            .await?
+        else {
+            return Ok(None);
+        };
+
+        // Reading twice keeps the time of the first read
+        if notification.read_at.is_some() {
+            return Ok(Some(notification));
+        }
+
+        let mut notification: notifications::ActiveModel = notification.into();
+        notification.read_at = Set(Some(Utc::now().fixed_offset()));
+        notification.update(&self.db_pool).await.map(Some)
+    }
+
+    async fn mark_all_read(&self, user_id: i32) -> Result<u64, DbErr> {
+        let result = Notifications::update_many()
+            .col_expr(notifications::Column::ReadAt, Expr::value(Utc::now()))
+            .filter(notifications::Column::UserId.eq(user_id))
+            .filter(notifications::Column::ReadAt.is_null())
+            .exec(&self.db_pool)
+            .await?;
+
+        Ok(result.rows_affected)
+    }
+
+    async fn find_preferences(&self, user_id: i32) -> Result<Vec<(NotificationKind, bool)>, DbErr> {
+        NotificationPreferences::find()
+            .select_only()
+            .column(notification_preferences::Column::Kind)
+            .column(notification_preferences::Column::Enabled)
+            .filter(notification_preferences::Column::UserId.eq(user_id))
+            .into_tuple()
+            .all(&self.db_pool)
+            .await
+    }
+
+    async fn set_preference(&self, user_id: i32, kind: NotificationKind, enabled: bool) -> Result<(), DbErr> {
+        let preference = notification_preferences::ActiveModel {
+            user_id: Set(user_id),
+            kind: Set(kind),
+            enabled: Set(enabled),
+            ..Default::default()
+        };
+
+        NotificationPreferences::insert(preference)
+            .on_conflict(
+                OnConflict::columns([notification_preferences::Column::UserId, notification_preferences::Column::Kind])
+                    .update_column(notification_preferences::Column::Enabled)
+                    .to_owned(),
+            )
+            .exec_without_returning(&self.db_pool)
+            .await
+            .map(|_| ())
+    }
+}
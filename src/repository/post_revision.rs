@@ -0,0 +1,33 @@
+use async_trait::async_trait;
+use sea_orm::{ColumnTrait, DatabaseConnection, DbErr, EntityTrait, QueryFilter, QueryOrder};
+
+use crate::abstract_trait::PostRevisionRepositoryTrait;
+use crate::entities::{post_revisions, prelude::PostRevisions};
+
+pub struct PostRevisionRepository {
+    db_pool: DatabaseConnection,
+}
+
+impl PostRevisionRepository {
+    pub fn new(db_pool: DatabaseConnection) -> Self {
+        Self { db_pool }
+    }
+}
+
+#[async_trait]
+impl PostRevisionRepositoryTrait for PostRevisionRepository {
+    async fn find_by_post(&self, post_id: i32) -> Result<Vec<post_revisions::Model>, DbErr> {
+        PostRevisions::find()
+            .filter(post_revisions::Column::PostId.eq(post_id))
+            .order_by_desc(post_revisions::Column::Id)
+            .all(&self.db_pool)
+            .await
+    }
+
+    async fn find_by_id(&self, post_id: i32, revision_id: i32) -> Result<Option<post_revisions::Model>, DbErr> {
+        PostRevisions::find_by_id(revision_id)
+            .filter(post_revisions::Column::PostId.eq(post_id))
+            .one(&self.db_pool)
+            .await
+    }
+}
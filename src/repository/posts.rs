@@ -1,10 +1,14 @@
 use crate::abstract_trait::PostsRepositoryTrait;
-use crate::domain::{CreatePostRequest, PostRelationResponse, UpdatePostRequest};
-use crate::entities::{comments, prelude::Posts, posts};
+use crate::domain::{CreatePostRequest, FeedCursor, PostListQuery, PostSort, SortOrder, UpdatePostRequest};
+use crate::entities::{post_revisions, post_slug_history, prelude::{PostSlugHistory, Posts, Tags}, posts, sea_orm_active_enums::{BodyFormat, PostStatus}, tags};
+use crate::repository::{post_tag_names, sync_post_media, sync_post_tags};
+use crate::utils::{generate_slug, render_body};
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sea_orm::sea_query::Expr;
 use sea_orm::{
-    ActiveModelTrait, ColumnTrait, DatabaseConnection, DbErr, EntityTrait, ModelTrait, QueryFilter,
-    Set,
+    ActiveModelTrait, ColumnTrait, ConnectionTrait, DatabaseConnection, DbBackend, DbErr, EntityTrait,
+    Order, PaginatorTrait, QueryFilter, QueryOrder, QuerySelect, Set, Statement, TransactionTrait, Value,
 };
 use tracing::{error, info};
 
@@ -16,82 +20,240 @@ impl PostRepository {
     pub fn new(db_pool: DatabaseConnection) -> Self {
         Self { db_pool }
     }
+
+    async fn record_revision<C: ConnectionTrait>(
+        db: &C,
+        post: &posts::Model,
+        editor_id: Option<i32>,
+    ) -> Result<post_revisions::Model, DbErr> {
+        let tags = post_tag_names(db, post.id).await?;
+
+        post_revisions::ActiveModel {
+            post_id: Set(post.id),
+            user_id: Set(editor_id),
+            title: Set(post.title.clone()),
+            body: Set(post.body.clone()),
+            img: Set(post.img.clone()),
+            category_id: Set(post.category_id),
+            body_format: Set(Some(post.body_format)),
+            tags: Set(Some(serde_json::json!(tags))),
+            ..Default::default()
+        }
+        .insert(db)
+        .await
+    }
+
+    // One branch per kind of follow. Each takes the newest `limit` posts of every followed author or
+    // category through a LATERAL walk of its (user_id or category_id, published_at, id) index, so a
+    // probe reads at most `limit` rows however many posts it has. Only those candidates get sorted,
+    // and UNION drops posts reached through both branches
+    fn feed_statement(user_id: i32, after: Option<FeedCursor>, limit: u64) -> Statement {
+        let mut values: Vec<Value> = vec![user_id.into(), i64::try_from(limit).unwrap_or(i64::MAX).into()];
+
+        // Keyset pagination, rows published at the same instant are told apart by id
+        let keyset = match after {
+            Some(cursor) => {
+                values.extend([cursor.published_at.into(), cursor.id.into()]);
+                "AND (posts.published_at, posts.id) < ($3, $4)"
+            }
+            None => "",
+        };
+
+        let branch = |follows: &str, column: &str| {
+            format!(
+                "(SELECT feed_posts.* FROM {} AS follows
+                CROSS JOIN LATERAL (
+                    SELECT posts.* FROM posts
+                    WHERE posts.{} = follows.followed
+                        AND posts.status = 'published' AND posts.published_at IS NOT NULL AND posts.deleted_at IS NULL
+                        {}
+                    ORDER BY posts.published_at DESC, posts.id DESC
+                    LIMIT $2
+                ) AS feed_posts
+                ORDER BY feed_posts.published_at DESC, feed_posts.id DESC
+                LIMIT $2)",
+                follows, column, keyset,
+            )
+        };
+
+        let sql = format!(
+            "SELECT * FROM ({} UNION {}) AS feed ORDER BY published_at DESC, id DESC LIMIT $2",
+            branch("(SELECT followed_id AS followed FROM user_follows WHERE follower_id = $1)", "user_id"),
+            branch("(SELECT category_id AS followed FROM category_follows WHERE user_id = $1)", "category_id"),
+        );
+
+        Statement::from_sql_and_values(DbBackend::Postgres, sql, values)
+    }
+
+    // Slugs are unique across posts, so a clashing title gets a numeric suffix
+    async fn unique_slug<C: ConnectionTrait>(
+        db: &C,
+        title: &str,
+        post_id: Option<i32>,
+    ) -> Result<String, DbErr> {
+        let base = match generate_slug(title) {
+            slug if slug.is_empty() => "post".to_string(),
+            slug => slug,
+        };
+
+        let mut candidate = base.clone();
+        let mut suffix = 1;
+
+        loop {
+            let mut query = Posts::find().filter(posts::Column::Slug.eq(candidate.as_str()));
+
+            if let Some(id) = post_id {
+                query = query.filter(posts::Column::Id.ne(id));
+            }
+
+            if query.count(db).await? == 0 {
+                return Ok(candidate);
+            }
+
+            suffix += 1;
+            candidate = format!("{}-{}", base, suffix);
+        }
+    }
 }
 
 #[async_trait]
 impl PostsRepositoryTrait for PostRepository {
-    async fn get_all_posts(&self) -> Result<Vec<posts::Model>, DbErr> {
-        Posts::find().all(&self.db_pool).await
-    }
+    async fn get_published_posts(&self, query: &PostListQuery) -> Result<Vec<posts::Model>, DbErr> {
+        let column = match query.sort.unwrap_or_default() {
+            PostSort::PublishedAt => posts::Column::PublishedAt,
+            PostSort::CreatedAt => posts::Column::CreatedAt,
+            PostSort::UpdatedAt => posts::Column::UpdatedAt,
+            PostSort::Title => posts::Column::Title,
+        };
+        let order = match query.order.unwrap_or_default() {
+            SortOrder::Asc => Order::Asc,
+            SortOrder::Desc => Order::Desc,
+        };
 
-    async fn get_post(&self, post_id: i32) -> Result<Option<posts::Model>, DbErr> {
-        Posts::find_by_id(post_id).one(&self.db_pool).await
+        let mut select = Posts::find_active().filter(posts::Column::Status.eq(PostStatus::Published));
+
+        if let Some(category_id) = query.category_id {
+            select = if query.include_descendants.unwrap_or(false) {
+                select.filter(Expr::cust_with_values(
+                    r#"posts.category_id IN (
+                        WITH RECURSIVE tree AS (
+                            SELECT id FROM categories WHERE id = $1
+                            UNION ALL
+                            SELECT c.id FROM categories c JOIN tree t ON c.parent_id = t.id WHERE c.deleted_at IS NULL
+                        )
+                        SELECT id FROM tree
+                    )"#,
+                    [category_id],
+                ))
+            } else {
+                select.filter(posts::Column::CategoryId.eq(category_id))
+            };
+        }
+
+        // Id as tie breaker keeps the order stable for equal timestamps or titles
+        select
+            .order_by(column, order.clone())
+            .order_by(posts::Column::Id, order)
+            .all(&self.db_pool)
+            .await
     }
 
-    async fn get_post_relation(&self, post_id: i32) -> Result<Vec<PostRelationResponse>, DbErr> {
-        // Log the start of the function
-        info!("Fetching post relation for post ID: {}", post_id);
+    async fn get_feed_posts(&self, user_id: i32, after: Option<FeedCursor>, limit: u64) -> Result<Vec<posts::Model>, DbErr> {
+        Posts::find()
+            .from_raw_sql(Self::feed_statement(user_id, after, limit))
+            .all(&self.db_pool)
+            .await
+    }
 
-        match Posts::find()
-            .filter(posts::Column::Id.eq(post_id))
-            .find_with_related(comments::Entity)
+    async fn get_published_posts_by_tag(&self, tag_slug: &str) -> Result<Vec<posts::Model>, DbErr> {
+        Posts::find_active()
+            .inner_join(Tags)
+            .filter(tags::Column::Slug.eq(tag_slug))
+            .filter(posts::Column::Status.eq(PostStatus::Published))
+            .order_by_desc(posts::Column::PublishedAt)
             .all(&self.db_pool)
             .await
-        {
-            Ok(post_with_comments) => {
-                info!(
-                    "Successfully fetched post with related comments for post ID: {}",
-                    post_id
-                );
-
-                let result = post_with_comments
-                    .into_iter()
-                    .flat_map(|(post, comments)| {
-                        comments.into_iter().map(move |comment| {
-                            PostRelationResponse::from_post_and_comment(&post, &comment)
-                        })
-                    })
-                    .collect::<Vec<_>>();
-
-            
-                info!(
-                    "Found {} related comments for post ID: {}",
-                    result.len(),
-                    post_id
-                );
-
-                Ok(result)
-            }
-            Err(e) => {
-               
-                error!(
-                    "Failed to fetch post relation for post ID: {}. Error: {:?}",
-                    post_id, e
-                );
-                Err(e)
-            }
+    }
+
+    async fn get_posts_by_author(&self, user_id: i32, status: Option<PostStatus>) -> Result<Vec<posts::Model>, DbErr> {
+        let mut query = Posts::find_active().filter(posts::Column::UserId.eq(user_id));
+
+        if let Some(status) = status {
+            query = query.filter(posts::Column::Status.eq(status));
         }
+
+        query.order_by_desc(posts::Column::Id).all(&self.db_pool).await
+    }
+
+    async fn get_post(&self, post_id: i32) -> Result<Option<posts::Model>, DbErr> {
+        Posts::find_active_by_id(post_id).one(&self.db_pool).await
+    }
+
+    async fn get_post_by_slug(&self, slug: &str) -> Result<Option<posts::Model>, DbErr> {
+        Posts::find_active()
+            .filter(posts::Column::Slug.eq(slug))
+            .one(&self.db_pool)
+            .await
+    }
+
+    async fn find_slug_redirect(&self, slug: &str) -> Result<Option<String>, DbErr> {
+        let history = PostSlugHistory::find()
+            .filter(post_slug_history::Column::Slug.eq(slug))
+            .find_also_related(Posts)
+            .one(&self.db_pool)
+            .await?;
+
+        Ok(history
+            .and_then(|(_, post)| post)
+            .filter(|post| post.deleted_at.is_none())
+            .map(|post| post.slug))
     }
 
     async fn create_post(&self, input: &CreatePostRequest) -> Result<posts::Model, DbErr> {
+        let txn = self.db_pool.begin().await?;
+
+        let slug = Self::unique_slug(&txn, &input.title, None).await?;
+        let status = input.status.unwrap_or(PostStatus::Published);
+        let body_format = input.body_format.unwrap_or(BodyFormat::Plain);
+
         let new_post = posts::ActiveModel {
             title: Set(input.title.to_string()),
             body: Set(input.body.to_string()),
-            slug: Set(input.title.to_string()),
+            body_format: Set(body_format),
+            body_html: Set(Some(render_body(&input.body, body_format))),
+            slug: Set(slug),
             img: Set(input.img.to_string()),
             category_id: Set(input.category_id),
             user_id: Set(input.user_id),
             user_name: Set(input.user_name.to_string()),
+            status: Set(status),
+            published_at: Set((status == PostStatus::Published).then(|| Utc::now().into())),
+            publish_at: Set(input.publish_at.filter(|_| status == PostStatus::Scheduled)),
             ..Default::default()
         };
 
-        match new_post.insert(&self.db_pool).await {
-            Ok(post) => Ok(post),
+        let post = match new_post.insert(&txn).await {
+            Ok(post) => post,
             Err(e) => {
                 error!("Failed to create post: {:?}", e);
-                Err(e)
+                return Err(e);
             }
+        };
+
+        if let Some(tags) = &input.tags {
+            sync_post_tags(&txn, post.id, tags).await?;
+        }
+
+        // Recorded after the tag sync so the revision captures the new tag list
+        Self::record_revision(&txn, &post, Some(input.user_id)).await?;
+
+        if let Some(media_ids) = &input.media_ids {
+            sync_post_media(&txn, post.id, media_ids, input.editor_id).await?;
         }
+
+        txn.commit().await?;
+
+        Ok(post)
     }
 
     async fn update_post(&self, input: &UpdatePostRequest) -> Result<posts::Model, DbErr> {
@@ -100,29 +262,249 @@ impl PostsRepositoryTrait for PostRepository {
             None => return Err(DbErr::Custom("Post ID is required".to_string())),
         };
 
-        let post = Posts::find_by_id(id)
-            .one(&self.db_pool)
+        let txn = self.db_pool.begin().await?;
+
+        let post = Posts::find_active_by_id(id)
+            .one(&txn)
             .await?
             .ok_or(DbErr::RecordNotFound("Post not found".to_owned()))?;
 
+        let old_slug = post.slug.clone();
+        let was_published = post.published_at.is_some();
+        let body_format = input.body_format.unwrap_or(post.body_format);
+        let new_slug = if post.title != input.title {
+            Self::unique_slug(&txn, &input.title, Some(id)).await?
+        } else {
+            old_slug.clone()
+        };
+
         let mut post: posts::ActiveModel = post.into();
         post.title = Set(input.title.to_string());
+        post.slug = Set(new_slug.clone());
         post.body = Set(input.body.to_string());
+        post.body_format = Set(body_format);
+        post.body_html = Set(Some(render_body(&input.body, body_format)));
         post.img = Set(input.img.to_string());
         post.category_id = Set(input.category_id);
-        post.user_id = Set(input.user_id);
-        post.user_name = Set(input.user_name.to_string());
 
-        post.update(&self.db_pool).await
+        if let Some(status) = input.status {
+            post.status = Set(status);
+            post.publish_at = Set(input.publish_at.filter(|_| status == PostStatus::Scheduled));
+
+            match status {
+                PostStatus::Published if !was_published => post.published_at = Set(Some(Utc::now().into())),
+                PostStatus::Draft | PostStatus::Scheduled => post.published_at = Set(None),
+                _ => {}
+            }
+        }
+
+        let post = post.update(&txn).await?;
+
+        if let Some(tags) = &input.tags {
+            sync_post_tags(&txn, post.id, tags).await?;
+        }
+
+        // Recorded after the tag sync so the revision captures the new tag list
+        Self::record_revision(&txn, &post, input.editor_id).await?;
+
+        if let Some(media_ids) = &input.media_ids {
+            sync_post_media(&txn, post.id, media_ids, input.editor_id).await?;
+        }
+
+        if new_slug != old_slug {
+            // The new slug is live again, so it must no longer redirect anywhere
+            PostSlugHistory::delete_many()
+                .filter(post_slug_history::Column::Slug.is_in([old_slug.as_str(), new_slug.as_str()]))
+                .exec(&txn)
+                .await?;
+
+            post_slug_history::ActiveModel {
+                post_id: Set(post.id),
+                slug: Set(old_slug),
+                ..Default::default()
+            }
+            .insert(&txn)
+            .await?;
+
+            info!("Recorded slug change for post ID {}: now {}", post.id, post.slug);
+        }
+
+        txn.commit().await?;
+
+        Ok(post)
     }
 
     async fn delete_post(&self, post_id: i32) -> Result<(), DbErr> {
-        let post = Posts::find_by_id(post_id)
+        let mut post: posts::ActiveModel = Posts::find_active_by_id(post_id)
             .one(&self.db_pool)
             .await?
-            .ok_or(DbErr::RecordNotFound("Post not found".to_owned()))?;
+            .ok_or(DbErr::RecordNotFound("Post not found".to_owned()))?
+            .into();
 
-        post.delete(&self.db_pool).await?;
+        post.deleted_at = Set(Some(Utc::now().into()));
+        post.update(&self.db_pool).await?;
         Ok(())
     }
+
+    async fn get_trashed_posts(&self) -> Result<Vec<posts::Model>, DbErr> {
+        Posts::find()
+            .filter(posts::Column::DeletedAt.is_not_null())
+            .order_by_desc(posts::Column::DeletedAt)
+            .all(&self.db_pool)
+            .await
+    }
+
+    async fn restore_post(&self, post_id: i32) -> Result<posts::Model, DbErr> {
+        let mut post: posts::ActiveModel = Posts::find_by_id(post_id)
+            .filter(posts::Column::DeletedAt.is_not_null())
+            .one(&self.db_pool)
+            .await?
+            .ok_or(DbErr::RecordNotFound("Post not found in trash".to_owned()))?
+            .into();
+
+        post.deleted_at = Set(None);
+        post.update(&self.db_pool).await
+    }
+
+    async fn purge_trashed_posts(&self, deleted_before: DateTime<Utc>) -> Result<u64, DbErr> {
+        let result = Posts::delete_many()
+            .filter(posts::Column::DeletedAt.lt(deleted_before))
+            .exec(&self.db_pool)
+            .await?;
+
+        Ok(result.rows_affected)
+    }
+
+    async fn publish_due_posts(&self) -> Result<u64, DbErr> {
+        let result = Posts::update_many()
+            .col_expr(posts::Column::Status, Expr::value(PostStatus::Published))
+            .col_expr(posts::Column::PublishedAt, Expr::col(posts::Column::PublishAt).into())
+            .col_expr(posts::Column::PublishAt, Expr::value(Option::<chrono::DateTime<chrono::FixedOffset>>::None))
+            .col_expr(posts::Column::UpdatedAt, Expr::current_timestamp().into())
+            .filter(posts::Column::Status.eq(PostStatus::Scheduled))
+            .filter(posts::Column::PublishAt.lte(Utc::now()))
+            .filter(posts::Column::DeletedAt.is_null())
+            .exec(&self.db_pool)
+            .await?;
+
+        Ok(result.rows_affected)
+    }
+
+    // Trashed posts are included so a restored post does not come back without its HTML
+    async fn render_missing_body_html(&self, limit: u64) -> Result<u64, DbErr> {
+        let pending = Posts::find()
+            .filter(posts::Column::BodyHtml.is_null())
+            .order_by_asc(posts::Column::Id)
+            .limit(limit)
+            .all(&self.db_pool)
+            .await?;
+
+        for post in &pending {
+            // An edit that lands in the meantime has already stored fresher HTML
+            Posts::update_many()
+                .col_expr(posts::Column::BodyHtml, Expr::value(render_body(&post.body, post.body_format)))
+                .filter(posts::Column::Id.eq(post.id))
+                .filter(posts::Column::BodyHtml.is_null())
+                .exec(&self.db_pool)
+                .await?;
+        }
+
+        Ok(pending.len() as u64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sea_orm::{Database, DatabaseTransaction, JsonValue};
+
+    const FEED_INDEXES: [&str; 2] = ["idx-posts-user_id-published_at-id", "idx-posts-category_id-published_at-id"];
+
+    async fn exec(txn: &DatabaseTransaction, sql: &str, values: Vec<Value>) {
+        txn.execute(Statement::from_sql_and_values(DbBackend::Postgres, sql, values)).await.unwrap();
+    }
+
+    // Every node that reads `posts`, with its node type, index and rows returned per loop
+    fn posts_scans(plan: &JsonValue, scans: &mut Vec<(String, Option<String>, f64)>) {
+        if plan["Relation Name"] == "posts" {
+            scans.push((
+                plan["Node Type"].as_str().unwrap_or_default().to_string(),
+                plan["Index Name"].as_str().map(str::to_string),
+                plan["Actual Rows"].as_f64().unwrap_or_default(),
+            ));
+        }
+
+        for child in plan["Plans"].as_array().into_iter().flatten() {
+            posts_scans(child, scans);
+        }
+    }
+
+    // Seeds 20,000 followed authors and 500 followed categories inside a transaction that is rolled
+    // back, then checks that the feed only probes the feed indexes and reads one page per followed id.
+    // Run with `DATABASE_URL=... cargo test -- --ignored feed_plan`
+    #[tokio::test]
+    #[ignore = "needs a migrated Postgres in DATABASE_URL"]
+    async fn feed_plan_reads_one_page_per_follow_with_many_follows() {
+        let url = std::env::var("DATABASE_URL").expect("DATABASE_URL must point at a migrated database");
+        let db = Database::connect(url).await.unwrap();
+        let txn = db.begin().await.unwrap();
+
+        let follower: i32 = txn
+            .query_one(Statement::from_string(
+                DbBackend::Postgres,
+                "INSERT INTO users (firstname, lastname, email, password)
+                VALUES ('Feed', 'Reader', 'feed-bench-reader@example.test', '') RETURNING id",
+            ))
+            .await
+            .unwrap()
+            .unwrap()
+            .try_get("", "id")
+            .unwrap();
+
+        exec(&txn, "INSERT INTO users (firstname, lastname, email, password)
+            SELECT 'Feed', 'Author', 'feed-bench-' || n || '@example.test', '' FROM generate_series(1, 20000) AS n", vec![]).await;
+        exec(&txn, "INSERT INTO categories (name, slug)
+            SELECT 'feed-bench-' || n, 'feed-bench-' || n FROM generate_series(1, 500) AS n", vec![]).await;
+        exec(&txn, "INSERT INTO user_follows (follower_id, followed_id)
+            SELECT $1, id FROM users WHERE email LIKE 'feed-bench-%' AND id <> $1", vec![follower.into()]).await;
+        exec(&txn, "INSERT INTO category_follows (user_id, category_id)
+            SELECT $1, id FROM categories WHERE slug LIKE 'feed-bench-%'", vec![follower.into()]).await;
+        exec(&txn, "WITH authors AS (
+                SELECT id, row_number() OVER (ORDER BY id) AS k FROM users WHERE email LIKE 'feed-bench-%' AND id <> $1
+            ), categories AS (
+                SELECT id, row_number() OVER (ORDER BY id) - 1 AS k FROM categories WHERE slug LIKE 'feed-bench-%'
+            )
+            INSERT INTO posts (title, slug, img, body, category_id, user_id, user_name, status, published_at)
+            SELECT 'Post', 'feed-bench-' || authors.id || '-' || n, '', '', categories.id, authors.id, 'Feed Author',
+                'published', now() - make_interval(mins => (authors.k * 5 + n)::int)
+            FROM authors
+            JOIN categories ON categories.k = authors.k % 500
+            CROSS JOIN generate_series(1, 5) AS n", vec![follower.into()]).await;
+        exec(&txn, "ANALYZE users, categories, user_follows, category_follows, posts", vec![]).await;
+
+        let feed = PostRepository::feed_statement(follower, None, 20);
+        let explain = Statement {
+            sql: format!("EXPLAIN (ANALYZE, FORMAT JSON) {}", feed.sql),
+            ..feed.clone()
+        };
+        let plan: JsonValue = txn.query_one(explain).await.unwrap().unwrap().try_get("", "QUERY PLAN").unwrap();
+
+        let mut scans = Vec::new();
+        posts_scans(&plan[0]["Plan"], &mut scans);
+
+        assert!(!scans.is_empty(), "feed plan never reads posts: {plan}");
+        for (node, index, rows) in &scans {
+            assert!(
+                node.starts_with("Index") && index.as_deref().is_some_and(|index| FEED_INDEXES.contains(&index)),
+                "posts is read by {node} {index:?} instead of a feed index: {plan}"
+            );
+            assert!(*rows <= 20.0, "a probe returned {rows} rows, more than one page: {plan}");
+        }
+
+        let posts = Posts::find().from_raw_sql(feed).all(&txn).await.unwrap();
+        assert_eq!(posts.len(), 20);
+        assert!(posts.windows(2).all(|pair| (pair[0].published_at, pair[0].id) > (pair[1].published_at, pair[1].id)));
+
+        txn.rollback().await.unwrap();
+    }
 }
@@ -0,0 +1,153 @@
+use async_trait::async_trait;
+use sea_orm::sea_query::{Expr, OnConflict};
+use sea_orm::{ColumnTrait, DatabaseConnection, DbErr, EntityTrait, QueryFilter, QueryOrder, QuerySelect, Set};
+
+use crate::abstract_trait::ReactionRepositoryTrait;
+use crate::entities::{comment_votes, post_reactions, prelude::{CommentVotes, PostReactions}, sea_orm_active_enums::ReactionKind};
+
+pub struct ReactionRepository {
+    db_pool: DatabaseConnection,
+}
+
+impl ReactionRepository {
+    pub fn new(db_pool: DatabaseConnection) -> Self {
+        Self { db_pool }
+    }
+}
+
+#[async_trait]
+impl ReactionRepositoryTrait for ReactionRepository {
+    async fn add_post_reaction(&self, post_id: i32, user_id: i32, kind: ReactionKind) -> Result<(), DbErr> {
+        let reaction = post_reactions::ActiveModel {
+            post_id: Set(post_id),
+            user_id: Set(user_id),
+            kind: Set(kind),
+            ..Default::default()
+        };
+
+        // Reacting twice with the same kind is a no-op thanks to the unique index
+        PostReactions::insert(reaction)
+            .on_conflict(
+                OnConflict::columns([
+                    post_reactions::Column::PostId,
+                    post_reactions::Column::UserId,
+                    post_reactions::Column::Kind,
+                ])
+                .do_nothing()
+                .to_owned(),
+            )
+            .exec_without_returning(&self.db_pool)
+            .await
+            .map(|_| ())
+    }
+
+    async fn remove_post_reaction(&self, post_id: i32, user_id: i32, kind: ReactionKind) -> Result<u64, DbErr> {
+        let result = PostReactions::delete_many()
+            .filter(post_reactions::Column::PostId.eq(post_id))
+            .filter(post_reactions::Column::UserId.eq(user_id))
+            .filter(post_reactions::Column::Kind.eq(kind))
+            .exec(&self.db_pool)
+            .await?;
+
+        Ok(result.rows_affected)
+    }
+
+    async fn count_post_reactions(&self, post_ids: &[i32]) -> Result<Vec<(i32, ReactionKind, i64)>, DbErr> {
+        if post_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        PostReactions::find()
+            .select_only()
+            .column(post_reactions::Column::PostId)
+            .column(post_reactions::Column::Kind)
+            .column_as(post_reactions::Column::Id.count(), "count")
+            .filter(post_reactions::Column::PostId.is_in(post_ids.iter().copied()))
+            .group_by(post_reactions::Column::PostId)
+            .group_by(post_reactions::Column::Kind)
+            .order_by_asc(post_reactions::Column::Kind)
+            .into_tuple()
+            .all(&self.db_pool)
+            .await
+    }
+
+    async fn find_user_post_reactions(&self, post_ids: &[i32], user_id: i32) -> Result<Vec<(i32, ReactionKind)>, DbErr> {
+        if post_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        PostReactions::find()
+            .select_only()
+            .column(post_reactions::Column::PostId)
+            .column(post_reactions::Column::Kind)
+            .filter(post_reactions::Column::PostId.is_in(post_ids.iter().copied()))
+            .filter(post_reactions::Column::UserId.eq(user_id))
+            .order_by_asc(post_reactions::Column::Kind)
+            .into_tuple()
+            .all(&self.db_pool)
+            .await
+    }
+
+    async fn set_comment_vote(&self, comment_id: i32, user_id: i32, value: i16) -> Result<(), DbErr> {
+        let vote = comment_votes::ActiveModel {
+            comment_id: Set(comment_id),
+            user_id: Set(user_id),
+            value: Set(value),
+            ..Default::default()
+        };
+
+        // Voting again replaces the previous vote
+        CommentVotes::insert(vote)
+            .on_conflict(
+                OnConflict::columns([comment_votes::Column::CommentId, comment_votes::Column::UserId])
+                    .update_column(comment_votes::Column::Value)
+                    .to_owned(),
+            )
+            .exec_without_returning(&self.db_pool)
+            .await
+            .map(|_| ())
+    }
+
+    async fn remove_comment_vote(&self, comment_id: i32, user_id: i32) -> Result<u64, DbErr> {
+        let result = CommentVotes::delete_many()
+            .filter(comment_votes::Column::CommentId.eq(comment_id))
+            .filter(comment_votes::Column::UserId.eq(user_id))
+            .exec(&self.db_pool)
+            .await?;
+
+        Ok(result.rows_affected)
+    }
+
+    async fn count_comment_votes(&self, comment_ids: &[i32]) -> Result<Vec<(i32, i64, i64)>, DbErr> {
+        if comment_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        CommentVotes::find()
+            .select_only()
+            .column(comment_votes::Column::CommentId)
+            .column_as(Expr::cust("COUNT(*) FILTER (WHERE value > 0)"), "upvotes")
+            .column_as(Expr::cust("COUNT(*) FILTER (WHERE value < 0)"), "downvotes")
+            .filter(comment_votes::Column::CommentId.is_in(comment_ids.iter().copied()))
+            .group_by(comment_votes::Column::CommentId)
+            .into_tuple()
+            .all(&self.db_pool)
+            .await
+    }
+
+    async fn find_user_comment_votes(&self, comment_ids: &[i32], user_id: i32) -> Result<Vec<(i32, i16)>, DbErr> {
+        if comment_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        CommentVotes::find()
+            .select_only()
+            .column(comment_votes::Column::CommentId)
+            .column(comment_votes::Column::Value)
+            .filter(comment_votes::Column::CommentId.is_in(comment_ids.iter().copied()))
+            .filter(comment_votes::Column::UserId.eq(user_id))
+            .into_tuple()
+            .all(&self.db_pool)
+            .await
+    }
+}
@@ -0,0 +1,202 @@
+use async_trait::async_trait;
+use sea_orm::sea_query::{Expr, OnConflict};
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, Condition, ConnectionTrait, DatabaseConnection, DbBackend, DbErr,
+    EntityTrait, FromQueryResult, JoinType, QueryFilter, QueryOrder, QuerySelect, RelationTrait,
+    Set, Statement, TransactionTrait,
+};
+
+use crate::abstract_trait::TagRepositoryTrait;
+use crate::entities::{post_tags, posts, prelude::{PostTags, Tags}, sea_orm_active_enums::PostStatus, tags};
+use crate::utils::generate_slug;
+
+pub struct TagRepository {
+    db_pool: DatabaseConnection,
+}
+
+impl TagRepository {
+    pub fn new(db_pool: DatabaseConnection) -> Self {
+        Self { db_pool }
+    }
+}
+
+#[derive(FromQueryResult)]
+struct TagPostCount {
+    id: i32,
+    name: String,
+    slug: String,
+    post_count: i64,
+}
+
+// Names of a post's current tags, read on the caller's connection so revisions see the synced tags
+pub(crate) async fn post_tag_names<C: ConnectionTrait>(db: &C, post_id: i32) -> Result<Vec<String>, DbErr> {
+    let links = PostTags::find()
+        .filter(post_tags::Column::PostId.eq(post_id))
+        .find_also_related(Tags)
+        .order_by_asc(tags::Column::Name)
+        .all(db)
+        .await?;
+
+    Ok(links.into_iter().filter_map(|(_, tag)| tag.map(|tag| tag.name)).collect())
+}
+
+// Replaces the tags of a post, creating tags that do not exist yet.
+// Runs on the caller's connection so it joins the post's transaction.
+pub(crate) async fn sync_post_tags<C: ConnectionTrait>(
+    db: &C,
+    post_id: i32,
+    names: &[String],
+) -> Result<(), DbErr> {
+    PostTags::delete_many()
+        .filter(post_tags::Column::PostId.eq(post_id))
+        .exec(db)
+        .await?;
+
+    let mut tag_ids = Vec::new();
+
+    for name in names {
+        let name = name.trim();
+        let slug = generate_slug(name);
+
+        if slug.is_empty() {
+            continue;
+        }
+
+        Tags::insert(tags::ActiveModel {
+            name: Set(name.to_string()),
+            slug: Set(slug.clone()),
+            ..Default::default()
+        })
+        .on_conflict(OnConflict::column(tags::Column::Slug).do_nothing().to_owned())
+        .exec_without_returning(db)
+        .await?;
+
+        let tag = Tags::find()
+            .filter(tags::Column::Slug.eq(slug.as_str()))
+            .one(db)
+            .await?
+            .ok_or(DbErr::RecordNotFound(format!("Tag {} not found", slug)))?;
+
+        if !tag_ids.contains(&tag.id) {
+            tag_ids.push(tag.id);
+        }
+    }
+
+    if tag_ids.is_empty() {
+        return Ok(());
+    }
+
+    PostTags::insert_many(tag_ids.into_iter().map(|tag_id| post_tags::ActiveModel {
+        post_id: Set(post_id),
+        tag_id: Set(tag_id),
+    }))
+    .exec_without_returning(db)
+    .await?;
+
+    Ok(())
+}
+
+#[async_trait]
+impl TagRepositoryTrait for TagRepository {
+    async fn find_all_with_post_count(&self) -> Result<Vec<(tags::Model, i64)>, DbErr> {
+        let rows = Tags::find()
+            .select_only()
+            .column(tags::Column::Id)
+            .column(tags::Column::Name)
+            .column(tags::Column::Slug)
+            .column_as(posts::Column::Id.count(), "post_count")
+            .join(JoinType::LeftJoin, tags::Relation::PostTags.def())
+            .join(
+                JoinType::LeftJoin,
+                post_tags::Relation::Posts
+                    .def()
+                    .on_condition(|_, right| {
+                        Condition::all()
+                            .add(Expr::col((right.clone(), posts::Column::Status)).eq(PostStatus::Published))
+                            .add(Expr::col((right, posts::Column::DeletedAt)).is_null())
+                    }),
+            )
+            .group_by(tags::Column::Id)
+            .order_by_asc(tags::Column::Name)
+            .into_model::<TagPostCount>()
+            .all(&self.db_pool)
+            .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| {
+                let tag = tags::Model { id: row.id, name: row.name, slug: row.slug };
+                (tag, row.post_count)
+            })
+            .collect())
+    }
+
+    async fn find_by_id(&self, id: i32) -> Result<Option<tags::Model>, DbErr> {
+        Tags::find_by_id(id).one(&self.db_pool).await
+    }
+
+    async fn find_by_slug(&self, slug: &str) -> Result<Option<tags::Model>, DbErr> {
+        Tags::find()
+            .filter(tags::Column::Slug.eq(slug))
+            .one(&self.db_pool)
+            .await
+    }
+
+    async fn find_by_posts(&self, post_ids: &[i32]) -> Result<Vec<(i32, tags::Model)>, DbErr> {
+        if post_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let links = PostTags::find()
+            .filter(post_tags::Column::PostId.is_in(post_ids.iter().copied()))
+            .find_also_related(Tags)
+            .order_by_asc(tags::Column::Name)
+            .all(&self.db_pool)
+            .await?;
+
+        Ok(links
+            .into_iter()
+            .filter_map(|(link, tag)| tag.map(|tag| (link.post_id, tag)))
+            .collect())
+    }
+
+    async fn rename(&self, id: i32, name: &str) -> Result<tags::Model, DbErr> {
+        let mut tag: tags::ActiveModel = Tags::find_by_id(id)
+            .one(&self.db_pool)
+            .await?
+            .ok_or(DbErr::RecordNotFound("Tag not found".to_string()))?
+            .into();
+
+        tag.name = Set(name.trim().to_string());
+        tag.slug = Set(generate_slug(name.trim()));
+
+        tag.update(&self.db_pool).await
+    }
+
+    async fn merge(&self, source_id: i32, target_id: i32) -> Result<tags::Model, DbErr> {
+        let txn = self.db_pool.begin().await?;
+
+        let target = Tags::find_by_id(target_id)
+            .one(&txn)
+            .await?
+            .ok_or(DbErr::RecordNotFound("Target tag not found".to_string()))?;
+
+        txn.execute(Statement::from_sql_and_values(
+            DbBackend::Postgres,
+            r#"
+            INSERT INTO post_tags (post_id, tag_id)
+            SELECT post_id, $1 FROM post_tags WHERE tag_id = $2
+            ON CONFLICT DO NOTHING
+            "#,
+            [target_id.into(), source_id.into()],
+        ))
+        .await?;
+
+        // Removing the source tag cascades to its remaining post_tags rows
+        Tags::delete_by_id(source_id).exec(&txn).await?;
+
+        txn.commit().await?;
+
+        Ok(target)
+    }
+}
@@ -1,9 +1,11 @@
-use sea_orm::{prelude::*, Set};
+use sea_orm::{prelude::*, QueryOrder, Set};
+use sea_orm::sea_query::Query;
 use sea_orm::{DatabaseConnection, DbErr};
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 use crate::abstract_trait::UserRepositoryTrait;
 use crate::domain::{CreateUserRequest, UpdateUserRequest};
-use crate::entities::{users, prelude::Users}; 
+use crate::entities::{posts, users, prelude::{Posts, Users}};
 
 pub struct UserRepository {
     db_pool: DatabaseConnection,
@@ -17,6 +19,7 @@ impl UserRepository {
 
 #[async_trait]
 impl UserRepositoryTrait for UserRepository {
+    // Trashed accounts keep their email reserved until they are purged
     async fn find_by_email_exists(&self, email: &str) -> Result<bool, DbErr> {
         let user_count = Users::find()
             .filter(users::Column::Email.eq(email))
@@ -38,14 +41,14 @@ impl UserRepositoryTrait for UserRepository {
     }
 
     async fn find_by_email(&self, email: &str) -> Result<Option<users::Model>, DbErr> {
-        Users::find()
+        Users::find_active()
             .filter(users::Column::Email.eq(email))
             .one(&self.db_pool)
             .await
     }
 
     async fn find_by_id(&self, id: i32) -> Result<Option<users::Model>, DbErr> {
-        Users::find_by_id(id)
+        Users::find_active_by_id(id)
             .one(&self.db_pool)
             .await
     }
@@ -56,7 +59,7 @@ impl UserRepositoryTrait for UserRepository {
             None => return Err(DbErr::Custom("User ID is required".to_string())), 
         };
     
-        let mut user: users::ActiveModel = Users::find_by_id(id)
+        let mut user: users::ActiveModel = Users::find_active_by_id(id)
             .one(&self.db_pool)
             .await?
             .ok_or(DbErr::Custom("User not found".to_string()))?
@@ -81,13 +84,49 @@ impl UserRepositoryTrait for UserRepository {
     
 
     async fn delete_user(&self, email: &str) -> Result<(), DbErr> {
-        let user: users::ActiveModel = Users::find()
+        let mut user: users::ActiveModel = Users::find_active()
             .filter(users::Column::Email.eq(email))
             .one(&self.db_pool)
             .await?
-            .ok_or(DbErr::Custom("User not found".to_string()))?
+            .ok_or(DbErr::RecordNotFound("User not found".to_string()))?
             .into();
 
-        user.delete(&self.db_pool).await.map(|_| ())
+        user.deleted_at = Set(Some(Utc::now().into()));
+        user.update(&self.db_pool).await.map(|_| ())
+    }
+
+    async fn find_trashed_users(&self) -> Result<Vec<users::Model>, DbErr> {
+        Users::find()
+            .filter(users::Column::DeletedAt.is_not_null())
+            .order_by_desc(users::Column::DeletedAt)
+            .all(&self.db_pool)
+            .await
+    }
+
+    async fn restore_user(&self, id: i32) -> Result<users::Model, DbErr> {
+        let mut user: users::ActiveModel = Users::find_by_id(id)
+            .filter(users::Column::DeletedAt.is_not_null())
+            .one(&self.db_pool)
+            .await?
+            .ok_or(DbErr::RecordNotFound("User not found in trash".to_string()))?
+            .into();
+
+        user.deleted_at = Set(None);
+        user.update(&self.db_pool).await
+    }
+
+    async fn purge_trashed_users(&self, deleted_before: DateTime<Utc>) -> Result<u64, DbErr> {
+        // Posts cascade with their author, so accounts that still own posts are kept
+        let result = Users::delete_many()
+            .filter(users::Column::DeletedAt.lt(deleted_before))
+            .filter(
+                users::Column::Id.not_in_subquery(
+                    Query::select().column(posts::Column::UserId).from(Posts).to_owned(),
+                ),
+            )
+            .exec(&self.db_pool)
+            .await?;
+
+        Ok(result.rows_affected)
     }
 }
@@ -71,6 +71,6 @@ impl AuthServiceTrait for AuthService {
     }
 
     fn verify_token(&self, token: &str) -> Result<i64, AppError> {
-        return self.jwt_config.verify_token(token).map_err(AppError::from);
+        self.jwt_config.verify_token(token)
     }
 }
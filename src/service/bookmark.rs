@@ -0,0 +1,111 @@
+use async_trait::async_trait;
+
+use crate::{
+    abstract_trait::{BookmarkServiceTrait, DynBookmarkRepository, DynPostsRepository, DynPostsService},
+    domain::{ApiResponse, BookmarkFolderResponse, BookmarkListQuery, BookmarkPageResponse, BookmarkResponse, ErrorResponse, SaveBookmarkRequest, MAX_BOOKMARK_FOLDER_LENGTH},
+    entities::sea_orm_active_enums::PostStatus,
+    utils::AppError,
+};
+
+pub struct BookmarkService {
+    repository: DynBookmarkRepository,
+    post_repository: DynPostsRepository,
+    post_service: DynPostsService,
+}
+
+// Blank values are stored as NULL so "" never shows up as a folder of its own
+fn non_blank(value: Option<&str>) -> Option<String> {
+    value.map(str::trim).filter(|value| !value.is_empty()).map(str::to_string)
+}
+
+impl BookmarkService {
+    pub fn new(repository: DynBookmarkRepository, post_repository: DynPostsRepository, post_service: DynPostsService) -> Self {
+        Self { repository, post_repository, post_service }
+    }
+}
+
+#[async_trait]
+impl BookmarkServiceTrait for BookmarkService {
+    async fn save_bookmark(&self, user_id: i32, post_id: i32, input: &SaveBookmarkRequest) -> Result<Option<ApiResponse<BookmarkResponse>>, ErrorResponse> {
+        let folder = non_blank(input.folder.as_deref());
+        if folder.as_ref().is_some_and(|folder| folder.chars().count() > MAX_BOOKMARK_FOLDER_LENGTH) {
+            return Err(ErrorResponse::from(AppError::ValidationError(format!(
+                "folder must be at most {} characters",
+                MAX_BOOKMARK_FOLDER_LENGTH
+            ))));
+        }
+
+        let Some(post) = self.post_repository.get_post(post_id)
+            .await
+            .map_err(AppError::from).map_err(ErrorResponse::from)?
+            .filter(|post| post.status == PostStatus::Published)
+        else {
+            return Ok(None);
+        };
+
+        let bookmark = self.repository.save(user_id, post_id, folder, non_blank(input.note.as_deref()))
+            .await
+            .map_err(AppError::from).map_err(ErrorResponse::from)?;
+
+        let mut posts = self.post_service.to_responses(vec![post], Some(user_id)).await?;
+
+        Ok(Some(ApiResponse {
+            status: "success".to_string(),
+            message: "Bookmark saved successfully".to_string(),
+            data: BookmarkResponse::new(bookmark, posts.remove(0)),
+        }))
+    }
+
+    async fn remove_bookmark(&self, user_id: i32, post_id: i32) -> Result<Option<ApiResponse<()>>, ErrorResponse> {
+        let removed = self.repository.delete(user_id, post_id)
+            .await
+            .map_err(AppError::from).map_err(ErrorResponse::from)?;
+
+        if removed == 0 {
+            return Ok(None);
+        }
+
+        Ok(Some(ApiResponse {
+            status: "success".to_string(),
+            message: "Bookmark removed successfully".to_string(),
+            data: (),
+        }))
+    }
+
+    async fn get_bookmarks(&self, user_id: i32, query: &BookmarkListQuery) -> Result<ApiResponse<BookmarkPageResponse>, ErrorResponse> {
+        let page = query.page();
+        let per_page = query.per_page();
+        let folder = non_blank(query.folder.as_deref());
+
+        let (rows, total) = self.repository.find_page(user_id, folder.as_deref(), page, per_page)
+            .await
+            .map_err(AppError::from).map_err(ErrorResponse::from)?;
+
+        let (bookmarks, posts): (Vec<_>, Vec<_>) = rows.into_iter().unzip();
+        let posts = self.post_service.to_responses(posts, Some(user_id)).await?;
+
+        Ok(ApiResponse {
+            status: "success".to_string(),
+            message: "Bookmarks retrieved successfully".to_string(),
+            data: BookmarkPageResponse {
+                bookmarks: bookmarks.into_iter().zip(posts).map(|(bookmark, post)| BookmarkResponse::new(bookmark, post)).collect(),
+                page,
+                per_page,
+                total,
+                total_pages: total.div_ceil(per_page),
+            },
+        })
+    }
+
+    async fn get_folders(&self, user_id: i32) -> Result<ApiResponse<Vec<BookmarkFolderResponse>>, ErrorResponse> {
+        let folders = self.repository.find_folders(user_id)
+            .await
+            .map_err(AppError::from).map_err(ErrorResponse::from)?;
+
+        Ok(ApiResponse {
+            status: "success".to_string(),
+            message: "Bookmark folders retrieved successfully".to_string(),
+            data: folders.into_iter().map(|(folder, count)| BookmarkFolderResponse { folder, count }).collect(),
+        })
+    }
+}
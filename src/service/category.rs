@@ -1,5 +1,6 @@
-use crate::{abstract_trait::{CategoryServiceTrait, DynCategoryRepository}, domain::{ApiResponse, CategoryResponse, CreateCategoryRequest, UpdateCategoryRequest, ErrorResponse}, utils::AppError};
+use crate::{abstract_trait::{CategoryServiceTrait, DynCategoryRepository}, domain::{ApiResponse, CategoryDeletionResponse, CategoryMergeResponse, CategoryResponse, CategoryTreeResponse, CreateCategoryRequest, DeleteCategoryQuery, MergeCategoriesRequest, UpdateCategoryRequest, ErrorResponse, SlugLookup, SlugRedirectResponse}, utils::AppError};
 use async_trait::async_trait;
+use sea_orm::DbErr;
 
 pub struct CategoryService {
     repository: DynCategoryRepository
@@ -9,6 +10,20 @@ impl CategoryService {
     pub fn new(repository: DynCategoryRepository) -> Self {
         Self { repository }
     }
+
+    async fn build_tree(&self) -> Result<Vec<CategoryTreeResponse>, ErrorResponse> {
+        let categories = self.repository.find_all().await.map_err(AppError::from).map_err(ErrorResponse::from)?;
+
+        Ok(CategoryTreeResponse::build(categories))
+    }
+}
+
+// The repository reports broken rules, like a parent cycle or a non-empty category, as custom errors
+fn category_error(e: DbErr) -> ErrorResponse {
+    match e {
+        DbErr::Custom(message) => ErrorResponse::from(AppError::ValidationError(message)),
+        e => ErrorResponse::from(AppError::from(e)),
+    }
 }
 
 #[async_trait]
@@ -16,7 +31,7 @@ impl CategoryServiceTrait for CategoryService {
     async fn get_categories(&self) -> Result<ApiResponse<Vec<CategoryResponse>>, ErrorResponse> {
         let categories = self.repository.find_all().await.map_err(AppError::from).map_err(ErrorResponse::from)?;
 
-        let category_responses: Vec<CategoryResponse> = categories.into_iter().map(|category| CategoryResponse::from(category)).collect();
+        let category_responses: Vec<CategoryResponse> = categories.into_iter().map(CategoryResponse::from).collect();
     
        
         Ok(ApiResponse {
@@ -26,6 +41,24 @@ impl CategoryServiceTrait for CategoryService {
         })
     }
 
+    async fn get_category_tree(&self) -> Result<ApiResponse<Vec<CategoryTreeResponse>>, ErrorResponse> {
+        Ok(ApiResponse {
+            status: "success".to_string(),
+            message: "Category tree retrieved successfully".to_string(),
+            data: self.build_tree().await?,
+        })
+    }
+
+    async fn get_category_subtree(&self, id: i32) -> Result<Option<ApiResponse<CategoryTreeResponse>>, ErrorResponse> {
+        let subtree = CategoryTreeResponse::find(self.build_tree().await?, id);
+
+        Ok(subtree.map(|subtree| ApiResponse {
+            status: "success".to_string(),
+            message: "Category tree retrieved successfully".to_string(),
+            data: subtree,
+        }))
+    }
+
     async fn get_category(&self, id: i32) -> Result<Option<ApiResponse<CategoryResponse>>, ErrorResponse> {
         let category = self.repository.find_by_id(id).await.map_err(AppError::from).map_err(ErrorResponse::from)?;
         
@@ -40,18 +73,47 @@ impl CategoryServiceTrait for CategoryService {
         }
     }
 
+    async fn get_category_by_slug(&self, slug: &str) -> Result<Option<SlugLookup<ApiResponse<CategoryResponse>>>, ErrorResponse> {
+        let category = self.repository.find_by_slug(slug).await.map_err(AppError::from).map_err(ErrorResponse::from)?;
+
+        if let Some(category) = category {
+            return Ok(Some(SlugLookup::Found(ApiResponse {
+                status: "success".to_string(),
+                message: "Category retrieved successfully".to_string(),
+                data: CategoryResponse::from(category),
+            })));
+        }
+
+        let canonical = self.repository.find_slug_redirect(slug).await
+            .map_err(AppError::from).map_err(ErrorResponse::from)?;
+
+        Ok(canonical.map(|canonical| SlugLookup::Moved(SlugRedirectResponse {
+            location: format!("/api/categories/slug/{}", canonical),
+            slug: canonical,
+        })))
+    }
+
     async fn create_category(&self, input: &CreateCategoryRequest) -> Result<ApiResponse<CategoryResponse>, ErrorResponse> {
-        let category = self.repository.create(input).await.map_err(AppError::from).map_err(ErrorResponse::from)?;
+        let category = self.repository.create(input).await.map_err(category_error)?;
 
         Ok(ApiResponse {
             status: "success".to_string(),
             message: "Category created successfully".to_string(),
-            data: CategoryResponse::from(category),
+            data: CategoryResponse::from((category, 0)),
         })
     }
 
     async fn update_category(&self, input: &UpdateCategoryRequest) -> Result<Option<ApiResponse<CategoryResponse>>, ErrorResponse> {
-        let category = self.repository.update(input).await.map_err(AppError::from).map_err(ErrorResponse::from)?;
+        let category = match self.repository.update(input).await {
+            Ok(category) => category,
+            Err(DbErr::RecordNotFound(_)) => return Ok(None),
+            Err(e) => return Err(category_error(e)),
+        };
+
+        // Read back through the aggregate query so the response carries the post count
+        let Some(category) = self.repository.find_by_id(category.id).await.map_err(AppError::from).map_err(ErrorResponse::from)? else {
+            return Ok(None);
+        };
 
         Ok(Some(ApiResponse {
             status: "success".to_string(),
@@ -60,13 +122,39 @@ impl CategoryServiceTrait for CategoryService {
         }))
     }
 
-    async fn delete_category(&self, id: i32) -> Result<ApiResponse<()>, ErrorResponse> {
-        self.repository.delete(id).await.map_err(AppError::from).map_err(ErrorResponse::from)?;
+    async fn merge_categories(&self, input: &MergeCategoriesRequest) -> Result<Option<ApiResponse<CategoryMergeResponse>>, ErrorResponse> {
+        let report = match self.repository.merge(input).await {
+            Ok(report) => report,
+            Err(DbErr::RecordNotFound(_)) => return Ok(None),
+            Err(e) => return Err(category_error(e)),
+        };
 
-        Ok(ApiResponse {
+        let message = if report.dry_run {
+            "Dry run, no categories were merged"
+        } else {
+            "Categories merged successfully"
+        };
+
+        Ok(Some(ApiResponse {
+            status: "success".to_string(),
+            message: message.to_string(),
+            data: report,
+        }))
+    }
+
+    async fn delete_category(&self, id: i32, query: &DeleteCategoryQuery) -> Result<Option<ApiResponse<CategoryDeletionResponse>>, ErrorResponse> {
+        let strategy = query.strategy.unwrap_or_default();
+
+        let affected_posts = match self.repository.delete(id, strategy, query.target_id).await {
+            Ok(affected_posts) => affected_posts,
+            Err(DbErr::RecordNotFound(_)) => return Ok(None),
+            Err(e) => return Err(category_error(e)),
+        };
+
+        Ok(Some(ApiResponse {
             status: "success".to_string(),
             message: "Category deleted successfully".to_string(),
-            data: (),
-        })
+            data: CategoryDeletionResponse { strategy, affected_posts },
+        }))
     }
 }
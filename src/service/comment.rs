@@ -1,24 +1,321 @@
-use crate::{abstract_trait::{CommentServiceTrait, DynCommentRepository, }, domain::{ApiResponse, CommentResponse, CreateCommentRequest, ErrorResponse, UpdateCommentRequest},  utils::AppError};
+use std::collections::{hash_map::Entry, HashMap, HashSet};
+
+use crate::{abstract_trait::{CommentServiceTrait, DynCategoryRepository, DynCommentRepository, DynNotificationService, DynPostsRepository, DynReactionRepository, DynUserRepository}, config::CommentConfig, domain::{ApiResponse, CommentEdit, CommentModerationResponse, CommentPageResponse, CommentResponse, CommentRevisionResponse, CommentThreadResponse, CreateCommentRequest, ErrorResponse, FlatCommentResponse, ModerateCommentsRequest, NewNotification, PostCommentsQuery, UpdateCommentRequest, VoteDirection}, entities::{comments, posts, sea_orm_active_enums::{CommentStatus, NotificationKind, UserRole}}, utils::AppError};
 use async_trait::async_trait;
+use chrono::{Duration, Utc};
+use sea_orm::DbErr;
+use tracing::error;
 
 pub struct CommentService {
     repository: DynCommentRepository,
+    post_repository: DynPostsRepository,
+    category_repository: DynCategoryRepository,
+    user_repository: DynUserRepository,
+    reaction_repository: DynReactionRepository,
+    notification_service: DynNotificationService,
+    config: CommentConfig,
+}
+
+const MAX_MENTIONS: usize = 10;
+
+// Users have no handle of their own, so "@jane@example.com" mentions the user with that email
+fn mentioned_emails(text: &str) -> Vec<String> {
+    let mut seen = HashSet::new();
+
+    text.split_whitespace()
+        .filter_map(|word| word.strip_prefix('@'))
+        .map(|email| email.trim_end_matches(|c: char| !c.is_alphanumeric()))
+        .filter(|email| email.contains('@'))
+        .filter(|email| seen.insert(email.to_string()))
+        .take(MAX_MENTIONS)
+        .map(str::to_string)
+        .collect()
+}
+
+// Vote counts for a batch of comments, plus the votes of the viewer when one is known
+struct VoteTally {
+    totals: HashMap<i32, (i64, i64)>,
+    mine: HashMap<i32, i16>,
+}
+
+impl VoteTally {
+    fn apply(&self, comment: &mut CommentResponse) {
+        let (upvotes, downvotes) = self.totals.get(&comment.id).copied().unwrap_or_default();
+        let my_vote = self.mine.get(&comment.id).copied().map(VoteDirection::from_value);
+        comment.set_votes(upvotes, downvotes, my_vote);
+    }
+}
+
+// Readers see approved comments, authors also see their own comments while they wait for a moderator
+fn visible_to(comment: &comments::Model, viewer_id: Option<i32>) -> bool {
+    comment.deleted_at.is_none()
+        && match comment.status {
+            CommentStatus::Approved => true,
+            CommentStatus::Pending => viewer_id.is_some() && comment.user_id == viewer_id,
+            CommentStatus::Rejected | CommentStatus::Spam => false,
+        }
 }
 
 impl CommentService {
-    pub fn new(repository: DynCommentRepository) -> Self {
-        Self { repository }
+    pub fn new(
+        repository: DynCommentRepository,
+        post_repository: DynPostsRepository,
+        category_repository: DynCategoryRepository,
+        user_repository: DynUserRepository,
+        reaction_repository: DynReactionRepository,
+        notification_service: DynNotificationService,
+        config: CommentConfig,
+    ) -> Self {
+        Self { repository, post_repository, category_repository, user_repository, reaction_repository, notification_service, config }
+    }
+
+    // Runs once a comment is visible to readers: the replied to author, the post author and mentioned
+    // users hear about it, each at most once and with the most specific kind
+    async fn notify_published(&self, comment: &comments::Model, post: &posts::Model) -> Result<(), ErrorResponse> {
+        let mut notified: HashSet<i32> = comment.user_id.into_iter().collect();
+        let mut notifications = Vec::new();
+        let notification = |user_id: i32, kind: NotificationKind, message: String| NewNotification {
+            user_id,
+            actor_id: comment.user_id,
+            kind,
+            post_id: Some(post.id),
+            comment_id: Some(comment.id),
+            message,
+        };
+
+        if let Some(parent_id) = comment.parent_id {
+            let parent = self.repository.find_by_id(parent_id).await.map_err(AppError::from).map_err(ErrorResponse::from)?;
+            if let Some(parent_author) = parent.and_then(|parent| parent.user_id) {
+                if notified.insert(parent_author) {
+                    notifications.push(notification(
+                        parent_author,
+                        NotificationKind::Reply,
+                        format!("{} replied to your comment on \"{}\"", comment.user_name_comment, post.title),
+                    ));
+                }
+            }
+        }
+
+        if notified.insert(post.user_id) {
+            notifications.push(notification(
+                post.user_id,
+                NotificationKind::Comment,
+                format!("{} commented on \"{}\"", comment.user_name_comment, post.title),
+            ));
+        }
+
+        for email in mentioned_emails(&comment.comment) {
+            let user = self.user_repository.find_by_email(&email).await.map_err(AppError::from).map_err(ErrorResponse::from)?;
+            if let Some(user) = user.filter(|user| notified.insert(user.id)) {
+                notifications.push(notification(
+                    user.id,
+                    NotificationKind::Mention,
+                    format!("{} mentioned you in a comment on \"{}\"", comment.user_name_comment, post.title),
+                ));
+            }
+        }
+
+        for notification in notifications {
+            self.notification_service.notify(notification).await?;
+        }
+
+        Ok(())
+    }
+
+    // Authors hear about the outcome when a moderator was involved, approved comments then notify like new ones
+    async fn send_comment_notifications(&self, comments: Vec<comments::Model>, status: CommentStatus, by_moderator: bool) -> Result<(), ErrorResponse> {
+        let outcome = match status {
+            CommentStatus::Approved => "was approved",
+            CommentStatus::Rejected => "was rejected",
+            CommentStatus::Spam => "was marked as spam",
+            CommentStatus::Pending => "is waiting for a moderator",
+        };
+        let mut posts: HashMap<i32, Option<posts::Model>> = HashMap::new();
+
+        for comment in comments {
+            if let Entry::Vacant(entry) = posts.entry(comment.id_post_comment) {
+                let post = self.post_repository.get_post(comment.id_post_comment).await.map_err(AppError::from).map_err(ErrorResponse::from)?;
+                entry.insert(post);
+            }
+            let Some(post) = posts.get(&comment.id_post_comment).and_then(Option::as_ref) else {
+                continue;
+            };
+
+            if let Some(author) = comment.user_id.filter(|_| by_moderator) {
+                self.notification_service.notify(NewNotification {
+                    user_id: author,
+                    actor_id: None,
+                    kind: NotificationKind::Moderation,
+                    post_id: Some(post.id),
+                    comment_id: Some(comment.id),
+                    message: format!("Your comment on \"{}\" {}", post.title, outcome),
+                }).await?;
+            }
+
+            if status == CommentStatus::Approved {
+                self.notify_published(&comment, post).await?;
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn tally_votes(&self, comment_ids: &[i32], viewer_id: Option<i32>) -> Result<VoteTally, ErrorResponse> {
+        let totals = self
+            .reaction_repository
+            .count_comment_votes(comment_ids)
+            .await
+            .map_err(AppError::from)
+            .map_err(ErrorResponse::from)?
+            .into_iter()
+            .map(|(comment_id, upvotes, downvotes)| (comment_id, (upvotes, downvotes)))
+            .collect();
+
+        let mine = match viewer_id {
+            Some(user_id) => self
+                .reaction_repository
+                .find_user_comment_votes(comment_ids, user_id)
+                .await
+                .map_err(AppError::from)
+                .map_err(ErrorResponse::from)?
+                .into_iter()
+                .collect(),
+            None => HashMap::new(),
+        };
+
+        Ok(VoteTally { totals, mine })
+    }
+
+    async fn with_votes(&self, mut comments: Vec<CommentResponse>, viewer_id: Option<i32>) -> Result<Vec<CommentResponse>, ErrorResponse> {
+        let ids: Vec<i32> = comments.iter().map(|comment| comment.id).collect();
+        let tally = self.tally_votes(&ids, viewer_id).await?;
+        comments.iter_mut().for_each(|comment| tally.apply(comment));
+        Ok(comments)
+    }
+
+    // None when the post does not exist or is in the trash
+    async fn find_page(
+        &self,
+        post_id: i32,
+        viewer_id: Option<i32>,
+        query: &PostCommentsQuery,
+    ) -> Result<Option<CommentPageResponse<CommentThreadResponse>>, ErrorResponse> {
+        let post = self.post_repository.get_post(post_id).await.map_err(AppError::from).map_err(ErrorResponse::from)?;
+        if post.is_none() {
+            return Ok(None);
+        }
+
+        let page = query.page();
+        let per_page = query.per_page();
+
+        // Only the threads of the requested page are loaded, visibility is applied again in `build`
+        // so a hidden comment still turns into a placeholder when it has visible replies
+        let total_threads = self
+            .repository
+            .count_threads(post_id, viewer_id)
+            .await
+            .map_err(AppError::from)
+            .map_err(ErrorResponse::from)?;
+        let comments = self
+            .repository
+            .find_thread_page(post_id, viewer_id, query.sort, (page - 1).saturating_mul(per_page), per_page)
+            .await
+            .map_err(AppError::from)
+            .map_err(ErrorResponse::from)?;
+
+        let ids: Vec<i32> = comments.iter().map(|comment| comment.id).collect();
+        let tally = self.tally_votes(&ids, viewer_id).await?;
+
+        let mut threads = CommentThreadResponse::build(comments, |comment| visible_to(comment, viewer_id));
+        CommentThreadResponse::for_each_comment(&mut threads, &mut |comment| tally.apply(comment));
+        CommentThreadResponse::sort(&mut threads, query.sort);
+
+        Ok(Some(CommentPageResponse {
+            comments: threads,
+            page,
+            per_page,
+            total_threads,
+            total_pages: total_threads.div_ceil(per_page),
+        }))
+    }
+
+    // The category of the post decides, categories without their own setting follow the global one
+    async fn initial_status(&self, post_id: i32) -> Result<CommentStatus, ErrorResponse> {
+        let post = self
+            .post_repository
+            .get_post(post_id)
+            .await
+            .map_err(AppError::from)
+            .map_err(ErrorResponse::from)?
+            .ok_or_else(|| ErrorResponse::from(AppError::ValidationError(format!("Post {} does not exist", post_id))))?;
+
+        let category = self
+            .category_repository
+            .find_by_id(post.category_id)
+            .await
+            .map_err(AppError::from)
+            .map_err(ErrorResponse::from)?;
+
+        let require_approval = category
+            .and_then(|(category, _)| category.comment_moderation)
+            .unwrap_or(self.config.require_approval);
+
+        Ok(if require_approval { CommentStatus::Pending } else { CommentStatus::Approved })
+    }
+
+    async fn check_parent(&self, input: &CreateCommentRequest, user_id: Option<i32>) -> Result<(), ErrorResponse> {
+        let Some(parent_id) = input.parent_id else {
+            return Ok(());
+        };
+
+        let comments: HashMap<i32, comments::Model> = self
+            .repository
+            .find_by_post(input.id_post_comment)
+            .await
+            .map_err(AppError::from)
+            .map_err(ErrorResponse::from)?
+            .into_iter()
+            .map(|comment| (comment.id, comment))
+            .collect();
+
+        let parent = comments
+            .get(&parent_id)
+            .filter(|parent| visible_to(parent, user_id))
+            .ok_or_else(|| {
+                ErrorResponse::from(AppError::ValidationError(format!(
+                    "Comment {} does not exist on post {}",
+                    parent_id, input.id_post_comment
+                )))
+            })?;
+
+        let mut depth = 1;
+        let mut current = parent.parent_id;
+        while let Some(id) = current {
+            depth += 1;
+            current = comments.get(&id).and_then(|comment| comment.parent_id);
+        }
+
+        if depth > self.config.max_depth {
+            return Err(ErrorResponse::from(AppError::ValidationError(format!(
+                "Replies cannot be nested more than {} levels deep",
+                self.config.max_depth
+            ))));
+        }
+
+        Ok(())
     }
 }
 
 #[async_trait]
 impl CommentServiceTrait for CommentService {
-    async fn get_comments(&self) -> Result<ApiResponse<Vec<CommentResponse>>, ErrorResponse> {
+    async fn get_comments(&self, viewer_id: Option<i32>) -> Result<ApiResponse<Vec<CommentResponse>>, ErrorResponse> {
         let comments = self.repository.find_all().await .map_err(AppError::from).map_err(ErrorResponse::from)?;
         
-        let response = comments.into_iter().map(|comment| {
-            CommentResponse::from(comment)
-        }).collect();
+        let response = comments.into_iter()
+            .filter(|comment| visible_to(comment, viewer_id))
+            .map(CommentResponse::from)
+            .collect();
+        let response = self.with_votes(response, viewer_id).await?;
         
         Ok(ApiResponse{
             status: "success".to_string(),
@@ -27,39 +324,205 @@ impl CommentServiceTrait for CommentService {
         })
     }
 
-    async fn get_comment(&self, id: i32) -> Result<Option<ApiResponse<CommentResponse>>, ErrorResponse> {
+    async fn get_comment(&self, id: i32, viewer_id: Option<i32>) -> Result<Option<ApiResponse<CommentResponse>>, ErrorResponse> {
         let comment = self.repository.find_by_id(id).await .map_err(AppError::from).map_err(ErrorResponse::from)?;
 
-        
-        
-        if let Some(comment) = comment{
-            Ok(Some(ApiResponse{
-                status: "success".to_string(),
-                message: "Comment retrieved successfully".to_string(),
-                data: CommentResponse::from(comment),
-            }))
-        }else{
-            Err(ErrorResponse::from(AppError::NotFound(format!("Comment with id {} not found", id))))
+        // Hidden comments are reported the same way as missing ones
+        match comment.filter(|comment| visible_to(comment, viewer_id)) {
+            Some(comment) => {
+                let mut response = CommentResponse::from(comment);
+                self.tally_votes(&[id], viewer_id).await?.apply(&mut response);
+
+                Ok(Some(ApiResponse{
+                    status: "success".to_string(),
+                    message: "Comment retrieved successfully".to_string(),
+                    data: response,
+                }))
+            }
+            None => Ok(None),
         }
     }
 
-    async fn create_comment(&self, input: &CreateCommentRequest) -> Result<ApiResponse<CommentResponse>, ErrorResponse> {
-        let comment = self.repository.create(input).await .map_err(AppError::from).map_err(ErrorResponse::from)?;
-        
+    async fn get_post_comment_tree(
+        &self,
+        post_id: i32,
+        viewer_id: Option<i32>,
+        query: &PostCommentsQuery,
+    ) -> Result<Option<ApiResponse<CommentPageResponse<CommentThreadResponse>>>, ErrorResponse> {
+        let Some(page) = self.find_page(post_id, viewer_id, query).await? else {
+            return Ok(None);
+        };
+
+        Ok(Some(ApiResponse {
+            status: "success".to_string(),
+            message: "Comments retrieved successfully".to_string(),
+            data: page,
+        }))
+    }
+
+    async fn get_post_comment_list(
+        &self,
+        post_id: i32,
+        viewer_id: Option<i32>,
+        query: &PostCommentsQuery,
+    ) -> Result<Option<ApiResponse<CommentPageResponse<FlatCommentResponse>>>, ErrorResponse> {
+        let Some(page) = self.find_page(post_id, viewer_id, query).await? else {
+            return Ok(None);
+        };
+
+        Ok(Some(ApiResponse {
+            status: "success".to_string(),
+            message: "Comments retrieved successfully".to_string(),
+            data: CommentPageResponse {
+                comments: CommentThreadResponse::flatten(page.comments),
+                page: page.page,
+                per_page: page.per_page,
+                total_threads: page.total_threads,
+                total_pages: page.total_pages,
+            },
+        }))
+    }
+
+    async fn create_comment(&self, input: &CreateCommentRequest, user_id: Option<i32>) -> Result<ApiResponse<CommentResponse>, ErrorResponse> {
+        let status = self.initial_status(input.id_post_comment).await?;
+        self.check_parent(input, user_id).await?;
+
+        let comment = self.repository.create(input, user_id, status).await .map_err(AppError::from).map_err(ErrorResponse::from)?;
+
+        // Notifications are best effort, a failure is logged and never undoes the comment
+        if status == CommentStatus::Approved {
+            if let Err(e) = self.send_comment_notifications(vec![comment.clone()], status, false).await {
+                error!("Failed to send notifications for comment {}: {}", comment.id, e);
+            }
+        }
+
+        let message = match status {
+            CommentStatus::Pending => "Comment created and awaiting approval",
+            _ => "Comment created successfully",
+        };
+
         Ok(ApiResponse {
             status: "success".to_string(),
-            message: "Comment created successfully".to_string(),
+            message: message.to_string(),
             data: CommentResponse::from(comment),
         })
     }
 
-    async fn update_comment(&self, input: &UpdateCommentRequest) -> Result<Option<ApiResponse<CommentResponse>>, ErrorResponse> {
-        let comment = self.repository.update(input).await.map_err(AppError::from).map_err(ErrorResponse::from)?;
-        
-        Ok(Some(ApiResponse {
+    async fn get_moderation_queue(&self, status: CommentStatus) -> Result<ApiResponse<Vec<CommentResponse>>, ErrorResponse> {
+        let comments = self.repository.find_by_status(status).await.map_err(AppError::from).map_err(ErrorResponse::from)?;
+
+        Ok(ApiResponse {
+            status: "success".to_string(),
+            message: "Moderation queue retrieved successfully".to_string(),
+            data: self.with_votes(comments.into_iter().map(CommentResponse::from).collect(), None).await?,
+        })
+    }
+
+    async fn moderate_comments(&self, input: &ModerateCommentsRequest) -> Result<ApiResponse<CommentModerationResponse>, ErrorResponse> {
+        if input.comment_ids.is_empty() {
+            return Err(ErrorResponse::from(AppError::ValidationError("comment_ids must not be empty".to_string())));
+        }
+
+        let status = CommentStatus::from(input.action);
+        let changed: Vec<comments::Model> = self
+            .repository
+            .find_by_ids(&input.comment_ids)
+            .await
+            .map_err(AppError::from)
+            .map_err(ErrorResponse::from)?
+            .into_iter()
+            .filter(|comment| comment.status != status)
+            .collect();
+
+        let updated = self
+            .repository
+            .set_status(&input.comment_ids, status)
+            .await
+            .map_err(AppError::from)
+            .map_err(ErrorResponse::from)?;
+
+        if let Err(e) = self.send_comment_notifications(changed, status, true).await {
+            error!("Failed to send moderation notifications: {}", e);
+        }
+
+        Ok(ApiResponse {
+            status: "success".to_string(),
+            message: format!("{} comments moderated", updated),
+            data: CommentModerationResponse { status, updated },
+        })
+    }
+
+    async fn update_comment(
+        &self,
+        id: i32,
+        input: &UpdateCommentRequest,
+        editor_id: i32,
+    ) -> Result<Option<CommentEdit<ApiResponse<CommentResponse>>>, ErrorResponse> {
+        if input.comment.trim().is_empty() {
+            return Err(ErrorResponse::from(AppError::ValidationError("comment must not be empty".to_string())));
+        }
+
+        let Some(comment) = self.repository.find_by_id(id).await.map_err(AppError::from).map_err(ErrorResponse::from)? else {
+            return Ok(None);
+        };
+
+        let is_moderator = self
+            .user_repository
+            .find_by_id(editor_id)
+            .await
+            .map_err(AppError::from)
+            .map_err(ErrorResponse::from)?
+            .is_some_and(|editor| matches!(editor.role, UserRole::Moderator | UserRole::Admin));
+
+        if !is_moderator {
+            if comment.user_id != Some(editor_id) {
+                return Ok(Some(CommentEdit::Forbidden("Only the author can edit this comment".to_string())));
+            }
+
+            let window = Duration::minutes(self.config.edit_window_minutes);
+            if Utc::now() > comment.created_at + window {
+                return Ok(Some(CommentEdit::Forbidden(format!(
+                    "Comments can only be edited within {} minutes of posting",
+                    self.config.edit_window_minutes
+                ))));
+            }
+        }
+
+        // Otherwise an approved comment could be swapped for text no moderator has seen
+        let status = if !is_moderator && comment.status == CommentStatus::Approved {
+            Some(self.initial_status(comment.id_post_comment).await?).filter(|status| *status == CommentStatus::Pending)
+        } else {
+            None
+        };
+
+        let comment = match self.repository.update(id, input, Some(editor_id), status).await {
+            Ok(comment) => comment,
+            Err(DbErr::RecordNotFound(_)) => return Ok(None),
+            Err(e) => return Err(ErrorResponse::from(AppError::from(e))),
+        };
+
+        let mut response = CommentResponse::from(comment);
+        self.tally_votes(&[id], Some(editor_id)).await?.apply(&mut response);
+
+        Ok(Some(CommentEdit::Edited(ApiResponse {
             status: "success".to_string(),
             message: "Comment updated successfully".to_string(),
-            data: CommentResponse::from(comment),
+            data: response,
+        })))
+    }
+
+    async fn get_comment_revisions(&self, id: i32) -> Result<Option<ApiResponse<Vec<CommentRevisionResponse>>>, ErrorResponse> {
+        let comment = self.repository.find_by_id(id).await.map_err(AppError::from).map_err(ErrorResponse::from)?;
+        if comment.is_none() {
+            return Ok(None);
+        }
+
+        let revisions = self.repository.find_revisions(id).await.map_err(AppError::from).map_err(ErrorResponse::from)?;
+
+        Ok(Some(ApiResponse {
+            status: "success".to_string(),
+            message: "Comment history retrieved successfully".to_string(),
+            data: revisions.into_iter().map(CommentRevisionResponse::from).collect(),
         }))
     }
 
@@ -73,3 +536,41 @@ impl CommentServiceTrait for CommentService {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mentioned_emails_finds_prefixed_emails() {
+        assert_eq!(
+            mentioned_emails("thanks @jane@example.com and @bob@example.org!"),
+            ["jane@example.com", "bob@example.org"]
+        );
+    }
+
+    #[test]
+    fn mentioned_emails_strips_trailing_punctuation() {
+        assert_eq!(mentioned_emails("cc @jane@example.com, @bob@example.org."), ["jane@example.com", "bob@example.org"]);
+        assert_eq!(mentioned_emails("(see @jane@example.com)"), ["jane@example.com"]);
+    }
+
+    #[test]
+    fn mentioned_emails_ignores_words_that_are_not_mentions() {
+        assert!(mentioned_emails("mail jane@example.com or ping @jane, @ alone").is_empty());
+    }
+
+    #[test]
+    fn mentioned_emails_drops_duplicates() {
+        assert_eq!(mentioned_emails("@jane@example.com @jane@example.com? @jane@example.com"), ["jane@example.com"]);
+    }
+
+    #[test]
+    fn mentioned_emails_stops_at_the_limit() {
+        let text: Vec<String> = (0..MAX_MENTIONS + 5).map(|i| format!("@user{}@example.com", i)).collect();
+        let mentions = mentioned_emails(&text.join(" "));
+
+        assert_eq!(mentions.len(), MAX_MENTIONS);
+        assert_eq!(mentions.first().map(String::as_str), Some("user0@example.com"));
+    }
+}
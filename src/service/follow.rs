@@ -0,0 +1,120 @@
+use async_trait::async_trait;
+
+use crate::{
+    abstract_trait::{DynCategoryRepository, DynFollowRepository, DynPostsRepository, DynPostsService, DynUserRepository, FollowServiceTrait},
+    domain::{ApiResponse, ErrorResponse, FeedCursor, FeedQuery, FeedResponse, FollowingResponse},
+    utils::AppError,
+};
+
+pub struct FollowService {
+    repository: DynFollowRepository,
+    user_repository: DynUserRepository,
+    category_repository: DynCategoryRepository,
+    post_repository: DynPostsRepository,
+    post_service: DynPostsService,
+}
+
+impl FollowService {
+    pub fn new(
+        repository: DynFollowRepository,
+        user_repository: DynUserRepository,
+        category_repository: DynCategoryRepository,
+        post_repository: DynPostsRepository,
+        post_service: DynPostsService,
+    ) -> Self {
+        Self { repository, user_repository, category_repository, post_repository, post_service }
+    }
+
+    fn done(message: &str) -> ApiResponse<()> {
+        ApiResponse {
+            status: "success".to_string(),
+            message: message.to_string(),
+            data: (),
+        }
+    }
+}
+
+#[async_trait]
+impl FollowServiceTrait for FollowService {
+    async fn follow_user(&self, follower_id: i32, user_id: i32) -> Result<Option<ApiResponse<()>>, ErrorResponse> {
+        if follower_id == user_id {
+            return Err(ErrorResponse::from(AppError::ValidationError("You cannot follow yourself".to_string())));
+        }
+
+        let user = self.user_repository.find_by_id(user_id).await.map_err(AppError::from).map_err(ErrorResponse::from)?;
+        if user.is_none() {
+            return Ok(None);
+        }
+
+        self.repository.follow_user(follower_id, user_id).await.map_err(AppError::from).map_err(ErrorResponse::from)?;
+
+        Ok(Some(Self::done("User followed successfully")))
+    }
+
+    async fn unfollow_user(&self, follower_id: i32, user_id: i32) -> Result<Option<ApiResponse<()>>, ErrorResponse> {
+        let removed = self.repository.unfollow_user(follower_id, user_id).await.map_err(AppError::from).map_err(ErrorResponse::from)?;
+
+        Ok((removed > 0).then(|| Self::done("User unfollowed successfully")))
+    }
+
+    async fn follow_category(&self, user_id: i32, category_id: i32) -> Result<Option<ApiResponse<()>>, ErrorResponse> {
+        let category = self.category_repository.find_by_id(category_id).await.map_err(AppError::from).map_err(ErrorResponse::from)?;
+        if category.is_none() {
+            return Ok(None);
+        }
+
+        self.repository.follow_category(user_id, category_id).await.map_err(AppError::from).map_err(ErrorResponse::from)?;
+
+        Ok(Some(Self::done("Category followed successfully")))
+    }
+
+    async fn unfollow_category(&self, user_id: i32, category_id: i32) -> Result<Option<ApiResponse<()>>, ErrorResponse> {
+        let removed = self.repository.unfollow_category(user_id, category_id).await.map_err(AppError::from).map_err(ErrorResponse::from)?;
+
+        Ok((removed > 0).then(|| Self::done("Category unfollowed successfully")))
+    }
+
+    async fn get_following(&self, user_id: i32) -> Result<ApiResponse<FollowingResponse>, ErrorResponse> {
+        let user_ids = self.repository.find_followed_user_ids(user_id).await.map_err(AppError::from).map_err(ErrorResponse::from)?;
+        let category_ids = self.repository.find_followed_category_ids(user_id).await.map_err(AppError::from).map_err(ErrorResponse::from)?;
+
+        Ok(ApiResponse {
+            status: "success".to_string(),
+            message: "Follows retrieved successfully".to_string(),
+            data: FollowingResponse { user_ids, category_ids },
+        })
+    }
+
+    async fn get_feed(&self, user_id: i32, query: &FeedQuery) -> Result<ApiResponse<FeedResponse>, ErrorResponse> {
+        let after = match query.cursor.as_deref() {
+            Some(cursor) => Some(
+                FeedCursor::decode(cursor)
+                    .ok_or_else(|| ErrorResponse::from(AppError::ValidationError("Invalid feed cursor".to_string())))?,
+            ),
+            None => None,
+        };
+
+        // One extra row tells whether another page exists without counting the whole feed
+        let limit = query.limit();
+        let mut posts = self.post_repository.get_feed_posts(user_id, after, limit + 1)
+            .await
+            .map_err(AppError::from).map_err(ErrorResponse::from)?;
+
+        let has_more = posts.len() as u64 > limit;
+        posts.truncate(limit as usize);
+
+        let next_cursor = posts
+            .last()
+            .filter(|_| has_more)
+            .and_then(|post| post.published_at.map(|published_at| FeedCursor { published_at, id: post.id }.encode()));
+
+        Ok(ApiResponse {
+            status: "success".to_string(),
+            message: "Feed retrieved successfully".to_string(),
+            data: FeedResponse {
+                posts: self.post_service.to_responses(posts, Some(user_id)).await?,
+                next_cursor,
+            },
+        })
+    }
+}
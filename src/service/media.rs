@@ -0,0 +1,159 @@
+use std::collections::HashSet;
+use std::time::{Duration, SystemTime};
+
+use async_trait::async_trait;
+use chrono::Utc;
+use tracing::{info, warn};
+
+use crate::{
+    abstract_trait::{DynImageVariantRepository, DynMediaRepository, DynStorage, MediaServiceTrait},
+    domain::{ApiResponse, ErrorResponse, MediaResponse, UpdateMediaRequest},
+    entities::media,
+    utils::AppError,
+};
+
+// Files younger than this may belong to an upload that has not recorded its media yet
+const GC_MIN_AGE: Duration = Duration::from_secs(60 * 60);
+
+pub struct MediaService {
+    repository: DynMediaRepository,
+    image_variant_repository: DynImageVariantRepository,
+    storage: DynStorage,
+}
+
+impl MediaService {
+    pub fn new(
+        repository: DynMediaRepository,
+        image_variant_repository: DynImageVariantRepository,
+        storage: DynStorage,
+    ) -> Self {
+        Self { repository, image_variant_repository, storage }
+    }
+
+    fn to_response(&self, media: media::Model) -> MediaResponse {
+        let url = self.storage.url(&media.filename);
+        MediaResponse::from_model(media, url)
+    }
+
+    // Only the uploader may change or remove a media item
+    async fn find_owned(&self, user_id: i32, id: i32) -> Result<Option<media::Model>, ErrorResponse> {
+        let media = self.repository.find_by_id(id).await.map_err(AppError::from).map_err(ErrorResponse::from)?;
+
+        Ok(media.filter(|media| media.user_id == Some(user_id)))
+    }
+}
+
+#[async_trait]
+impl MediaServiceTrait for MediaService {
+    async fn get_media(&self, user_id: Option<i32>, query: Option<&str>) -> Result<ApiResponse<Vec<MediaResponse>>, ErrorResponse> {
+        let media = self.repository.search(user_id, query).await.map_err(AppError::from).map_err(ErrorResponse::from)?;
+
+        Ok(ApiResponse {
+            status: "success".to_string(),
+            message: "Media retrieved successfully".to_string(),
+            data: media.into_iter().map(|media| self.to_response(media)).collect(),
+        })
+    }
+
+    async fn get_media_item(&self, id: i32) -> Result<Option<ApiResponse<MediaResponse>>, ErrorResponse> {
+        let media = self.repository.find_by_id(id).await.map_err(AppError::from).map_err(ErrorResponse::from)?;
+
+        Ok(media.map(|media| ApiResponse {
+            status: "success".to_string(),
+            message: "Media retrieved successfully".to_string(),
+            data: self.to_response(media),
+        }))
+    }
+
+    async fn update_media(&self, user_id: i32, input: &UpdateMediaRequest) -> Result<Option<ApiResponse<MediaResponse>>, ErrorResponse> {
+        let id = input.id.ok_or_else(|| AppError::ValidationError("Media ID is required".to_string()))?;
+
+        if self.find_owned(user_id, id).await?.is_none() {
+            return Ok(None);
+        }
+
+        let media = self.repository.update_alt_text(id, input.alt_text.trim())
+            .await
+            .map_err(AppError::from).map_err(ErrorResponse::from)?;
+
+        Ok(Some(ApiResponse {
+            status: "success".to_string(),
+            message: "Media updated successfully".to_string(),
+            data: self.to_response(media),
+        }))
+    }
+
+    async fn delete_media(&self, user_id: i32, id: i32, force: bool) -> Result<Option<ApiResponse<()>>, ErrorResponse> {
+        let Some(media) = self.find_owned(user_id, id).await? else {
+            return Ok(None);
+        };
+
+        let references = self.repository.count_references(id, &self.storage.url(&media.filename))
+            .await
+            .map_err(AppError::from).map_err(ErrorResponse::from)?;
+
+        if references > 0 && !force {
+            return Err(ErrorResponse::from(AppError::ValidationError(format!(
+                "Media is still used by {} posts or categories, pass force=true to delete it anyway",
+                references
+            ))));
+        }
+
+        // Attachments go with the row, the file itself is left for garbage collection
+        self.repository.delete(id).await.map_err(AppError::from).map_err(ErrorResponse::from)?;
+
+        Ok(Some(ApiResponse {
+            status: "success".to_string(),
+            message: "Media deleted successfully".to_string(),
+            data: (),
+        }))
+    }
+
+    async fn collect_garbage(&self) -> Result<usize, ErrorResponse> {
+        let mut referenced: HashSet<String> = self.repository.all_filenames()
+            .await
+            .map_err(AppError::from).map_err(ErrorResponse::from)?
+            .into_iter()
+            .collect();
+
+        let post_images = self.repository.all_post_images().await.map_err(AppError::from).map_err(ErrorResponse::from)?;
+        referenced.extend(post_images.iter().filter_map(|img| self.storage.key_from_url(img)));
+
+        let category_covers = self.repository.all_category_covers().await.map_err(AppError::from).map_err(ErrorResponse::from)?;
+        referenced.extend(category_covers.iter().filter_map(|img| self.storage.key_from_url(img)));
+
+        let variants = self.image_variant_repository.find_all().await.map_err(AppError::from).map_err(ErrorResponse::from)?;
+        let cutoff = Utc::now() - GC_MIN_AGE;
+        let mut keep = referenced.clone();
+
+        for variant in variants {
+            if referenced.contains(&variant.source_key) {
+                keep.insert(variant.storage_key);
+            } else if variant.created_at < cutoff {
+                self.image_variant_repository.delete(variant.id).await.map_err(AppError::from).map_err(ErrorResponse::from)?;
+            }
+        }
+
+        let objects = self.storage.list().await.map_err(AppError::from).map_err(ErrorResponse::from)?;
+        let mut removed = 0;
+
+        for (key, modified) in objects {
+            let old_enough = SystemTime::now().duration_since(modified).is_ok_and(|age| age >= GC_MIN_AGE);
+
+            if keep.contains(&key) || !old_enough {
+                continue;
+            }
+
+            match self.storage.delete(&key).await {
+                Ok(()) => removed += 1,
+                Err(e) => warn!("Failed to remove unreferenced file {}: {}", key, e),
+            }
+        }
+
+        if removed > 0 {
+            info!("Removed {} unreferenced files", removed);
+        }
+
+        Ok(removed)
+    }
+}
@@ -3,9 +3,27 @@ mod comment;
 mod posts;
 mod user;
 mod auth;
+mod post_revision;
+mod tag;
+mod upload;
+mod media;
+mod trash;
+mod reaction;
+mod bookmark;
+mod follow;
+mod notification;
 
 pub use self::category::CategoryService;
 pub use self::comment::CommentService;
 pub use self::posts::PostService;
 pub use self::user::UserService;
-pub use self::auth::AuthService;
\ No newline at end of file
+pub use self::auth::AuthService;
+pub use self::post_revision::PostRevisionService;
+pub use self::tag::TagService;
+pub use self::upload::UploadService;
+pub use self::media::MediaService;
+pub use self::trash::TrashService;
+pub use self::reaction::ReactionService;
+pub use self::bookmark::BookmarkService;
+pub use self::follow::FollowService;
+pub use self::notification::NotificationService;
@@ -0,0 +1,136 @@
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use sea_orm::Iterable;
+
+use crate::{
+    abstract_trait::{DynNotificationRepository, NotificationServiceTrait},
+    domain::{
+        ApiResponse, ErrorResponse, MarkAllReadResponse, NewNotification, NotificationListQuery, NotificationPageResponse,
+        NotificationPreferenceResponse, NotificationResponse, UnreadCountResponse, UpdateNotificationPreferencesRequest,
+    },
+    entities::sea_orm_active_enums::NotificationKind,
+    utils::AppError,
+};
+
+pub struct NotificationService {
+    repository: DynNotificationRepository,
+}
+
+impl NotificationService {
+    pub fn new(repository: DynNotificationRepository) -> Self {
+        Self { repository }
+    }
+
+    // Every kind is listed, kinds without a stored preference are enabled
+    async fn preferences(&self, user_id: i32) -> Result<Vec<NotificationPreferenceResponse>, ErrorResponse> {
+        let stored: HashMap<NotificationKind, bool> = self.repository.find_preferences(user_id)
+            .await
+            .map_err(AppError::from).map_err(ErrorResponse::from)?
+            .into_iter()
+            .collect();
+
+        Ok(NotificationKind::iter()
+            .map(|kind| NotificationPreferenceResponse { kind, enabled: stored.get(&kind).copied().unwrap_or(true) })
+            .collect())
+    }
+}
+
+#[async_trait]
+impl NotificationServiceTrait for NotificationService {
+    async fn notify(&self, input: NewNotification) -> Result<(), ErrorResponse> {
+        if input.actor_id == Some(input.user_id) {
+            return Ok(());
+        }
+
+        let enabled = self.preferences(input.user_id)
+            .await?
+            .into_iter()
+            .any(|preference| preference.kind == input.kind && preference.enabled);
+        if !enabled {
+            return Ok(());
+        }
+
+        self.repository.create(&input).await.map_err(AppError::from).map_err(ErrorResponse::from)?;
+        Ok(())
+    }
+
+    async fn get_notifications(&self, user_id: i32, query: &NotificationListQuery) -> Result<ApiResponse<NotificationPageResponse>, ErrorResponse> {
+        let page = query.page();
+        let per_page = query.per_page();
+
+        let (notifications, total) = self.repository.find_page(user_id, query.unread.unwrap_or(false), page, per_page)
+            .await
+            .map_err(AppError::from).map_err(ErrorResponse::from)?;
+        let unread = self.repository.count_unread(user_id).await.map_err(AppError::from).map_err(ErrorResponse::from)?;
+
+        Ok(ApiResponse {
+            status: "success".to_string(),
+            message: "Notifications retrieved successfully".to_string(),
+            data: NotificationPageResponse {
+                notifications: notifications.into_iter().map(NotificationResponse::from).collect(),
+                page,
+                per_page,
+                total,
+                total_pages: total.div_ceil(per_page),
+                unread,
+            },
+        })
+    }
+
+    async fn get_unread_count(&self, user_id: i32) -> Result<ApiResponse<UnreadCountResponse>, ErrorResponse> {
+        let unread = self.repository.count_unread(user_id).await.map_err(AppError::from).map_err(ErrorResponse::from)?;
+
+        Ok(ApiResponse {
+            status: "success".to_string(),
+            message: "Unread count retrieved successfully".to_string(),
+            data: UnreadCountResponse { unread },
+        })
+    }
+
+    async fn mark_read(&self, user_id: i32, id: i32) -> Result<Option<ApiResponse<NotificationResponse>>, ErrorResponse> {
+        let notification = self.repository.mark_read(user_id, id).await.map_err(AppError::from).map_err(ErrorResponse::from)?;
+
+        Ok(notification.map(|notification| ApiResponse {
+            status: "success".to_string(),
+            message: "Notification marked as read".to_string(),
+            data: NotificationResponse::from(notification),
+        }))
+    }
+
+    async fn mark_all_read(&self, user_id: i32) -> Result<ApiResponse<MarkAllReadResponse>, ErrorResponse> {
+        let updated = self.repository.mark_all_read(user_id).await.map_err(AppError::from).map_err(ErrorResponse::from)?;
+
+        Ok(ApiResponse {
+            status: "success".to_string(),
+            message: format!("{} notifications marked as read", updated),
+            data: MarkAllReadResponse { updated },
+        })
+    }
+
+    async fn get_preferences(&self, user_id: i32) -> Result<ApiResponse<Vec<NotificationPreferenceResponse>>, ErrorResponse> {
+        Ok(ApiResponse {
+            status: "success".to_string(),
+            message: "Notification preferences retrieved successfully".to_string(),
+            data: self.preferences(user_id).await?,
+        })
+    }
+
+    async fn update_preferences(
+        &self,
+        user_id: i32,
+        input: &UpdateNotificationPreferencesRequest,
+    ) -> Result<ApiResponse<Vec<NotificationPreferenceResponse>>, ErrorResponse> {
+        for preference in &input.preferences {
+            self.repository.set_preference(user_id, preference.kind, preference.enabled)
+                .await
+                .map_err(AppError::from).map_err(ErrorResponse::from)?;
+        }
+
+        Ok(ApiResponse {
+            status: "success".to_string(),
+            message: "Notification preferences updated successfully".to_string(),
+            data: self.preferences(user_id).await?,
+        })
+    }
+}
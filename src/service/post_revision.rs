@@ -0,0 +1,137 @@
+use async_trait::async_trait;
+use similar::{ChangeTag, TextDiff};
+
+use crate::{
+    abstract_trait::{DynPostRevisionRepository, DynPostsRepository, DynPostsService, PostRevisionServiceTrait},
+    domain::{
+        ApiResponse, DiffLineResponse, ErrorResponse, PostResponse, PostRevisionDiffResponse,
+        PostRevisionResponse, UpdatePostRequest,
+    },
+    utils::AppError,
+};
+
+pub struct PostRevisionService {
+    repository: DynPostRevisionRepository,
+    post_repository: DynPostsRepository,
+    post_service: DynPostsService,
+}
+
+impl PostRevisionService {
+    pub fn new(
+        repository: DynPostRevisionRepository,
+        post_repository: DynPostsRepository,
+        post_service: DynPostsService,
+    ) -> Self {
+        Self { repository, post_repository, post_service }
+    }
+
+    fn line_diff(old: &str, new: &str) -> Vec<DiffLineResponse> {
+        TextDiff::from_lines(old, new)
+            .iter_all_changes()
+            .map(|change| DiffLineResponse {
+                tag: match change.tag() {
+                    ChangeTag::Equal => "equal",
+                    ChangeTag::Delete => "delete",
+                    ChangeTag::Insert => "insert",
+                }
+                .to_string(),
+                old_line: change.old_index().map(|index| index + 1),
+                new_line: change.new_index().map(|index| index + 1),
+                content: change.value().trim_end_matches(['\r', '\n']).to_string(),
+            })
+            .collect()
+    }
+}
+
+#[async_trait]
+impl PostRevisionServiceTrait for PostRevisionService {
+    async fn get_revisions(&self, post_id: i32) -> Result<ApiResponse<Vec<PostRevisionResponse>>, ErrorResponse> {
+        let revisions = self.repository.find_by_post(post_id)
+            .await
+            .map_err(AppError::from).map_err(ErrorResponse::from)?;
+
+        Ok(ApiResponse {
+            status: "success".to_string(),
+            message: "Revisions retrieved successfully".to_string(),
+            data: revisions.into_iter().map(PostRevisionResponse::from).collect(),
+        })
+    }
+
+    async fn diff_revisions(
+        &self,
+        post_id: i32,
+        from_revision: i32,
+        to_revision: i32,
+    ) -> Result<Option<ApiResponse<PostRevisionDiffResponse>>, ErrorResponse> {
+        let from = self.repository.find_by_id(post_id, from_revision)
+            .await
+            .map_err(AppError::from).map_err(ErrorResponse::from)?;
+
+        let to = self.repository.find_by_id(post_id, to_revision)
+            .await
+            .map_err(AppError::from).map_err(ErrorResponse::from)?;
+
+        let (Some(from), Some(to)) = (from, to) else {
+            return Ok(None);
+        };
+
+        Ok(Some(ApiResponse {
+            status: "success".to_string(),
+            message: "Revision diff computed successfully".to_string(),
+            data: PostRevisionDiffResponse {
+                post_id,
+                from_revision: from.id,
+                to_revision: to.id,
+                title: Self::line_diff(&from.title, &to.title),
+                body: Self::line_diff(&from.body, &to.body),
+                img_changed: from.img != to.img,
+                category_changed: from.category_id != to.category_id,
+            },
+        }))
+    }
+
+    async fn restore_revision(
+        &self,
+        post_id: i32,
+        revision_id: i32,
+        editor_id: i32,
+    ) -> Result<Option<ApiResponse<PostResponse>>, ErrorResponse> {
+        let revision = self.repository.find_by_id(post_id, revision_id)
+            .await
+            .map_err(AppError::from).map_err(ErrorResponse::from)?;
+
+        let post = self.post_repository.get_post(post_id)
+            .await
+            .map_err(AppError::from).map_err(ErrorResponse::from)?;
+
+        let (Some(revision), Some(post)) = (revision, post) else {
+            return Ok(None);
+        };
+
+        // Restoring is an ordinary update, so it shows up in the history as a new revision.
+        // Older revisions carry no format or tags, leaving the post's current ones in place.
+        let tags = revision.tags.and_then(|tags| serde_json::from_value(tags).ok());
+
+        let request = UpdatePostRequest {
+            post_id: Some(post.id),
+            title: revision.title,
+            body: revision.body,
+            body_format: revision.body_format,
+            img: revision.img,
+            category_id: revision.category_id,
+            status: None,
+            publish_at: None,
+            tags,
+            media_ids: None,
+            editor_id: Some(editor_id),
+        };
+
+        let post = self.post_service.update_post(&request).await?;
+
+        Ok(Some(ApiResponse {
+            status: "success".to_string(),
+            message: format!("Post restored to revision {}", revision_id),
+            data: post.data,
+        }))
+    }
+}
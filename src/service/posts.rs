@@ -1,26 +1,215 @@
-use crate::{abstract_trait::{DynPostsRepository, PostsServiceTrait}, domain::{ApiResponse, CreatePostRequest, ErrorResponse, PostRelationResponse, PostResponse, UpdatePostRequest}, utils::AppError};
+use std::collections::{HashMap, HashSet};
+
+use crate::{abstract_trait::{DynBookmarkRepository, DynCategoryRepository, DynCommentRepository, DynCommentService, DynImageVariantRepository, DynMediaRepository, DynPostsRepository, DynReactionRepository, DynStorage, DynTagRepository, DynUserRepository, PostsServiceTrait}, domain::{ApiResponse, CategoryResponse, CreatePostRequest, ErrorResponse, ImageVariantResponse, MediaResponse, PostAggregateResponse, PostListQuery, PostRelationQuery, PostResponse, ReactionCountResponse, SlugLookup, SlugRedirectResponse, TagResponse, UpdatePostRequest, UserResponse}, entities::{posts, sea_orm_active_enums::{PostStatus, ReactionKind, UserRole}}, utils::AppError};
 use async_trait::async_trait;
+use chrono::{DateTime, FixedOffset, Utc};
+
+// Posts rendered per query while filling in missing HTML
+const BODY_HTML_BATCH_SIZE: u64 = 100;
 
 pub struct PostService {
     repository: DynPostsRepository,
+    tag_repository: DynTagRepository,
+    image_variant_repository: DynImageVariantRepository,
+    media_repository: DynMediaRepository,
+    comment_repository: DynCommentRepository,
+    user_repository: DynUserRepository,
+    category_repository: DynCategoryRepository,
+    comment_service: DynCommentService,
+    reaction_repository: DynReactionRepository,
+    bookmark_repository: DynBookmarkRepository,
+    storage: DynStorage,
 }
 
 impl PostService {
-    pub fn new(repository: DynPostsRepository) -> Self {
-        Self { repository }
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        repository: DynPostsRepository,
+        tag_repository: DynTagRepository,
+        image_variant_repository: DynImageVariantRepository,
+        media_repository: DynMediaRepository,
+        comment_repository: DynCommentRepository,
+        user_repository: DynUserRepository,
+        category_repository: DynCategoryRepository,
+        comment_service: DynCommentService,
+        reaction_repository: DynReactionRepository,
+        bookmark_repository: DynBookmarkRepository,
+        storage: DynStorage,
+    ) -> Self {
+        Self {
+            repository,
+            tag_repository,
+            image_variant_repository,
+            media_repository,
+            comment_repository,
+            user_repository,
+            category_repository,
+            comment_service,
+            reaction_repository,
+            bookmark_repository,
+            storage,
+        }
+    }
+
+    // External image URLs are taken as is, uploaded ones must point at a stored file
+    async fn validate_image(&self, img: &str) -> Result<(), ErrorResponse> {
+        let Some(key) = self.storage.key_from_url(img) else {
+            return Ok(());
+        };
+
+        let exists = self.storage.exists(&key).await.map_err(AppError::from).map_err(ErrorResponse::from)?;
+
+        if exists {
+            Ok(())
+        } else {
+            Err(ErrorResponse::from(AppError::ValidationError(format!("Uploaded image {} does not exist", img))))
+        }
+    }
+
+    // Loads everything a PostResponse needs besides the post row, batched for the whole page
+    async fn build_responses(&self, posts: Vec<posts::Model>, viewer_id: Option<i32>) -> Result<Vec<PostResponse>, ErrorResponse> {
+        let post_ids: Vec<i32> = posts.iter().map(|post| post.id).collect();
+
+        let mut tags_by_post: HashMap<i32, Vec<TagResponse>> = HashMap::new();
+        let tags = self.tag_repository.find_by_posts(&post_ids)
+            .await
+            .map_err(AppError::from).map_err(ErrorResponse::from)?;
+
+        for (post_id, tag) in tags {
+            tags_by_post.entry(post_id).or_default().push(TagResponse::from(tag));
+        }
+
+        let image_keys: Vec<String> = posts.iter()
+            .filter_map(|post| self.storage.key_from_url(&post.img))
+            .collect();
+
+        let mut variants_by_image: HashMap<String, Vec<ImageVariantResponse>> = HashMap::new();
+        let variants = self.image_variant_repository.find_by_sources(&image_keys)
+            .await
+            .map_err(AppError::from).map_err(ErrorResponse::from)?;
+
+        for variant in variants {
+            variants_by_image.entry(variant.source_key).or_default().push(ImageVariantResponse {
+                name: variant.name,
+                url: self.storage.url(&variant.storage_key),
+                content_type: variant.content_type,
+                width: variant.width,
+                height: variant.height,
+            });
+        }
+
+        let mut media_by_post: HashMap<i32, Vec<MediaResponse>> = HashMap::new();
+        let media = self.media_repository.find_by_posts(&post_ids)
+            .await
+            .map_err(AppError::from).map_err(ErrorResponse::from)?;
+
+        for (post_id, media) in media {
+            let url = self.storage.url(&media.filename);
+            media_by_post.entry(post_id).or_default().push(MediaResponse::from_model(media, url));
+        }
+
+        let comment_counts: HashMap<i32, i64> = self.comment_repository.count_by_posts(&post_ids)
+            .await
+            .map_err(AppError::from).map_err(ErrorResponse::from)?
+            .into_iter()
+            .collect();
+
+        let mut reactions_by_post: HashMap<i32, Vec<ReactionCountResponse>> = HashMap::new();
+        let reaction_counts = self.reaction_repository.count_post_reactions(&post_ids)
+            .await
+            .map_err(AppError::from).map_err(ErrorResponse::from)?;
+
+        for (post_id, kind, count) in reaction_counts {
+            reactions_by_post.entry(post_id).or_default().push(ReactionCountResponse { kind, count });
+        }
+
+        let mut my_reactions_by_post: Option<HashMap<i32, Vec<ReactionKind>>> = None;
+        let mut bookmarked: Option<HashSet<i32>> = None;
+        if let Some(user_id) = viewer_id {
+            let mut mine: HashMap<i32, Vec<ReactionKind>> = HashMap::new();
+            let reactions = self.reaction_repository.find_user_post_reactions(&post_ids, user_id)
+                .await
+                .map_err(AppError::from).map_err(ErrorResponse::from)?;
+
+            for (post_id, kind) in reactions {
+                mine.entry(post_id).or_default().push(kind);
+            }
+            my_reactions_by_post = Some(mine);
+
+            let saved = self.bookmark_repository.find_bookmarked(&post_ids, user_id)
+                .await
+                .map_err(AppError::from).map_err(ErrorResponse::from)?;
+            bookmarked = Some(saved.into_iter().collect());
+        }
+
+        Ok(posts.into_iter()
+            .map(|post| {
+                let comment_count = comment_counts.get(&post.id).copied().unwrap_or(0);
+                let reactions = reactions_by_post.remove(&post.id).unwrap_or_default();
+                let my_reactions = my_reactions_by_post.as_mut()
+                    .map(|mine| mine.remove(&post.id).unwrap_or_default());
+                let bookmarked = bookmarked.as_ref().map(|saved| saved.contains(&post.id));
+                let tags = tags_by_post.remove(&post.id).unwrap_or_default();
+                let media = media_by_post.remove(&post.id).unwrap_or_default();
+                let srcset = self.storage.key_from_url(&post.img)
+                    .and_then(|key| variants_by_image.get(&key).cloned())
+                    .unwrap_or_default();
+                PostResponse { comment_count, tags, srcset, media, reactions, my_reactions, bookmarked, ..PostResponse::from(post) }
+            })
+            .collect())
+    }
+
+    async fn build_response(&self, post: posts::Model, viewer_id: Option<i32>) -> Result<PostResponse, ErrorResponse> {
+        let mut responses = self.build_responses(vec![post], viewer_id).await?;
+        Ok(responses.remove(0))
+    }
+
+    fn validate_schedule(status: Option<PostStatus>, publish_at: Option<DateTime<FixedOffset>>) -> Result<(), ErrorResponse> {
+        if status != Some(PostStatus::Scheduled) {
+            return Ok(());
+        }
+
+        match publish_at {
+            Some(publish_at) if publish_at > Utc::now() => Ok(()),
+            Some(_) => Err(ErrorResponse::from(AppError::ValidationError("publish_at must be in the future".to_string()))),
+            None => Err(ErrorResponse::from(AppError::ValidationError("publish_at is required for scheduled posts".to_string()))),
+        }
     }
 }
 
 #[async_trait]
 impl PostsServiceTrait for PostService {
-    async fn get_all_posts(&self) -> Result<ApiResponse<Vec<PostResponse>>, ErrorResponse> {
-        let posts = self.repository.get_all_posts()
+    // Drafts and revisions are private, so only the author and admins may change or inspect a post
+    async fn can_manage(&self, post_id: i32, user_id: i32) -> Result<Option<bool>, ErrorResponse> {
+        let Some(post) = self.repository.get_post(post_id)
+            .await
+            .map_err(AppError::from).map_err(ErrorResponse::from)?
+        else {
+            return Ok(None);
+        };
+
+        if post.user_id == user_id {
+            return Ok(Some(true));
+        }
+
+        let is_admin = self.user_repository.find_by_id(user_id)
+            .await
+            .map_err(AppError::from).map_err(ErrorResponse::from)?
+            .is_some_and(|user| user.role == UserRole::Admin);
+
+        Ok(Some(is_admin))
+    }
+
+    async fn to_responses(&self, posts: Vec<posts::Model>, viewer_id: Option<i32>) -> Result<Vec<PostResponse>, ErrorResponse> {
+        self.build_responses(posts, viewer_id).await
+    }
+
+    async fn get_all_posts(&self, query: &PostListQuery, viewer_id: Option<i32>) -> Result<ApiResponse<Vec<PostResponse>>, ErrorResponse> {
+        let posts = self.repository.get_published_posts(query)
             .await
             .map_err(AppError::from).map_err(ErrorResponse::from)?;
 
-        let responses = posts.into_iter()
-            .map(|post| PostResponse::from(post))
-            .collect();
+        let responses = self.build_responses(posts, viewer_id).await?;
 
         Ok(ApiResponse {
             status: "success".to_string(),
@@ -29,44 +218,133 @@ impl PostsServiceTrait for PostService {
         })
     }
 
-    async fn get_post(&self, post_id: i32) -> Result<Option<ApiResponse<PostResponse>>, ErrorResponse> {
-        let post = self.repository.get_post(post_id)
+    async fn get_posts_by_tag(&self, tag_slug: &str, viewer_id: Option<i32>) -> Result<ApiResponse<Vec<PostResponse>>, ErrorResponse> {
+        let posts = self.repository.get_published_posts_by_tag(tag_slug)
+            .await
+            .map_err(AppError::from).map_err(ErrorResponse::from)?;
+
+        Ok(ApiResponse {
+            status: "success".to_string(),
+            message: "Posts retrieved successfully".to_string(),
+            data: self.build_responses(posts, viewer_id).await?,
+        })
+    }
+
+    async fn get_author_posts(&self, user_id: i32, status: Option<PostStatus>) -> Result<ApiResponse<Vec<PostResponse>>, ErrorResponse> {
+        let posts = self.repository.get_posts_by_author(user_id, status)
             .await
             .map_err(AppError::from).map_err(ErrorResponse::from)?;
 
+        Ok(ApiResponse {
+            status: "success".to_string(),
+            message: "Posts retrieved successfully".to_string(),
+            data: self.build_responses(posts, Some(user_id)).await?,
+        })
+    }
+
+    async fn get_post(&self, post_id: i32, viewer_id: Option<i32>) -> Result<Option<ApiResponse<PostResponse>>, ErrorResponse> {
+        let post = self.repository.get_post(post_id)
+            .await
+            .map_err(AppError::from).map_err(ErrorResponse::from)?
+            .filter(|post| post.status == PostStatus::Published);
+
         
         
         if let Some(post) = post{
             Ok(Some(ApiResponse{
                 status: "success".to_string(),
                 message: "Post retrieved successfully".to_string(),
-                data: PostResponse::from(post),
+                data: self.build_response(post, viewer_id).await?,
             }))
         }else{
-            Err(ErrorResponse::from(AppError::NotFound(format!("Posts with id {} not found", post_id))))
+            Ok(None)
         }
     }
 
-    async fn get_post_relation(&self, post_id: i32) -> Result<ApiResponse<PostRelationResponse>, ErrorResponse> {
-        let relations = self.repository.get_post_relation(post_id)
+    async fn get_post_by_slug(&self, slug: &str, viewer_id: Option<i32>) -> Result<Option<SlugLookup<ApiResponse<PostResponse>>>, ErrorResponse> {
+        let post = self.repository.get_post_by_slug(slug)
+            .await
+            .map_err(AppError::from).map_err(ErrorResponse::from)?
+            .filter(|post| post.status == PostStatus::Published);
+
+        if let Some(post) = post {
+            return Ok(Some(SlugLookup::Found(ApiResponse {
+                status: "success".to_string(),
+                message: "Post retrieved successfully".to_string(),
+                data: self.build_response(post, viewer_id).await?,
+            })));
+        }
+
+        let canonical = self.repository.find_slug_redirect(slug)
             .await
             .map_err(AppError::from).map_err(ErrorResponse::from)?;
 
-        let first_relation = relations.into_iter()
-            .next()
-            .ok_or_else(|| AppError::NotFound("Post relation not found".to_string()))?;
+        Ok(canonical.map(|canonical| SlugLookup::Moved(SlugRedirectResponse {
+            location: format!("/api/posts/slug/{}", canonical),
+            slug: canonical,
+        })))
+    }
 
-        Ok(ApiResponse {
+    async fn get_post_relation(
+        &self,
+        post_id: i32,
+        viewer_id: Option<i32>,
+        query: &PostRelationQuery,
+    ) -> Result<Option<ApiResponse<PostAggregateResponse>>, ErrorResponse> {
+        let Some(post) = self.repository.get_post(post_id)
+            .await
+            .map_err(AppError::from).map_err(ErrorResponse::from)?
+            .filter(|post| post.status == PostStatus::Published)
+        else {
+            return Ok(None);
+        };
+
+        // Every part is a fixed number of queries, no matter how many comments the post has
+        let author = if query.includes("author") {
+            self.user_repository.find_by_id(post.user_id)
+                .await
+                .map_err(AppError::from).map_err(ErrorResponse::from)?
+                .map(UserResponse::from)
+        } else {
+            None
+        };
+
+        let category = if query.includes("category") {
+            self.category_repository.find_by_id(post.category_id)
+                .await
+                .map_err(AppError::from).map_err(ErrorResponse::from)?
+                .map(CategoryResponse::from)
+        } else {
+            None
+        };
+
+        let comments = if query.includes("comments") {
+            self.comment_service.get_post_comment_tree(post_id, viewer_id, &query.comments_query())
+                .await?
+                .map(|comments| comments.data)
+        } else {
+            None
+        };
+
+        let mut post = self.build_response(post, viewer_id).await?;
+        if query.includes("toc") {
+            post = post.with_toc();
+        }
+
+        Ok(Some(ApiResponse {
             status: "success".to_string(),
-            message: "Post relation retrieved successfully".to_string(),
-            data: first_relation,
-        })
+            message: "Post retrieved successfully".to_string(),
+            data: PostAggregateResponse { post, author, category, comments },
+        }))
     }
 
     async fn create_post(
         &self,
         input: &CreatePostRequest
     ) -> Result<ApiResponse<PostResponse>, ErrorResponse> {
+        Self::validate_schedule(input.status, input.publish_at)?;
+        self.validate_image(&input.img).await?;
+
         let post = self.repository.create_post(input)
             .await
             .map_err(AppError::from).map_err(ErrorResponse::from)?;
@@ -74,7 +352,7 @@ impl PostsServiceTrait for PostService {
         Ok(ApiResponse {
             status: "success".to_string(),
             message: "Post created successfully".to_string(),
-            data: PostResponse::from(post)
+            data: self.build_response(post, None).await?,
         })
     }
 
@@ -82,13 +360,16 @@ impl PostsServiceTrait for PostService {
         &self,
         input: &UpdatePostRequest
     ) -> Result<ApiResponse<PostResponse>, ErrorResponse> {
+        Self::validate_schedule(input.status, input.publish_at)?;
+        self.validate_image(&input.img).await?;
+
         let post = self.repository.update_post(input)
             .await.map_err(AppError::from).map_err(ErrorResponse::from)?;
 
         Ok(ApiResponse {
             status: "success".to_string(),
             message: "Post updated successfully".to_string(),
-            data: PostResponse::from(post),
+            data: self.build_response(post, None).await?,
         })
     }
 
@@ -103,4 +384,25 @@ impl PostsServiceTrait for PostService {
             data: (),
         })
     }
+
+    async fn publish_scheduled_posts(&self) -> Result<u64, ErrorResponse> {
+        self.repository.publish_due_posts()
+            .await
+            .map_err(AppError::from).map_err(ErrorResponse::from)
+    }
+
+    async fn backfill_body_html(&self) -> Result<u64, ErrorResponse> {
+        let mut total = 0;
+
+        loop {
+            let rendered = self.repository.render_missing_body_html(BODY_HTML_BATCH_SIZE)
+                .await
+                .map_err(AppError::from).map_err(ErrorResponse::from)?;
+
+            if rendered == 0 {
+                return Ok(total);
+            }
+            total += rendered;
+        }
+    }
 }
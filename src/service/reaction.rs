@@ -0,0 +1,141 @@
+use async_trait::async_trait;
+
+use crate::{
+    abstract_trait::{DynCommentRepository, DynPostsRepository, DynReactionRepository, ReactionServiceTrait},
+    domain::{ApiResponse, CommentVoteResponse, ErrorResponse, PostReactionsResponse, ReactionCountResponse, VoteDirection},
+    entities::sea_orm_active_enums::{CommentStatus, PostStatus, ReactionKind},
+    utils::AppError,
+};
+
+pub struct ReactionService {
+    repository: DynReactionRepository,
+    post_repository: DynPostsRepository,
+    comment_repository: DynCommentRepository,
+}
+
+impl ReactionService {
+    pub fn new(
+        repository: DynReactionRepository,
+        post_repository: DynPostsRepository,
+        comment_repository: DynCommentRepository,
+    ) -> Self {
+        Self { repository, post_repository, comment_repository }
+    }
+
+    // Only published posts can be reacted to, anything else is reported as missing
+    async fn post_exists(&self, post_id: i32) -> Result<bool, ErrorResponse> {
+        let post = self.post_repository.get_post(post_id).await.map_err(AppError::from).map_err(ErrorResponse::from)?;
+        Ok(post.is_some_and(|post| post.status == PostStatus::Published))
+    }
+
+    async fn comment_exists(&self, comment_id: i32) -> Result<bool, ErrorResponse> {
+        let comment = self.comment_repository.find_by_id(comment_id).await.map_err(AppError::from).map_err(ErrorResponse::from)?;
+        Ok(comment.is_some_and(|comment| comment.status == CommentStatus::Approved))
+    }
+
+    async fn post_reactions(&self, post_id: i32, user_id: i32) -> Result<PostReactionsResponse, ErrorResponse> {
+        let reactions = self
+            .repository
+            .count_post_reactions(&[post_id])
+            .await
+            .map_err(AppError::from)
+            .map_err(ErrorResponse::from)?
+            .into_iter()
+            .map(|(_, kind, count)| ReactionCountResponse { kind, count })
+            .collect();
+
+        let my_reactions = self
+            .repository
+            .find_user_post_reactions(&[post_id], user_id)
+            .await
+            .map_err(AppError::from)
+            .map_err(ErrorResponse::from)?
+            .into_iter()
+            .map(|(_, kind)| kind)
+            .collect();
+
+        Ok(PostReactionsResponse { post_id, reactions, my_reactions })
+    }
+
+    async fn comment_votes(&self, comment_id: i32, user_id: i32) -> Result<CommentVoteResponse, ErrorResponse> {
+        let (upvotes, downvotes) = self
+            .repository
+            .count_comment_votes(&[comment_id])
+            .await
+            .map_err(AppError::from)
+            .map_err(ErrorResponse::from)?
+            .first()
+            .map(|(_, upvotes, downvotes)| (*upvotes, *downvotes))
+            .unwrap_or_default();
+
+        let my_vote = self
+            .repository
+            .find_user_comment_votes(&[comment_id], user_id)
+            .await
+            .map_err(AppError::from)
+            .map_err(ErrorResponse::from)?
+            .first()
+            .map(|(_, value)| VoteDirection::from_value(*value));
+
+        Ok(CommentVoteResponse { comment_id, upvotes, downvotes, score: upvotes - downvotes, my_vote })
+    }
+}
+
+#[async_trait]
+impl ReactionServiceTrait for ReactionService {
+    async fn react_to_post(&self, post_id: i32, user_id: i32, kind: ReactionKind) -> Result<Option<ApiResponse<PostReactionsResponse>>, ErrorResponse> {
+        if !self.post_exists(post_id).await? {
+            return Ok(None);
+        }
+
+        self.repository.add_post_reaction(post_id, user_id, kind).await.map_err(AppError::from).map_err(ErrorResponse::from)?;
+
+        Ok(Some(ApiResponse {
+            status: "success".to_string(),
+            message: "Reaction added successfully".to_string(),
+            data: self.post_reactions(post_id, user_id).await?,
+        }))
+    }
+
+    async fn remove_post_reaction(&self, post_id: i32, user_id: i32, kind: ReactionKind) -> Result<Option<ApiResponse<PostReactionsResponse>>, ErrorResponse> {
+        if !self.post_exists(post_id).await? {
+            return Ok(None);
+        }
+
+        self.repository.remove_post_reaction(post_id, user_id, kind).await.map_err(AppError::from).map_err(ErrorResponse::from)?;
+
+        Ok(Some(ApiResponse {
+            status: "success".to_string(),
+            message: "Reaction removed successfully".to_string(),
+            data: self.post_reactions(post_id, user_id).await?,
+        }))
+    }
+
+    async fn vote_comment(&self, comment_id: i32, user_id: i32, direction: VoteDirection) -> Result<Option<ApiResponse<CommentVoteResponse>>, ErrorResponse> {
+        if !self.comment_exists(comment_id).await? {
+            return Ok(None);
+        }
+
+        self.repository.set_comment_vote(comment_id, user_id, direction.value()).await.map_err(AppError::from).map_err(ErrorResponse::from)?;
+
+        Ok(Some(ApiResponse {
+            status: "success".to_string(),
+            message: "Vote recorded successfully".to_string(),
+            data: self.comment_votes(comment_id, user_id).await?,
+        }))
+    }
+
+    async fn remove_comment_vote(&self, comment_id: i32, user_id: i32) -> Result<Option<ApiResponse<CommentVoteResponse>>, ErrorResponse> {
+        if !self.comment_exists(comment_id).await? {
+            return Ok(None);
+        }
+
+        self.repository.remove_comment_vote(comment_id, user_id).await.map_err(AppError::from).map_err(ErrorResponse::from)?;
+
+        Ok(Some(ApiResponse {
+            status: "success".to_string(),
+            message: "Vote removed successfully".to_string(),
+            data: self.comment_votes(comment_id, user_id).await?,
+        }))
+    }
+}
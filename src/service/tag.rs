@@ -0,0 +1,87 @@
+use async_trait::async_trait;
+
+use crate::{
+    abstract_trait::{DynTagRepository, TagServiceTrait},
+    domain::{ApiResponse, ErrorResponse, MergeTagRequest, RenameTagRequest, TagResponse},
+    utils::{generate_slug, AppError},
+};
+
+pub struct TagService {
+    repository: DynTagRepository,
+}
+
+impl TagService {
+    pub fn new(repository: DynTagRepository) -> Self {
+        Self { repository }
+    }
+}
+
+#[async_trait]
+impl TagServiceTrait for TagService {
+    async fn get_tags(&self) -> Result<ApiResponse<Vec<TagResponse>>, ErrorResponse> {
+        let tags = self.repository.find_all_with_post_count().await.map_err(AppError::from).map_err(ErrorResponse::from)?;
+
+        let responses = tags
+            .into_iter()
+            .map(|(tag, post_count)| TagResponse { post_count: Some(post_count), ..TagResponse::from(tag) })
+            .collect();
+
+        Ok(ApiResponse {
+            status: "success".to_string(),
+            message: "Tags retrieved successfully".to_string(),
+            data: responses,
+        })
+    }
+
+    async fn rename_tag(&self, input: &RenameTagRequest) -> Result<Option<ApiResponse<TagResponse>>, ErrorResponse> {
+        let id = input.id.ok_or_else(|| AppError::ValidationError("Tag ID is required".to_string()))?;
+
+        if self.repository.find_by_id(id).await.map_err(AppError::from).map_err(ErrorResponse::from)?.is_none() {
+            return Ok(None);
+        }
+
+        let slug = generate_slug(input.name.trim());
+
+        if slug.is_empty() {
+            return Err(ErrorResponse::from(AppError::ValidationError("Tag name must contain letters or digits".to_string())));
+        }
+
+        let existing = self.repository.find_by_slug(&slug).await.map_err(AppError::from).map_err(ErrorResponse::from)?;
+
+        if existing.is_some_and(|tag| tag.id != id) {
+            return Err(ErrorResponse::from(AppError::ValidationError(format!(
+                "Tag with slug {} already exists, merge the tags instead",
+                slug
+            ))));
+        }
+
+        let tag = self.repository.rename(id, &input.name).await.map_err(AppError::from).map_err(ErrorResponse::from)?;
+
+        Ok(Some(ApiResponse {
+            status: "success".to_string(),
+            message: "Tag renamed successfully".to_string(),
+            data: TagResponse::from(tag),
+        }))
+    }
+
+    async fn merge_tag(&self, source_id: i32, input: &MergeTagRequest) -> Result<Option<ApiResponse<TagResponse>>, ErrorResponse> {
+        if source_id == input.target_id {
+            return Err(ErrorResponse::from(AppError::ValidationError("Cannot merge a tag into itself".to_string())));
+        }
+
+        let source = self.repository.find_by_id(source_id).await.map_err(AppError::from).map_err(ErrorResponse::from)?;
+        let target = self.repository.find_by_id(input.target_id).await.map_err(AppError::from).map_err(ErrorResponse::from)?;
+
+        if source.is_none() || target.is_none() {
+            return Ok(None);
+        }
+
+        let tag = self.repository.merge(source_id, input.target_id).await.map_err(AppError::from).map_err(ErrorResponse::from)?;
+
+        Ok(Some(ApiResponse {
+            status: "success".to_string(),
+            message: "Tags merged successfully".to_string(),
+            data: TagResponse::from(tag),
+        }))
+    }
+}
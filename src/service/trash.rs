@@ -0,0 +1,97 @@
+use async_trait::async_trait;
+use chrono::{Duration, Utc};
+use sea_orm::DbErr;
+use tracing::info;
+
+use crate::{
+    abstract_trait::{DynCategoryRepository, DynCommentRepository, DynPostsRepository, DynUserRepository, TrashServiceTrait},
+    domain::{ApiResponse, CategoryResponse, CommentResponse, ErrorResponse, PostResponse, TrashKind, TrashResponse, UserResponse},
+    utils::AppError,
+};
+
+pub struct TrashService {
+    post_repository: DynPostsRepository,
+    category_repository: DynCategoryRepository,
+    comment_repository: DynCommentRepository,
+    user_repository: DynUserRepository,
+    retention: Duration,
+}
+
+impl TrashService {
+    pub fn new(
+        post_repository: DynPostsRepository,
+        category_repository: DynCategoryRepository,
+        comment_repository: DynCommentRepository,
+        user_repository: DynUserRepository,
+        retention_days: i64,
+    ) -> Self {
+        Self {
+            post_repository,
+            category_repository,
+            comment_repository,
+            user_repository,
+            retention: Duration::days(retention_days),
+        }
+    }
+}
+
+#[async_trait]
+impl TrashServiceTrait for TrashService {
+    async fn get_trash(&self) -> Result<ApiResponse<TrashResponse>, ErrorResponse> {
+        let posts = self.post_repository.get_trashed_posts().await.map_err(AppError::from).map_err(ErrorResponse::from)?;
+        let categories = self.category_repository.find_trashed().await.map_err(AppError::from).map_err(ErrorResponse::from)?;
+        let comments = self.comment_repository.find_trashed().await.map_err(AppError::from).map_err(ErrorResponse::from)?;
+        let users = self.user_repository.find_trashed_users().await.map_err(AppError::from).map_err(ErrorResponse::from)?;
+
+        Ok(ApiResponse {
+            status: "success".to_string(),
+            message: "Trash retrieved successfully".to_string(),
+            data: TrashResponse {
+                posts: posts.into_iter().map(PostResponse::from).collect(),
+                categories: categories.into_iter().map(CategoryResponse::from).collect(),
+                comments: comments.into_iter().map(CommentResponse::from).collect(),
+                users: users.into_iter().map(UserResponse::from).collect(),
+            },
+        })
+    }
+
+    async fn restore(&self, kind: TrashKind, id: i32) -> Result<Option<ApiResponse<()>>, ErrorResponse> {
+        let (restored, message) = match kind {
+            TrashKind::Posts => (self.post_repository.restore_post(id).await.map(|_| ()), "Post restored successfully"),
+            TrashKind::Categories => (self.category_repository.restore(id).await.map(|_| ()), "Category restored successfully"),
+            TrashKind::Comments => (self.comment_repository.restore(id).await.map(|_| ()), "Comment restored successfully"),
+            TrashKind::Users => (self.user_repository.restore_user(id).await.map(|_| ()), "User restored successfully"),
+        };
+
+        match restored {
+            Ok(()) => Ok(Some(ApiResponse {
+                status: "success".to_string(),
+                message: message.to_string(),
+                data: (),
+            })),
+            Err(DbErr::RecordNotFound(_)) => Ok(None),
+            Err(e) => Err(ErrorResponse::from(AppError::from(e))),
+        }
+    }
+
+    async fn purge_expired(&self) -> Result<u64, ErrorResponse> {
+        let cutoff = Utc::now() - self.retention;
+
+        // Children first, so a parent purged in the same run no longer has anything to cascade to
+        let comments = self.comment_repository.purge_trashed(cutoff).await.map_err(AppError::from).map_err(ErrorResponse::from)?;
+        let posts = self.post_repository.purge_trashed_posts(cutoff).await.map_err(AppError::from).map_err(ErrorResponse::from)?;
+        let categories = self.category_repository.purge_trashed(cutoff).await.map_err(AppError::from).map_err(ErrorResponse::from)?;
+        let users = self.user_repository.purge_trashed_users(cutoff).await.map_err(AppError::from).map_err(ErrorResponse::from)?;
+
+        let purged = comments + posts + categories + users;
+
+        if purged > 0 {
+            info!(
+                "Purged {} posts, {} categories, {} comments and {} users from the trash",
+                posts, categories, comments, users
+            );
+        }
+
+        Ok(purged)
+    }
+}
@@ -0,0 +1,126 @@
+use async_trait::async_trait;
+use sea_orm::Set;
+use sha2::{Digest, Sha256};
+
+use crate::{
+    abstract_trait::{DynImageVariantRepository, DynMediaRepository, DynStorage, UploadServiceTrait},
+    domain::{ApiResponse, ErrorResponse, StoredFile, UploadResponse},
+    entities::media,
+    jobs::spawn_image_variants,
+    utils::{image_dimensions, strip_metadata, AppError, ImageSize},
+};
+
+// Image types we accept, detected from the file contents rather than the client supplied type
+const IMAGE_TYPES: [(&[u8], &str, &str); 4] = [
+    (b"\x89PNG\r\n\x1a\n", "png", "image/png"),
+    (b"\xff\xd8\xff", "jpg", "image/jpeg"),
+    (b"GIF8", "gif", "image/gif"),
+    (b"RIFF", "webp", "image/webp"),
+];
+
+pub struct UploadService {
+    storage: DynStorage,
+    media_repository: DynMediaRepository,
+    image_variant_repository: DynImageVariantRepository,
+    image_sizes: Vec<ImageSize>,
+}
+
+impl UploadService {
+    pub fn new(
+        storage: DynStorage,
+        media_repository: DynMediaRepository,
+        image_variant_repository: DynImageVariantRepository,
+        image_sizes: Vec<ImageSize>,
+    ) -> Self {
+        Self { storage, media_repository, image_variant_repository, image_sizes }
+    }
+
+    fn sniff_image(bytes: &[u8]) -> Option<(&'static str, &'static str)> {
+        IMAGE_TYPES
+            .iter()
+            .find(|(magic, extension, _)| {
+                bytes.starts_with(magic) && (*extension != "webp" || bytes.get(8..12) == Some(b"WEBP"))
+            })
+            .map(|(_, extension, content_type)| (*extension, *content_type))
+    }
+
+    fn content_type_for(filename: &str) -> &'static str {
+        let extension = filename.rsplit('.').next().unwrap_or_default();
+
+        IMAGE_TYPES
+            .iter()
+            .find(|(_, known, _)| *known == extension)
+            .map(|(_, _, content_type)| *content_type)
+            .unwrap_or("application/octet-stream")
+    }
+}
+
+#[async_trait]
+impl UploadServiceTrait for UploadService {
+    async fn upload_image(&self, user_id: i32, original_name: &str, bytes: &[u8]) -> Result<ApiResponse<UploadResponse>, ErrorResponse> {
+        let (extension, content_type) = Self::sniff_image(bytes).ok_or_else(|| {
+            ErrorResponse::from(AppError::ValidationError("Only PNG, JPEG, GIF and WebP images can be uploaded".to_string()))
+        })?;
+
+        // Location and camera details must never reach the public copy
+        let bytes = strip_metadata(bytes, extension).ok_or_else(|| {
+            ErrorResponse::from(AppError::ValidationError("Image file is corrupt".to_string()))
+        })?;
+
+        // Named after the content, so the same image uploaded twice is stored once
+        let filename = format!("{}.{}", hex::encode(Sha256::digest(&bytes)), extension);
+
+        let exists = self.storage.exists(&filename).await.map_err(AppError::from).map_err(ErrorResponse::from)?;
+
+        if !exists {
+            self.storage.put(&filename, &bytes).await.map_err(AppError::from).map_err(ErrorResponse::from)?;
+        }
+
+        let size = bytes.len();
+        let dimensions = image_dimensions(&bytes);
+
+        let media = self.media_repository.create(media::ActiveModel {
+            user_id: Set(Some(user_id)),
+            filename: Set(filename.clone()),
+            original_name: Set(original_name.to_string()),
+            mime: Set(content_type.to_string()),
+            size: Set(size as i32),
+            width: Set(dimensions.map(|(width, _)| width as i32)),
+            height: Set(dimensions.map(|(_, height)| height as i32)),
+            ..Default::default()
+        })
+        .await
+        .map_err(AppError::from).map_err(ErrorResponse::from)?;
+
+        if !exists && !self.image_sizes.is_empty() {
+            spawn_image_variants(
+                self.storage.clone(),
+                self.image_variant_repository.clone(),
+                filename.clone(),
+                bytes,
+                self.image_sizes.clone(),
+            );
+        }
+
+        Ok(ApiResponse {
+            status: "success".to_string(),
+            message: "File uploaded successfully".to_string(),
+            data: UploadResponse {
+                media_id: media.id,
+                url: self.storage.url(&filename),
+                filename,
+                content_type: content_type.to_string(),
+                size,
+            },
+        })
+    }
+
+    async fn get_upload(&self, filename: &str) -> Result<Option<StoredFile>, ErrorResponse> {
+        let bytes = self.storage.get(filename).await.map_err(AppError::from).map_err(ErrorResponse::from)?;
+
+        Ok(bytes.map(|bytes| StoredFile {
+            bytes,
+            content_type: Self::content_type_for(filename).to_string(),
+        }))
+    }
+}
@@ -1,21 +1,22 @@
 use sea_orm::DatabaseConnection;
 
-use crate::{config::{Hashing, JwtConfig}, utils::DependenciesInject};
+use crate::{config::{CommentConfig, Hashing, JwtConfig, UploadConfig}, utils::DependenciesInject};
 
 #[derive(Clone)]
 pub struct AppState {
     pub di_container: DependenciesInject,
     pub jwt_config: JwtConfig,
+    pub upload_config: UploadConfig,
 }
 
 impl AppState {
-    pub fn new(pool: DatabaseConnection, jwt_secret: &str) -> Self {
+    pub fn new(pool: DatabaseConnection, jwt_secret: &str, upload_config: UploadConfig, trash_retention_days: i64, comment_config: CommentConfig) -> Self {
         let jwt_config = JwtConfig::new(jwt_secret);
         let hashing = Hashing::new();
 
-        let di_container = DependenciesInject::new(pool, hashing, jwt_config.clone());
+        let di_container = DependenciesInject::new(pool, hashing, jwt_config.clone(), upload_config.clone(), trash_retention_days, comment_config);
         
-        Self { di_container, jwt_config }
+        Self { di_container, jwt_config, upload_config }
     }
 
 }
\ No newline at end of file
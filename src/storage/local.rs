@@ -0,0 +1,109 @@
+use std::io::ErrorKind;
+use std::path::PathBuf;
+use std::time::SystemTime;
+
+use async_trait::async_trait;
+use tokio::fs;
+
+use crate::abstract_trait::StorageTrait;
+
+const PUBLIC_PREFIX: &str = "/uploads/";
+
+pub struct LocalStorage {
+    root: PathBuf,
+}
+
+impl LocalStorage {
+    pub fn new(root: &str) -> Self {
+        Self { root: PathBuf::from(root) }
+    }
+
+    // Keys come from URLs, anything that could leave the upload directory is refused
+    fn path(&self, key: &str) -> Result<PathBuf, std::io::Error> {
+        let valid = !key.is_empty()
+            && !key.starts_with('.')
+            && key.chars().all(|c| c.is_ascii_alphanumeric() || c == '.' || c == '-' || c == '_');
+
+        if !valid {
+            return Err(std::io::Error::new(ErrorKind::InvalidInput, format!("Invalid storage key {}", key)));
+        }
+
+        Ok(self.root.join(key))
+    }
+}
+
+#[async_trait]
+impl StorageTrait for LocalStorage {
+    async fn put(&self, key: &str, bytes: &[u8]) -> Result<(), std::io::Error> {
+        let path = self.path(key)?;
+        fs::create_dir_all(&self.root).await?;
+
+        // Write next to the target and rename, so readers never see a half written file
+        let tmp_path = self.root.join(format!(".{}.tmp", key));
+        fs::write(&tmp_path, bytes).await?;
+        fs::rename(&tmp_path, &path).await
+    }
+
+    async fn get(&self, key: &str) -> Result<Option<Vec<u8>>, std::io::Error> {
+        let Ok(path) = self.path(key) else {
+            return Ok(None);
+        };
+
+        match fs::read(path).await {
+            Ok(bytes) => Ok(Some(bytes)),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    async fn exists(&self, key: &str) -> Result<bool, std::io::Error> {
+        let Ok(path) = self.path(key) else {
+            return Ok(false);
+        };
+
+        fs::try_exists(path).await
+    }
+
+    async fn delete(&self, key: &str) -> Result<(), std::io::Error> {
+        match fs::remove_file(self.path(key)?).await {
+            Err(e) if e.kind() != ErrorKind::NotFound => Err(e),
+            _ => Ok(()),
+        }
+    }
+
+    async fn list(&self) -> Result<Vec<(String, SystemTime)>, std::io::Error> {
+        let mut entries = match fs::read_dir(&self.root).await {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e),
+        };
+
+        let mut keys = Vec::new();
+
+        while let Some(entry) = entries.next_entry().await? {
+            let Ok(key) = entry.file_name().into_string() else {
+                continue;
+            };
+
+            // Temporary files of writes in progress are not keys
+            if key.starts_with('.') {
+                continue;
+            }
+
+            let metadata = entry.metadata().await?;
+            if metadata.is_file() {
+                keys.push((key, metadata.modified()?));
+            }
+        }
+
+        Ok(keys)
+    }
+
+    fn url(&self, key: &str) -> String {
+        format!("{}{}", PUBLIC_PREFIX, key)
+    }
+
+    fn key_from_url(&self, url: &str) -> Option<String> {
+        url.strip_prefix(PUBLIC_PREFIX).map(str::to_string)
+    }
+}
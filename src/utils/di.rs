@@ -2,7 +2,7 @@ use std::sync::Arc;
 
 use sea_orm::DatabaseConnection;
 
-use crate::{abstract_trait::{DynAuthService, DynCategoryRepository, DynCategoryService, DynCommentRepository, DynCommentService, DynPostsRepository, DynPostsService, DynUserRepository, DynUserService}, config::{Hashing, JwtConfig}, repository::{CategoryRepository, CommentRepository, PostRepository, UserRepository}, service::{AuthService, CategoryService, CommentService, PostService, UserService}};
+use crate::{abstract_trait::{DynAuthService, DynBookmarkRepository, DynBookmarkService, DynCategoryRepository, DynCategoryService, DynCommentRepository, DynCommentService, DynFollowRepository, DynFollowService, DynImageVariantRepository, DynMediaRepository, DynMediaService, DynNotificationRepository, DynNotificationService, DynPostRevisionRepository, DynPostRevisionService, DynPostsRepository, DynPostsService, DynReactionRepository, DynReactionService, DynStorage, DynTagRepository, DynTagService, DynTrashService, DynUploadService, DynUserRepository, DynUserService}, config::{CommentConfig, Hashing, JwtConfig, UploadConfig}, repository::{BookmarkRepository, CategoryRepository, CommentRepository, FollowRepository, ImageVariantRepository, MediaRepository, NotificationRepository, PostRepository, PostRevisionRepository, ReactionRepository, TagRepository, UserRepository}, service::{AuthService, BookmarkService, CategoryService, CommentService, FollowService, MediaService, NotificationService, PostRevisionService, PostService, ReactionService, TagService, TrashService, UploadService, UserService}, storage::LocalStorage};
 
 
 
@@ -10,36 +10,135 @@ use crate::{abstract_trait::{DynAuthService, DynCategoryRepository, DynCategoryS
 pub struct DependenciesInject{
     pub category_service: DynCategoryService,
     pub post_service: DynPostsService,
+    pub post_revision_service: DynPostRevisionService,
+    pub tag_service: DynTagService,
+    pub upload_service: DynUploadService,
+    pub media_service: DynMediaService,
     pub comment_service: DynCommentService,
     pub user_service: DynUserService,
     pub auth_service: DynAuthService,
+    pub trash_service: DynTrashService,
+    pub reaction_service: DynReactionService,
+    pub bookmark_service: DynBookmarkService,
+    pub follow_service: DynFollowService,
+    pub notification_service: DynNotificationService,
 }
 
 impl DependenciesInject{
-    pub fn new(pool: DatabaseConnection, hashing: Hashing, jwt_config: JwtConfig) -> Self{
+    pub fn new(pool: DatabaseConnection, hashing: Hashing, jwt_config: JwtConfig, upload_config: UploadConfig, trash_retention_days: i64, comment_config: CommentConfig) -> Self{
         let category_repository =
             Arc::new(CategoryRepository::new(pool.clone())) as DynCategoryRepository;
 
         let category_service =
-            Arc::new(CategoryService::new(category_repository)) as DynCategoryService;
+            Arc::new(CategoryService::new(category_repository.clone())) as DynCategoryService;
+
+        let tag_repository = Arc::new(TagRepository::new(pool.clone())) as DynTagRepository;
+
+        let tag_service = Arc::new(TagService::new(tag_repository.clone())) as DynTagService;
 
         let post_repository = Arc::new(PostRepository::new(pool.clone())) as DynPostsRepository;
 
-        let post_service = Arc::new(PostService::new(post_repository.clone())) as DynPostsService;
+        let storage = Arc::new(LocalStorage::new(&upload_config.upload_dir)) as DynStorage;
 
-        let comment_repository =
-            Arc::new(CommentRepository::new(pool.clone())) as DynCommentRepository;
-        let comment_service =
-            Arc::new(CommentService::new(comment_repository)) as DynCommentService;
+        let image_variant_repository =
+            Arc::new(ImageVariantRepository::new(pool.clone())) as DynImageVariantRepository;
+
+        let media_repository = Arc::new(MediaRepository::new(pool.clone())) as DynMediaRepository;
+
+        let media_service = Arc::new(MediaService::new(
+            media_repository.clone(),
+            image_variant_repository.clone(),
+            storage.clone(),
+        )) as DynMediaService;
 
+        let upload_service = Arc::new(UploadService::new(
+            storage.clone(),
+            media_repository.clone(),
+            image_variant_repository.clone(),
+            upload_config.image_sizes.clone(),
+        )) as DynUploadService;
 
         let user_repository = Arc::new(UserRepository::new(pool.clone())) as DynUserRepository;
 
+        let comment_repository =
+            Arc::new(CommentRepository::new(pool.clone())) as DynCommentRepository;
+
+        let reaction_repository =
+            Arc::new(ReactionRepository::new(pool.clone())) as DynReactionRepository;
+        let reaction_service = Arc::new(ReactionService::new(
+            reaction_repository.clone(),
+            post_repository.clone(),
+            comment_repository.clone(),
+        )) as DynReactionService;
+
+        let bookmark_repository =
+            Arc::new(BookmarkRepository::new(pool.clone())) as DynBookmarkRepository;
+
+        let notification_repository =
+            Arc::new(NotificationRepository::new(pool.clone())) as DynNotificationRepository;
+        let notification_service =
+            Arc::new(NotificationService::new(notification_repository)) as DynNotificationService;
+
+        let comment_service = Arc::new(CommentService::new(
+            comment_repository.clone(),
+            post_repository.clone(),
+            category_repository.clone(),
+            user_repository.clone(),
+            reaction_repository.clone(),
+            notification_service.clone(),
+            comment_config,
+        )) as DynCommentService;
+
+        let post_service = Arc::new(PostService::new(
+            post_repository.clone(),
+            tag_repository.clone(),
+            image_variant_repository.clone(),
+            media_repository.clone(),
+            comment_repository.clone(),
+            user_repository.clone(),
+            category_repository.clone(),
+            comment_service.clone(),
+            reaction_repository.clone(),
+            bookmark_repository.clone(),
+            storage.clone(),
+        )) as DynPostsService;
+
+        let bookmark_service = Arc::new(BookmarkService::new(
+            bookmark_repository,
+            post_repository.clone(),
+            post_service.clone(),
+        )) as DynBookmarkService;
+
+        let follow_repository = Arc::new(FollowRepository::new(pool.clone())) as DynFollowRepository;
+        let follow_service = Arc::new(FollowService::new(
+            follow_repository,
+            user_repository.clone(),
+            category_repository.clone(),
+            post_repository.clone(),
+            post_service.clone(),
+        )) as DynFollowService;
+
+        let post_revision_repository =
+            Arc::new(PostRevisionRepository::new(pool.clone())) as DynPostRevisionRepository;
+        let post_revision_service = Arc::new(PostRevisionService::new(
+            post_revision_repository,
+            post_repository.clone(),
+            post_service.clone(),
+        )) as DynPostRevisionService;
+
         let user_service = Arc::new(UserService::new(user_repository.clone())) as DynUserService;
 
         let auth_service = Arc::new(AuthService::new(user_repository.clone(), hashing, jwt_config));
 
+        let trash_service = Arc::new(TrashService::new(
+            post_repository.clone(),
+            category_repository.clone(),
+            comment_repository.clone(),
+            user_repository.clone(),
+            trash_retention_days,
+        )) as DynTrashService;
+
 
-        Self { category_service, post_service, comment_service, user_service, auth_service }
+        Self { category_service, post_service, post_revision_service, tag_service, upload_service, media_service, comment_service, user_service, auth_service, trash_service, reaction_service, bookmark_service, follow_service, notification_service }
     }
 }
\ No newline at end of file
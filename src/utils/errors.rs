@@ -35,6 +35,12 @@ pub enum AppError {
 
     #[error("Email already exists")]
     EmailAlreadyExists,
+
+    #[error("Validation error: {0}")]
+    ValidationError(String),
+
+    #[error("Storage error: {0}")]
+    StorageError(#[from] std::io::Error),
 }
 
 impl Serialize for AppError {
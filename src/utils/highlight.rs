@@ -0,0 +1,75 @@
+use std::collections::HashSet;
+use std::sync::OnceLock;
+
+use syntect::highlighting::ThemeSet;
+use syntect::html::{css_for_theme_with_class_style, ClassStyle, ClassedHTMLGenerator};
+use syntect::parsing::SyntaxSet;
+use syntect::util::LinesWithEndings;
+
+pub const DEFAULT_HIGHLIGHT_THEME: &str = "InspiredGitHub";
+
+// Prefixed so highlight classes cannot collide with a frontend's own stylesheet
+const CLASS_STYLE: ClassStyle = ClassStyle::SpacedPrefixed { prefix: "hl-" };
+
+static HIGHLIGHTER: OnceLock<Highlighter> = OnceLock::new();
+
+pub struct Highlighter {
+    syntaxes: SyntaxSet,
+    themes: ThemeSet,
+    theme: String,
+    languages: Option<HashSet<String>>,
+}
+
+impl Highlighter {
+    fn new(theme: &str, languages: Option<Vec<String>>) -> Self {
+        Highlighter {
+            syntaxes: SyntaxSet::load_defaults_newlines(),
+            themes: ThemeSet::load_defaults(),
+            theme: theme.to_string(),
+            languages: languages
+                .map(|languages| languages.iter().map(|language| language.trim().to_lowercase()).collect()),
+        }
+    }
+}
+
+// Sets the theme and allowed languages, must run before the first post is rendered to take effect
+pub fn init_highlighter(theme: &str, languages: Option<Vec<String>>) {
+    if HIGHLIGHTER.set(Highlighter::new(theme, languages)).is_err() {
+        tracing::warn!("Syntax highlighter was already initialised, keeping the existing configuration");
+    }
+}
+
+fn highlighter() -> &'static Highlighter {
+    HIGHLIGHTER.get_or_init(|| Highlighter::new(DEFAULT_HIGHLIGHT_THEME, None))
+}
+
+// Class-annotated HTML for a code block, or None when the language is unknown or not enabled
+pub fn highlight_code(code: &str, language: &str) -> Option<String> {
+    let highlighter = highlighter();
+    let language = language.trim().to_lowercase();
+
+    if highlighter.languages.as_ref().is_some_and(|languages| !languages.contains(&language)) {
+        return None;
+    }
+
+    let syntax = highlighter.syntaxes.find_syntax_by_token(&language)?;
+    let mut generator = ClassedHTMLGenerator::new_with_class_style(syntax, &highlighter.syntaxes, CLASS_STYLE);
+
+    for line in LinesWithEndings::from(code) {
+        generator.parse_html_for_line_which_includes_newline(line).ok()?;
+    }
+
+    Some(generator.finalize())
+}
+
+// Stylesheet matching the highlight classes, for the configured theme unless another is named
+pub fn highlight_css(theme: Option<&str>) -> Option<String> {
+    let highlighter = highlighter();
+    let theme = highlighter.themes.themes.get(theme.unwrap_or(&highlighter.theme))?;
+
+    css_for_theme_with_class_style(theme, CLASS_STYLE).ok()
+}
+
+pub fn highlight_themes() -> Vec<String> {
+    highlighter().themes.themes.keys().cloned().collect()
+}
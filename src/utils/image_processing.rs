@@ -0,0 +1,261 @@
+use std::io::Cursor;
+
+use image::codecs::jpeg::JpegEncoder;
+use image::codecs::png::PngEncoder;
+use image::codecs::webp::WebPEncoder;
+use image::imageops::FilterType;
+use image::metadata::Orientation;
+use image::{DynamicImage, ImageDecoder, ImageReader, ImageResult};
+
+const JPEG_QUALITY: u8 = 82;
+
+// PNG chunks that only carry metadata, dropped on upload
+const PNG_METADATA_CHUNKS: [&[u8]; 5] = [b"eXIf", b"tEXt", b"zTXt", b"iTXt", b"tIME"];
+
+#[derive(Debug, Clone)]
+pub struct ImageSize {
+    pub name: String,
+    pub width: u32,
+}
+
+#[derive(Debug)]
+pub struct ImageVariant {
+    pub name: String,
+    pub extension: &'static str,
+    pub content_type: &'static str,
+    pub width: u32,
+    pub height: u32,
+    pub bytes: Vec<u8>,
+}
+
+// Removes EXIF, XMP and text metadata without re-encoding the image.
+// JPEG orientation is the one tag kept, otherwise phone photos would show up rotated.
+// Returns None when the file is not structured like the format it claims to be.
+pub fn strip_metadata(bytes: &[u8], extension: &str) -> Option<Vec<u8>> {
+    match extension {
+        "jpg" => strip_jpeg(bytes),
+        "png" => strip_png(bytes),
+        "webp" => strip_webp(bytes),
+        _ => Some(bytes.to_vec()),
+    }
+}
+
+fn strip_jpeg(bytes: &[u8]) -> Option<Vec<u8>> {
+    let mut output = bytes.get(..2)?.to_vec();
+    let mut position = 2;
+    let mut orientation = None;
+
+    loop {
+        let marker = *bytes.get(position + 1)?;
+
+        if *bytes.get(position)? != 0xFF {
+            return None;
+        }
+
+        // Fill bytes may pad a marker
+        if marker == 0xFF {
+            position += 1;
+            continue;
+        }
+
+        // Start of scan, the entropy coded data that follows has no segments we care about
+        if marker == 0xDA {
+            output.extend_from_slice(&bytes[position..]);
+            break;
+        }
+
+        // The length covers its own two bytes, anything shorter is a malformed segment
+        let length = u16::from_be_bytes([*bytes.get(position + 2)?, *bytes.get(position + 3)?]) as usize;
+        if length < 2 {
+            return None;
+        }
+        let segment = bytes.get(position..position + 2 + length)?;
+
+        match marker {
+            // APP1 holds EXIF and XMP, APP13 holds IPTC
+            0xE1 => {
+                if let Some(exif) = segment.get(4..).and_then(|payload| payload.strip_prefix(b"Exif\0\0")) {
+                    orientation = orientation.or(Orientation::from_exif_chunk(exif));
+                }
+            }
+            0xED => {}
+            _ => {
+                if marker == 0xDB || (0xC0..=0xCF).contains(&marker) {
+                    // First table or frame segment, a good place to put the orientation back
+                    if let Some(orientation) = orientation.take().filter(|o| *o != Orientation::NoTransforms) {
+                        output.extend_from_slice(&orientation_segment(orientation));
+                    }
+                }
+                output.extend_from_slice(segment);
+            }
+        }
+
+        position += 2 + length;
+    }
+
+    Some(output)
+}
+
+// Minimal big endian EXIF block with a single IFD entry for the orientation
+fn orientation_segment(orientation: Orientation) -> Vec<u8> {
+    let mut payload = b"Exif\0\0MM\0\x2a\0\0\0\x08\0\x01\x01\x12\0\x03\0\0\0\x01".to_vec();
+    payload.extend_from_slice(&[0, orientation.to_exif(), 0, 0]);
+    payload.extend_from_slice(&[0, 0, 0, 0]);
+
+    let mut segment = vec![0xFF, 0xE1];
+    segment.extend_from_slice(&((payload.len() + 2) as u16).to_be_bytes());
+    segment.extend_from_slice(&payload);
+    segment
+}
+
+fn strip_png(bytes: &[u8]) -> Option<Vec<u8>> {
+    let mut output = bytes.get(..8)?.to_vec();
+    let mut position = 8;
+
+    while position < bytes.len() {
+        let length = u32::from_be_bytes(bytes.get(position..position + 4)?.try_into().ok()?) as usize;
+        let chunk = bytes.get(position..position + 12 + length)?;
+
+        if !PNG_METADATA_CHUNKS.contains(&&chunk[4..8]) {
+            output.extend_from_slice(chunk);
+        }
+
+        position += 12 + length;
+    }
+
+    Some(output)
+}
+
+fn strip_webp(bytes: &[u8]) -> Option<Vec<u8>> {
+    let mut output = bytes.get(..12)?.to_vec();
+    let mut position = 12;
+
+    while position < bytes.len() {
+        let length = u32::from_le_bytes(bytes.get(position + 4..position + 8)?.try_into().ok()?) as usize;
+        let padded = length + length % 2;
+        let chunk = bytes.get(position..(position + 8 + padded).min(bytes.len()))?;
+
+        match &chunk[..4] {
+            b"EXIF" | b"XMP " => {}
+            b"VP8X" => {
+                let mut chunk = chunk.to_vec();
+                // Clear the EXIF and XMP presence flags now that the chunks are gone
+                *chunk.get_mut(8)? &= !0b0000_1100;
+                output.extend_from_slice(&chunk);
+            }
+            _ => output.extend_from_slice(chunk),
+        }
+
+        position += 8 + padded;
+    }
+
+    let riff_size = (output.len() - 8) as u32;
+    output[4..8].copy_from_slice(&riff_size.to_le_bytes());
+
+    Some(output)
+}
+
+// Reads only the image header, cheap enough to run inside a request
+pub fn image_dimensions(bytes: &[u8]) -> Option<(u32, u32)> {
+    ImageReader::new(Cursor::new(bytes)).with_guessed_format().ok()?.into_dimensions().ok()
+}
+
+fn decode(bytes: &[u8]) -> ImageResult<DynamicImage> {
+    let mut decoder = ImageReader::new(Cursor::new(bytes)).with_guessed_format()?.into_decoder()?;
+    let orientation = decoder.orientation()?;
+
+    let mut image = DynamicImage::from_decoder(decoder)?;
+    image.apply_orientation(orientation);
+
+    Ok(image)
+}
+
+fn encode(image: &DynamicImage, extension: &str) -> ImageResult<Vec<u8>> {
+    let mut bytes = Vec::new();
+
+    match extension {
+        "jpg" => image.to_rgb8().write_with_encoder(JpegEncoder::new_with_quality(&mut bytes, JPEG_QUALITY))?,
+        "png" => image.write_with_encoder(PngEncoder::new(&mut bytes))?,
+        _ => image.to_rgba8().write_with_encoder(WebPEncoder::new_lossless(&mut bytes))?,
+    }
+
+    Ok(bytes)
+}
+
+// Downscaled copies of an image for each configured size, in the original format and as WebP.
+// Sizes wider than the original are skipped, upscaling only costs bandwidth.
+pub fn render_variants(bytes: &[u8], extension: &str, sizes: &[ImageSize]) -> ImageResult<Vec<ImageVariant>> {
+    let image = decode(bytes)?;
+    let mut variants = Vec::new();
+
+    let formats: &[(&'static str, &'static str)] = match extension {
+        "jpg" => &[("jpg", "image/jpeg"), ("webp", "image/webp")],
+        "webp" => &[("webp", "image/webp")],
+        _ => &[("png", "image/png"), ("webp", "image/webp")],
+    };
+
+    for size in sizes.iter().filter(|size| size.width < image.width()) {
+        let resized = image.resize(size.width, u32::MAX, FilterType::Lanczos3);
+
+        for (extension, content_type) in formats {
+            variants.push(ImageVariant {
+                name: size.name.clone(),
+                extension,
+                content_type,
+                width: resized.width(),
+                height: resized.height(),
+                bytes: encode(&resized, extension)?,
+            });
+        }
+    }
+
+    Ok(variants)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SOI: [u8; 2] = [0xFF, 0xD8];
+    const SOS: [u8; 6] = [0xFF, 0xDA, 0x00, 0x02, 0x12, 0x34];
+
+    fn jpeg(segments: &[&[u8]]) -> Vec<u8> {
+        let mut bytes = SOI.to_vec();
+        segments.iter().for_each(|segment| bytes.extend_from_slice(segment));
+        bytes.extend_from_slice(&SOS);
+        bytes
+    }
+
+    #[test]
+    fn strip_jpeg_rejects_truncated_app1_segments() {
+        for length in [0u8, 1] {
+            let bytes = jpeg(&[&[0xFF, 0xE1, 0x00, length]]);
+            assert_eq!(strip_jpeg(&bytes), None);
+        }
+    }
+
+    #[test]
+    fn strip_jpeg_accepts_an_empty_app1_segment() {
+        let bytes = jpeg(&[&[0xFF, 0xE1, 0x00, 0x02]]);
+        assert_eq!(strip_jpeg(&bytes), Some(jpeg(&[])));
+    }
+
+    #[test]
+    fn strip_jpeg_drops_metadata_and_keeps_other_segments() {
+        let comment: &[u8] = &[0xFF, 0xFE, 0x00, 0x04, b'h', b'i'];
+        let bytes = jpeg(&[
+            &[0xFF, 0xE1, 0x00, 0x08, b'E', b'x', b'i', b'f', 0, 0],
+            &[0xFF, 0xED, 0x00, 0x03, 0x00],
+            comment,
+        ]);
+        assert_eq!(strip_jpeg(&bytes), Some(jpeg(&[comment])));
+    }
+
+    #[test]
+    fn strip_jpeg_keeps_the_orientation_before_the_first_table() {
+        let exif = orientation_segment(Orientation::Rotate90);
+        let table: &[u8] = &[0xFF, 0xDB, 0x00, 0x03, 0x00];
+        let bytes = jpeg(&[&exif, table]);
+        assert_eq!(strip_jpeg(&bytes), Some(jpeg(&[&exif, table])));
+    }
+}
@@ -0,0 +1,202 @@
+use std::collections::HashSet;
+
+use pulldown_cmark::{html, CodeBlockKind, CowStr, Event, Options, Parser, Tag, TagEnd};
+
+use crate::entities::sea_orm_active_enums::BodyFormat;
+
+use super::{generate_slug, highlight_code};
+
+const EXCERPT_LENGTH: usize = 200;
+const WORDS_PER_MINUTE: usize = 200;
+
+fn markdown_options() -> Options {
+    Options::ENABLE_TABLES | Options::ENABLE_STRIKETHROUGH | Options::ENABLE_FOOTNOTES
+}
+
+fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+
+    escaped
+}
+
+#[derive(Debug, Clone)]
+pub struct Heading {
+    pub level: u8,
+    pub title: String,
+    pub anchor: String,
+}
+
+// Headings of a markdown body in document order. Anchors derive from the heading text,
+// repeated headings get a numeric suffix so every anchor on the page stays unique.
+pub fn headings(body: &str) -> Vec<Heading> {
+    let mut headings = Vec::new();
+    let mut used = HashSet::new();
+    let mut current: Option<(u8, String)> = None;
+
+    for event in Parser::new_ext(body, markdown_options()) {
+        match event {
+            Event::Start(Tag::Heading { level, .. }) => current = Some((level as u8, String::new())),
+            Event::Text(value) | Event::Code(value) => {
+                if let Some((_, title)) = current.as_mut() {
+                    title.push_str(&value);
+                }
+            }
+            Event::End(TagEnd::Heading(_)) => {
+                if let Some((level, title)) = current.take() {
+                    let title = title.trim().to_string();
+                    let base = match generate_slug(&title) {
+                        slug if slug.is_empty() => "section".to_string(),
+                        slug => slug,
+                    };
+
+                    let mut anchor = base.clone();
+                    let mut suffix = 1;
+                    while !used.insert(anchor.clone()) {
+                        anchor = format!("{}-{}", base, suffix);
+                        suffix += 1;
+                    }
+
+                    headings.push(Heading { level, title, anchor });
+                }
+            }
+            _ => {}
+        }
+    }
+
+    headings
+}
+
+// Renders a post body to HTML that is safe to embed as is, whatever the source format
+pub fn render_body(body: &str, format: BodyFormat) -> String {
+    let html = match format {
+        BodyFormat::Markdown => {
+            let mut anchors = headings(body).into_iter().map(|heading| heading.anchor);
+            let mut events = Vec::new();
+            let mut code_block: Option<(CowStr, String)> = None;
+
+            for event in Parser::new_ext(body, markdown_options()) {
+                match event {
+                    Event::Start(Tag::Heading { level, classes, attrs, .. }) => events.push(Event::Start(Tag::Heading {
+                        level,
+                        id: anchors.next().map(Into::into),
+                        classes,
+                        attrs,
+                    })),
+                    Event::Start(Tag::CodeBlock(CodeBlockKind::Fenced(info))) if !info.trim().is_empty() => {
+                        code_block = Some((info, String::new()));
+                    }
+                    Event::Text(text) if code_block.is_some() => {
+                        if let Some((_, code)) = code_block.as_mut() {
+                            code.push_str(&text);
+                        }
+                    }
+                    Event::End(TagEnd::CodeBlock) if code_block.is_some() => {
+                        let Some((info, code)) = code_block.take() else { continue };
+                        let language = info.split([' ', ',']).next().unwrap_or_default();
+
+                        // Unknown or disabled languages fall back to a plain code block
+                        match highlight_code(&code, language) {
+                            Some(highlighted) => events.push(Event::Html(
+                                format!(
+                                    "<pre class=\"hl-code\"><code class=\"language-{}\">{}</code></pre>\n",
+                                    escape_html(language),
+                                    highlighted
+                                )
+                                .into(),
+                            )),
+                            None => {
+                                events.push(Event::Start(Tag::CodeBlock(CodeBlockKind::Fenced(info.clone()))));
+                                events.push(Event::Text(code.into()));
+                                events.push(Event::End(TagEnd::CodeBlock));
+                            }
+                        }
+                    }
+                    event => events.push(event),
+                }
+            }
+
+            let mut html = String::new();
+            html::push_html(&mut html, events.into_iter());
+            html
+        }
+        BodyFormat::Plain => body
+            .split("\n\n")
+            .map(str::trim)
+            .filter(|paragraph| !paragraph.is_empty())
+            .map(|paragraph| format!("<p>{}</p>\n", escape_html(paragraph).replace('\n', "<br>\n")))
+            .collect(),
+    };
+
+    // Heading ids are kept so the table of contents can link to them, classes carry the highlighting
+    ammonia::Builder::default()
+        .add_tag_attributes("h1", &["id"])
+        .add_tag_attributes("h2", &["id"])
+        .add_tag_attributes("h3", &["id"])
+        .add_tag_attributes("h4", &["id"])
+        .add_tag_attributes("h5", &["id"])
+        .add_tag_attributes("h6", &["id"])
+        .add_tag_attributes("pre", &["class"])
+        .add_tag_attributes("code", &["class"])
+        .add_tag_attributes("span", &["class"])
+        .clean(&html)
+        .to_string()
+}
+
+// The readable text of a body, with markup removed and whitespace collapsed
+pub fn plain_text(body: &str, format: BodyFormat) -> String {
+    let text = match format {
+        BodyFormat::Markdown => {
+            let mut text = String::new();
+
+            for event in Parser::new_ext(body, markdown_options()) {
+                match event {
+                    Event::Text(value) | Event::Code(value) => text.push_str(&value),
+                    Event::SoftBreak | Event::HardBreak | Event::Rule => text.push(' '),
+                    Event::End(TagEnd::Paragraph | TagEnd::Heading(_) | TagEnd::Item | TagEnd::TableCell | TagEnd::CodeBlock) => {
+                        text.push(' ')
+                    }
+                    _ => {}
+                }
+            }
+
+            text
+        }
+        BodyFormat::Plain => body.to_string(),
+    };
+
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+pub fn excerpt(text: &str) -> String {
+    if text.chars().count() <= EXCERPT_LENGTH {
+        return text.to_string();
+    }
+
+    let cut: String = text.chars().take(EXCERPT_LENGTH).collect();
+    let cut = match cut.rfind(' ') {
+        Some(index) => &cut[..index],
+        None => cut.as_str(),
+    };
+
+    format!("{}…", cut.trim_end_matches(|c: char| c.is_ascii_punctuation()))
+}
+
+pub fn word_count(text: &str) -> usize {
+    text.split_whitespace().count()
+}
+
+// Reading time in whole minutes, rounded up so short posts still read as one minute
+pub fn reading_time(word_count: usize) -> usize {
+    word_count.div_ceil(WORDS_PER_MINUTE)
+}
@@ -1,9 +1,15 @@
 mod errors;
 mod di;
+mod highlight;
+mod image_processing;
 mod log;
+mod markdown;
 mod slug;
 
 pub use self::errors::{AppError, ConnectionManagerError};
 pub use self::di::DependenciesInject;
+pub use self::highlight::{highlight_code, highlight_css, highlight_themes, init_highlighter, DEFAULT_HIGHLIGHT_THEME};
+pub use self::image_processing::{image_dimensions, render_variants, strip_metadata, ImageSize, ImageVariant};
 pub use self::log::tracing;
+pub use self::markdown::{excerpt, headings, plain_text, reading_time, render_body, word_count, Heading};
 pub use self::slug::generate_slug;
\ No newline at end of file